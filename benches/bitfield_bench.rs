@@ -7,8 +7,12 @@
 //! - bitfield_copy_words (1024 bits) target: <60ns
 //! - Logical operations (AND, OR, XOR)
 //! - PartialEq comparison (critical for change tracking)
+//! - SIMD kernels per instruction set (scalar vs SSE2 vs AVX2 vs AVX-512)
+//!
+//! Run with `--features word64` to measure 64-bit words.
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use gnomics::simd::{self, SimdLevel};
 use gnomics::{bitfield_copy_words, BitField};
 use rand::SeedableRng;
 
@@ -297,6 +301,119 @@ fn bench_bitfield_copy_words(c: &mut Criterion) {
     group.finish();
 }
 
+// =============================================================================
+// SIMD Kernels (per instruction set)
+// =============================================================================
+
+/// Instruction sets supported by this CPU, lowest first
+fn simd_levels() -> Vec<SimdLevel> {
+    [
+        SimdLevel::Scalar,
+        SimdLevel::Sse2,
+        SimdLevel::Avx2,
+        SimdLevel::Avx512,
+    ]
+    .into_iter()
+    .filter(|&l| l <= SimdLevel::detect())
+    .collect()
+}
+
+fn bench_simd_kernels(c: &mut Criterion) {
+    for size in [1024, 16384].iter() {
+        let mut ba1 = BitField::new(*size);
+        let mut ba2 = BitField::new(*size);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        ba1.random_set_pct(&mut rng, 0.2);
+        ba2.random_set_pct(&mut rng, 0.2);
+        let mut dst = ba1.clone();
+
+        let mut group = c.benchmark_group(format!("simd/{}", size));
+
+        for level in simd_levels() {
+            simd::set_simd_level(level);
+
+            group.bench_function(BenchmarkId::new("popcount", level.name()), |b| {
+                b.iter(|| black_box(simd::popcount(black_box(ba1.words()))));
+            });
+
+            group.bench_function(BenchmarkId::new("and_popcount", level.name()), |b| {
+                b.iter(|| {
+                    black_box(simd::and_popcount(
+                        black_box(ba1.words()),
+                        black_box(ba2.words()),
+                    ))
+                });
+            });
+
+            group.bench_function(BenchmarkId::new("and_assign", level.name()), |b| {
+                b.iter(|| simd::and_assign(black_box(dst.words_mut()), black_box(ba2.words())));
+            });
+        }
+
+        // simd::copy is memcpy at every level; compare it against explicit
+        // vector loops to check that it stays the faster choice
+        group.bench_function(BenchmarkId::new("copy", "memcpy"), |b| {
+            b.iter(|| simd::copy(black_box(dst.words_mut()), black_box(ba1.words())));
+        });
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if SimdLevel::detect() >= SimdLevel::Avx2 {
+                group.bench_function(BenchmarkId::new("copy", "avx2_loop"), |b| {
+                    b.iter(|| unsafe {
+                        copy_loops::avx2(black_box(dst.words_mut()), black_box(ba1.words()))
+                    });
+                });
+            }
+            if SimdLevel::detect() >= SimdLevel::Avx512 {
+                group.bench_function(BenchmarkId::new("copy", "avx512_loop"), |b| {
+                    b.iter(|| unsafe {
+                        copy_loops::avx512(black_box(dst.words_mut()), black_box(ba1.words()))
+                    });
+                });
+            }
+        }
+
+        group.finish();
+    }
+
+    simd::set_simd_level(SimdLevel::detect());
+}
+
+/// Explicit unaligned load/store copy loops, the reference `simd::copy` is
+/// measured against
+#[cfg(target_arch = "x86_64")]
+mod copy_loops {
+    use gnomics::Word;
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn avx2(dst: &mut [Word], src: &[Word]) {
+        let n = 32 / std::mem::size_of::<Word>();
+        let chunks = src.len() / n;
+        let pd = dst.as_mut_ptr() as *mut __m256i;
+        let ps = src.as_ptr() as *const __m256i;
+
+        for i in 0..chunks {
+            _mm256_storeu_si256(pd.add(i), _mm256_loadu_si256(ps.add(i)));
+        }
+        dst[chunks * n..].copy_from_slice(&src[chunks * n..]);
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub unsafe fn avx512(dst: &mut [Word], src: &[Word]) {
+        let n = 64 / std::mem::size_of::<Word>();
+        let chunks = src.len() / n;
+        let pd = dst.as_mut_ptr() as *mut __m512i;
+        let ps = src.as_ptr() as *const __m512i;
+
+        for i in 0..chunks {
+            _mm512_storeu_si512(pd.add(i), _mm512_loadu_si512(ps.add(i)));
+        }
+        dst[chunks * n..].copy_from_slice(&src[chunks * n..]);
+    }
+}
+
 // =============================================================================
 // Random Operations
 // =============================================================================
@@ -370,6 +487,7 @@ criterion_group!(
     bench_equality_same,
     bench_equality_different,
     bench_bitfield_copy_words,
+    bench_simd_kernels,
     bench_random_set_num,
     bench_random_shuffle,
     bench_find_next_set_bit
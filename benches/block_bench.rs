@@ -5,6 +5,7 @@
 //! - pull (per child): <120ns for 1024 bits
 //! - children_changed: <10ns per child
 //! - store with comparison: <100ns for 1024 bits
+//! - overlap_all: dense (overlap_conn) vs inverted index across input sparsities
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use gnomics::{BlockInput, BlockOutput, BlockMemory};
@@ -169,6 +170,64 @@ fn bench_block_memory_learn(c: &mut Criterion) {
     });
 }
 
+fn bench_block_memory_overlap_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("BlockMemory::overlap_all");
+
+    // PatternPooler-sized memory: 1024 dendrites over 2048 input bits
+    let mut memory = BlockMemory::new(1024, 0, 20, 2, 1, 0.3);
+    let mut rng = StdRng::seed_from_u64(42);
+    memory.init_pooled_conn(2048, &mut rng, 0.8, 0.5);
+    memory.init_index();
+
+    let mut overlaps = vec![0; 1024];
+
+    for pct_active in [0.02, 0.05, 0.10, 0.20].iter() {
+        let mut input = gnomics::BitField::new(2048);
+        input.random_set_pct(&mut rng, *pct_active);
+        let label = format!("{:.0}% active", pct_active * 100.0);
+
+        group.bench_with_input(BenchmarkId::new("dense", &label), &input, |b, input| {
+            b.iter(|| {
+                for (d, overlap) in overlaps.iter_mut().enumerate() {
+                    *overlap = memory.overlap_conn(d, black_box(input));
+                }
+                black_box(&overlaps);
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("index", &label), &input, |b, input| {
+            b.iter(|| {
+                memory.overlap_index(black_box(input), &mut overlaps);
+                black_box(&overlaps);
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("auto", &label), &input, |b, input| {
+            b.iter(|| {
+                memory.overlap_all(black_box(input), &mut overlaps);
+                black_box(&overlaps);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_block_memory_learn_index(c: &mut Criterion) {
+    c.bench_function("BlockMemory::learn (with index)", |b| {
+        let mut memory = BlockMemory::new(100, 50, 20, 2, 1, 0.3);
+        let mut rng = StdRng::seed_from_u64(42);
+        memory.init_pooled(1024, &mut rng, 0.8, 0.5);
+        memory.init_index();
+
+        let mut input = gnomics::BitField::new(1024);
+        input.random_set_num(&mut rng, 128);
+
+        b.iter(|| {
+            memory.learn(black_box(0), black_box(&input), black_box(&mut rng));
+        });
+    });
+}
+
 fn bench_end_to_end(c: &mut Criterion) {
     let mut group = c.benchmark_group("end-to-end pipeline");
 
@@ -222,6 +281,8 @@ criterion_group!(
     bench_store,
     bench_block_memory_overlap,
     bench_block_memory_learn,
+    bench_block_memory_overlap_all,
+    bench_block_memory_learn_index,
     bench_end_to_end
 );
 criterion_main!(benches);
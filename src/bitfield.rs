@@ -6,8 +6,12 @@
 //!
 //! # Design
 //!
-//! - Uses `BitVec<u32, Lsb0>` for storage (32-bit words, LSB-first ordering)
-//! - Bit indexing: word_idx = bit_idx / 32, bit_offset = bit_idx % 32
+//! - Uses `BitVec<Word, Lsb0>` for storage (`Word` words, LSB-first ordering)
+//! - Bit indexing: word_idx = bit_idx / BITS_PER_WORD,
+//!   bit_offset = bit_idx % BITS_PER_WORD
+//! - `Word` is `u32` by default; the `word64` feature switches it to `u64`
+//! - Popcount, AND-popcount, logical ops and word copies dispatch to SIMD
+//!   kernels (see [`crate::simd`])
 //! - Optimized for bulk operations and word-level copying
 //! - Critical for Phase 2 lazy copying in `BlockInput::pull()`
 //!
@@ -36,26 +40,19 @@ use std::cell::Cell;
 use std::ops::{BitAnd, BitOr, BitXor, Not};
 
 /// Word type for bit storage (32-bit unsigned integer)
+#[cfg(not(feature = "word64"))]
 pub type Word = u32;
 
+/// Word type for bit storage (64-bit unsigned integer, `word64` feature)
+#[cfg(feature = "word64")]
+pub type Word = u64;
+
 /// Number of bits per word
-pub const BITS_PER_WORD: usize = 32;
+pub const BITS_PER_WORD: usize = Word::BITS as usize;
 
 /// Maximum word value
 pub const WORD_MAX: Word = Word::MAX;
 
-/// Get word index from bit position
-#[inline(always)]
-const fn get_word_idx(bit_pos: usize) -> usize {
-    bit_pos >> 5 // bit_pos / 32
-}
-
-/// Get bit index within word from bit position
-#[inline(always)]
-const fn get_bit_idx(bit_pos: usize) -> usize {
-    bit_pos & 31 // bit_pos % 32
-}
-
 /// Create bitmask with n bits set (from LSB)
 #[inline(always)]
 const fn bitmask(n: usize) -> Word {
@@ -94,8 +91,8 @@ fn default_version() -> Cell<u64> {
 /// handle overflow (which is negligible: ~584 years at 1GHz mutation rate).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BitField {
-    /// Underlying bitvec storage with u32 words, LSB0 ordering
-    bv: BitVec<u32, Lsb0>,
+    /// Underlying bitvec storage with Word-sized words, LSB0 ordering
+    bv: BitVec<Word, Lsb0>,
 
     /// Version counter incremented on every modification (wrapping)
     /// Skipped during serialization - reset to 0 on deserialization
@@ -364,6 +361,16 @@ impl BitField {
     /// OPTIMIZED: Uses word-level iteration with early exit for 2x speedup.
     pub fn get_acts(&self) -> Vec<usize> {
         let mut acts = Vec::with_capacity(self.num_set());
+        self.get_acts_into(&mut acts);
+        acts
+    }
+
+    /// Get indices of all set bits into an existing vector.
+    ///
+    /// Clears `acts` first. Does not allocate when `acts` already has enough
+    /// capacity, which makes it suitable for per-step hot paths.
+    pub fn get_acts_into(&self, acts: &mut Vec<usize>) {
+        acts.clear();
         let words = self.bv.as_raw_slice();
 
         for (word_idx, word) in words.iter().enumerate() {
@@ -382,8 +389,6 @@ impl BitField {
                 }
             }
         }
-
-        acts
     }
 
     // =========================================================================
@@ -392,10 +397,11 @@ impl BitField {
 
     /// Count number of set bits (population count).
     ///
-    /// Uses hardware popcount instruction for performance.
+    /// Uses SIMD popcount kernels (see [`crate::simd`]) for performance.
     #[inline]
     pub fn num_set(&self) -> usize {
-        self.bv.count_ones()
+        // Padding bits beyond num_bits are always zero
+        crate::simd::popcount(self.bv.as_raw_slice())
     }
 
     /// Count number of cleared bits.
@@ -418,14 +424,7 @@ impl BitField {
             "BitFields must have same word count"
         );
 
-        let self_words = self.bv.as_raw_slice();
-        let other_words = other.bv.as_raw_slice();
-
-        self_words
-            .iter()
-            .zip(other_words.iter())
-            .map(|(a, b)| (a & b).count_ones() as usize)
-            .sum()
+        crate::simd::and_popcount(self.bv.as_raw_slice(), other.bv.as_raw_slice())
     }
 
     // =========================================================================
@@ -446,85 +445,24 @@ impl BitField {
 
     /// Find next set bit in range [beg, beg+len), with wrapping.
     ///
+    /// A range running past the end is searched as [beg, num_bits) then
+    /// [0, beg + len - num_bits).
+    ///
     /// Returns Some(index) if found, None otherwise.
     pub fn find_next_set_bit_range(&self, beg: usize, len: usize) -> Option<usize> {
         debug_assert!(beg < self.bv.len());
         debug_assert!(len > 0 && len <= self.bv.len());
 
-        // Calculate end position (with wrapping)
-        let mut end = beg + len;
-        if end > self.bv.len() {
-            end -= self.bv.len();
-        }
-
-        // Calculate number of words to check
-        let num_words = (len / BITS_PER_WORD) + 1;
-
-        let beg_word = get_word_idx(beg);
-        let beg_bit = get_bit_idx(beg);
-        let end_word = get_word_idx(end);
-        let end_bit = get_bit_idx(end);
-
-        let beg_mask = bitmask(beg_bit);
-        let end_mask = bitmask(end_bit);
-
-        let words = self.bv.as_raw_slice();
-
-        // Single word case
-        if num_words == 1 {
-            // Check high side of beg
-            let mut word = words[beg_word] & !beg_mask;
-            if word > 0 {
-                return Some(beg_word * BITS_PER_WORD + word.trailing_zeros() as usize);
-            }
-
-            // Check low side of end
-            word = words[beg_word] & end_mask;
-            if word > 0 {
-                return Some(beg_word * BITS_PER_WORD + word.trailing_zeros() as usize);
-            }
-
-            return None;
-        }
-
-        // Multiple words case
-
-        // Check high side of first word
-        let mut word = words[beg_word] & !beg_mask;
-        if word > 0 {
-            return Some(beg_word * BITS_PER_WORD + word.trailing_zeros() as usize);
-        }
-
-        // Check middle words
-        let mid = if beg_word == end_word {
-            num_words
-        } else {
-            num_words - 1
-        };
-
-        for i in 1..mid {
-            let j = beg_word + i;
-            let w = if j < words.len() {
-                j
-            } else {
-                j - words.len()
-            };
-
-            word = words[w];
-            if word > 0 {
-                return Some(w * BITS_PER_WORD + word.trailing_zeros() as usize);
-            }
-        }
-
-        // Check low side of last word (if within bounds)
-        if end_word < words.len() {
-            word = words[end_word] & end_mask;
-            if word > 0 {
-                return Some(end_word * BITS_PER_WORD + word.trailing_zeros() as usize);
-            }
+        let num_bits = self.bv.len();
+        let end = beg + len;
+        if end <= num_bits {
+            return self.bv[beg..end].first_one().map(|i| beg + i);
         }
 
-        None
+        self.bv[beg..]
+            .first_one()
+            .map(|i| beg + i)
+            .or_else(|| self.bv[..end - num_bits].first_one())
     }
 
     // =========================================================================
@@ -590,6 +528,21 @@ impl BitField {
         self.bv.as_raw_slice()
     }
 
+    /// Copy the contents of `src` into this BitField.
+    ///
+    /// Equivalent to `*self = src.clone()` (including the version counter) but
+    /// reuses the existing storage when both have the same size, so it does not
+    /// allocate.
+    #[inline]
+    pub fn copy_from(&mut self, src: &BitField) {
+        if self.bv.len() == src.bv.len() {
+            crate::simd::copy(self.bv.as_raw_mut_slice(), src.bv.as_raw_slice());
+        } else {
+            self.bv.clone_from(&src.bv);
+        }
+        self.version.set(src.version.get());
+    }
+
     /// Get direct mutable access to word storage.
     ///
     /// CRITICAL: Used for efficient word-level copying in Phase 2.
@@ -654,17 +607,12 @@ impl BitAnd for &BitField {
 
     /// Bitwise AND operation.
     ///
-    /// OPTIMIZED: Uses word-level operations on raw slices for 10x speedup.
+    /// OPTIMIZED: Uses SIMD word-level operations on raw slices.
     fn bitand(self, rhs: Self) -> Self::Output {
         assert_eq!(self.bv.len(), rhs.bv.len(), "BitFields must have same size");
 
         let mut result = self.clone();
-        let result_words = result.bv.as_raw_mut_slice();
-        let rhs_words = rhs.bv.as_raw_slice();
-
-        for (a, b) in result_words.iter_mut().zip(rhs_words) {
-            *a &= *b;
-        }
+        crate::simd::and_assign(result.bv.as_raw_mut_slice(), rhs.bv.as_raw_slice());
 
         result
     }
@@ -683,17 +631,12 @@ impl BitOr for &BitField {
 
     /// Bitwise OR operation.
     ///
-    /// OPTIMIZED: Uses word-level operations on raw slices for 10x speedup.
+    /// OPTIMIZED: Uses SIMD word-level operations on raw slices.
     fn bitor(self, rhs: Self) -> Self::Output {
         assert_eq!(self.bv.len(), rhs.bv.len(), "BitFields must have same size");
 
         let mut result = self.clone();
-        let result_words = result.bv.as_raw_mut_slice();
-        let rhs_words = rhs.bv.as_raw_slice();
-
-        for (a, b) in result_words.iter_mut().zip(rhs_words) {
-            *a |= *b;
-        }
+        crate::simd::or_assign(result.bv.as_raw_mut_slice(), rhs.bv.as_raw_slice());
 
         result
     }
@@ -712,17 +655,12 @@ impl BitXor for &BitField {
 
     /// Bitwise XOR operation.
     ///
-    /// OPTIMIZED: Uses word-level operations on raw slices for 10x speedup.
+    /// OPTIMIZED: Uses SIMD word-level operations on raw slices.
     fn bitxor(self, rhs: Self) -> Self::Output {
         assert_eq!(self.bv.len(), rhs.bv.len(), "BitFields must have same size");
 
         let mut result = self.clone();
-        let result_words = result.bv.as_raw_mut_slice();
-        let rhs_words = rhs.bv.as_raw_slice();
-
-        for (a, b) in result_words.iter_mut().zip(rhs_words) {
-            *a ^= *b;
-        }
+        crate::simd::xor_assign(result.bv.as_raw_mut_slice(), rhs.bv.as_raw_slice());
 
         result
     }
@@ -808,7 +746,7 @@ pub fn bitfield_copy_words(
     let dst_words = dst.words_mut();
     let src_words = src.words();
 
-    dst_words[dst_start..dst_end].copy_from_slice(&src_words[src_start..src_end]);
+    crate::simd::copy(&mut dst_words[dst_start..dst_end], &src_words[src_start..src_end]);
 }
 
 #[cfg(test)]
@@ -820,7 +758,7 @@ mod tests {
     fn test_new() {
         let ba = BitField::new(1024);
         assert_eq!(ba.num_bits(), 1024);
-        assert_eq!(ba.num_words(), 32);
+        assert_eq!(ba.num_words(), 1024 / BITS_PER_WORD);
         assert_eq!(ba.num_set(), 0);
     }
 
@@ -1020,6 +958,35 @@ mod tests {
         assert_eq!(dst.words()[3], src.words()[1]);
     }
 
+    #[test]
+    fn test_copy_from() {
+        let mut src = BitField::new(128);
+        src.set_acts(&[1, 40, 127]);
+
+        // Same size: copies in place
+        let mut dst = BitField::new(128);
+        dst.set_bit(5);
+        dst.copy_from(&src);
+        assert_eq!(dst, src);
+        assert_eq!(dst.version(), src.version());
+
+        // Different size: resizes to match
+        let mut dst = BitField::new(32);
+        dst.copy_from(&src);
+        assert_eq!(dst.num_bits(), 128);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    fn test_get_acts_into() {
+        let mut ba = BitField::new(128);
+        ba.set_acts(&[3, 64, 100]);
+
+        let mut acts = vec![9, 9, 9, 9];
+        ba.get_acts_into(&mut acts);
+        assert_eq!(acts, vec![3, 64, 100]);
+    }
+
     #[test]
     fn test_resize() {
         let mut ba = BitField::new(32);
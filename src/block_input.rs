@@ -21,6 +21,9 @@
 //! - **Speedup: 4.9× for this simple case**
 //! - Real-world: **5-100× depending on change rate**
 //!
+//! Children may keep dense or sparse history (see `BlockOutput::set_sparse`);
+//! sparse children are scattered into their word range instead of memcpy'd.
+//!
 //! # Rc<RefCell<>> Pattern
 //!
 //! Uses `Rc<RefCell<BlockOutput>>` for shared ownership:
@@ -55,8 +58,9 @@
 //! input.pull();
 //! ```
 
-use crate::bitfield::BitField;
+use crate::bitfield::{BitField, BITS_PER_WORD};
 use crate::block_output::BlockOutput;
+use crate::sparse_bitfield::bitfield_copy_sparse;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -174,8 +178,9 @@ impl BlockInput {
         self.word_offsets.push(word_offset);
         self.word_sizes.push(word_size);
 
-        // Resize state to accommodate all children (use current size + child bits)
-        let num_bits = self.state.num_bits() + child_bits;
+        // Resize state to accommodate all children (children start on word
+        // boundaries, so include any padding after the previous child)
+        let num_bits = word_offset * BITS_PER_WORD + child_bits;
         self.state.resize(num_bits);
     }
 
@@ -221,12 +226,23 @@ impl BlockInput {
                 continue; // Skip memcpy!
             }
 
+            if child.is_sparse() {
+                // Sparse child: clear its word range and scatter active bits
+                bitfield_copy_sparse(
+                    &mut self.state,
+                    child.get_sparse(self.times[i]),
+                    self.word_offsets[i],
+                    self.word_sizes[i],
+                );
+                continue;
+            }
+
             let src_bitfield = child.get_bitfield(self.times[i]);
 
             // Fast word-level copy (equivalent to C++ bitfield_copy)
             bitfield_copy_words(
                 &mut self.state,
-                src_bitfield,
+                &src_bitfield,
                 self.word_offsets[i],
                 0,
                 self.word_sizes[i],
@@ -321,6 +337,22 @@ impl BlockInput {
             .collect()
     }
 
+    /// Get the source block IDs of all children with the output each reads.
+    ///
+    /// Like `get_source_blocks()`, but tells connections to a block's
+    /// predicted output apart from those to its main output.
+    pub fn get_source_ports(
+        &self,
+    ) -> Vec<(crate::network::BlockId, crate::network_config::OutputPort)> {
+        self.children
+            .iter()
+            .filter_map(|child| {
+                let child = child.borrow();
+                child.source_block_id().map(|id| (id, child.source_port()))
+            })
+            .collect()
+    }
+
     /// Get information about all children (output references and time offsets).
     ///
     /// Returns a vector of ChildInfo structs containing the output reference
@@ -386,6 +418,21 @@ pub trait ContextAccess {
     fn context_mut(&mut self) -> &mut BlockInput;
 }
 
+/// Trait for blocks that contain an apical (top-down feedback) BlockInput
+///
+/// Mirrors [`ContextAccess`] for blocks whose statelets can be biased by
+/// patterns from higher-level blocks.
+///
+pub trait ApicalAccess {
+    /// Get immutable reference to apical BlockInput.
+    fn apical(&self) -> &BlockInput;
+
+    /// Get mutable reference to apical BlockInput.
+    ///
+    /// Allows connecting child blocks to this block's apical input.
+    fn apical_mut(&mut self) -> &mut BlockInput;
+}
+
 /// Fast word-level copy between BitFields (equivalent to C++ bitfield_copy).
 ///
 /// **CRITICAL**: This compiles to a single memcpy call, matching C++ performance.
@@ -396,7 +443,7 @@ pub trait ContextAccess {
 /// * `src` - Source BitField
 /// * `dst_word_offset` - Starting word position in destination
 /// * `src_word_offset` - Starting word position in source
-/// * `num_words` - Number of 32-bit words to copy
+/// * `num_words` - Number of words to copy
 ///
 /// # Performance
 ///
@@ -420,7 +467,7 @@ fn bitfield_copy_words(
     let src_end = src_start + num_words;
 
     // Direct slice copy - compiles to memcpy
-    dst_words[dst_start..dst_end].copy_from_slice(&src_words[src_start..src_end]);
+    crate::simd::copy(&mut dst_words[dst_start..dst_end], &src_words[src_start..src_end]);
 }
 
 #[cfg(test)]
@@ -446,7 +493,7 @@ mod tests {
 
         assert_eq!(input.num_children(), 1);
         assert_eq!(input.word_offsets[0], 0);
-        assert_eq!(input.word_sizes[0], 4); // 128 bits = 4 words
+        assert_eq!(input.word_sizes[0], 128 / BITS_PER_WORD);
     }
 
     #[test]
@@ -466,10 +513,10 @@ mod tests {
 
         assert_eq!(input.num_children(), 2);
         assert_eq!(input.word_offsets[0], 0);
-        assert_eq!(input.word_offsets[1], 4);
-        assert_eq!(input.word_sizes[0], 4);
-        assert_eq!(input.word_sizes[1], 8);
-        assert_eq!(input.state.num_bits(), (4 + 8) * 32);
+        assert_eq!(input.word_offsets[1], 128 / BITS_PER_WORD);
+        assert_eq!(input.word_sizes[0], 128 / BITS_PER_WORD);
+        assert_eq!(input.word_sizes[1], 256 / BITS_PER_WORD);
+        assert_eq!(input.state.num_bits(), 128 + 256);
     }
 
     #[test]
@@ -517,8 +564,36 @@ mod tests {
         // First 32 bits from output1
         assert_eq!(input.state.get_bit(5), 1);
 
-        // Next 32 bits from output2 (offset by 32)
-        assert_eq!(input.state.get_bit(32 + 10), 1);
+        // Next child starts at the following word boundary
+        assert_eq!(input.state.get_bit(BITS_PER_WORD + 10), 1);
+    }
+
+    #[test]
+    fn test_pull_unaligned_children() {
+        let mut input = BlockInput::new();
+
+        // Children narrower than a word still start on word boundaries
+        let outputs: Vec<_> = (0..3)
+            .map(|i| {
+                let mut output = BlockOutput::new();
+                output.setup(2, 20);
+                output.state.set_bit(15 + i);
+                output.store();
+                Rc::new(RefCell::new(output))
+            })
+            .collect();
+        for output in &outputs {
+            input.add_child(Rc::clone(output), 0);
+        }
+
+        assert_eq!(input.state.num_bits(), 2 * BITS_PER_WORD + 20);
+
+        input.pull();
+
+        assert_eq!(
+            input.state.get_acts(),
+            vec![15, BITS_PER_WORD + 16, 2 * BITS_PER_WORD + 17]
+        );
     }
 
     #[test]
@@ -649,8 +724,8 @@ mod tests {
         src.set_bit(10);
         src.set_bit(70); // This is in word 2 (bits 64-95)
 
-        // Copy all 4 words (128 bits)
-        bitfield_copy_words(&mut dst, &src, 0, 0, 4);
+        // Copy all words (128 bits)
+        bitfield_copy_words(&mut dst, &src, 0, 0, 128 / BITS_PER_WORD);
 
         assert_eq!(dst.get_bit(5), 1);
         assert_eq!(dst.get_bit(10), 1);
@@ -665,7 +740,8 @@ mod tests {
         src.set_bit(5);
 
         // Copy to offset position in dst
-        bitfield_copy_words(&mut dst, &src, 2, 0, 2); // Offset by 2 words (64 bits)
+        let num_words = 64 / BITS_PER_WORD;
+        bitfield_copy_words(&mut dst, &src, num_words, 0, num_words); // Offset by 64 bits
 
         assert_eq!(dst.get_bit(5), 0); // Original position
         assert_eq!(dst.get_bit(64 + 5), 1); // Offset position
@@ -26,6 +26,16 @@
 //! - `punish(d, input)` - Weaken matching receptors
 //! - `learn_move(d, input)` - Move dead receptors to new positions
 //!
+//! # Inverted Index
+//!
+//! Inputs are typically only 2-10% active, yet `overlap()` and `overlap_conn()`
+//! touch every receptor (or every word of `d_conns`) of every dendrite. After
+//! `init_index()`, BlockMemory also maintains an input bit → connected receptor
+//! index that is updated incrementally by `learn()`, `learn_move()` and
+//! `punish()`. `overlap_all()` then accumulates overlaps for all dendrites from
+//! the active input bits only, falling back to the dense path when the input is
+//! too dense for the index to pay off.
+//!
 //! # Examples
 //!
 //! ```
@@ -53,7 +63,7 @@
 //! memory.learn(0, &input, &mut rng);
 //! ```
 
-use crate::bitfield::BitField;
+use crate::bitfield::{BitField, BITS_PER_WORD};
 use crate::utils::{max, min};
 use rand::rngs::StdRng;
 use rand::Rng;
@@ -64,6 +74,9 @@ pub const PERM_MIN: u8 = 0;
 /// Maximum permanence value
 pub const PERM_MAX: u8 = 99;
 
+/// Relative cost of one inverted-index increment vs. one dense word/receptor check
+const INDEX_COST: usize = 2;
+
 /// BlockMemory implements synaptic learning with dendrites and receptors.
 ///
 /// Each dendrite has `num_rpd` receptors that connect to different positions
@@ -87,10 +100,13 @@ pub struct BlockMemory {
     r_perms: Vec<u8>,    // Receptor permanences (flattened 2D: [num_d][num_rpd])
     d_conns: Vec<BitField>, // Optional dendrite connections (for fast overlap)
     lmask: BitField,     // Learning mask (which receptors can learn)
+    r_index: Vec<Vec<u32>>, // Optional inverted index: input bit -> connected receptors
+    available: BitField, // Scratch buffer for learn_move (reused every call)
 
     // Flags
     init_flag: bool,
     conns_flag: bool, // Using connection BitFields?
+    index_flag: bool, // Maintaining inverted index?
 }
 
 impl BlockMemory {
@@ -136,8 +152,11 @@ impl BlockMemory {
             r_perms: vec![0; num_r],
             d_conns: Vec::new(),
             lmask: BitField::new(num_rpd),
+            r_index: Vec::new(),
+            available: BitField::new(0),
             init_flag: false,
             conns_flag: false,
+            index_flag: false,
         }
     }
 
@@ -164,6 +183,10 @@ impl BlockMemory {
         self.r_perms.fill(0);
 
         self.init_flag = true;
+
+        if self.index_flag {
+            self.rebuild_index();
+        }
     }
 
     /// Initialize with optional connection BitFields.
@@ -257,6 +280,10 @@ impl BlockMemory {
         }
 
         self.init_flag = true;
+
+        if self.index_flag {
+            self.rebuild_index();
+        }
     }
 
     /// Initialize pooled with connection BitFields.
@@ -280,6 +307,189 @@ impl BlockMemory {
         }
     }
 
+    /// Append dendrites with pooled connectivity.
+    ///
+    /// New dendrites sample their receptors like `init_pooled()` with the
+    /// current `num_rpd`. Existing dendrites and their permanences are left
+    /// untouched, so blocks can grow at runtime without disturbing what they
+    /// have learned.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_new` - Number of dendrites to add
+    /// * `rng` - Random number generator
+    /// * `pct_conn` - Initially connected percentage (0.0-1.0, typically 0.5)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::BlockMemory;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut memory = BlockMemory::new(10, 0, 20, 2, 1, 0.3);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// memory.init_pooled(1024, &mut rng, 0.8, 0.5);
+    ///
+    /// let first = memory.perms(0);
+    /// memory.add_dendrites_pooled(5, &mut rng, 0.5);
+    /// assert_eq!(memory.num_dendrites(), 15);
+    /// assert_eq!(memory.perms(0), first);
+    /// ```
+    pub fn add_dendrites_pooled(&mut self, num_new: usize, rng: &mut StdRng, pct_conn: f64) {
+        assert!(self.init_flag, "must call init() first");
+        assert!((0.0..=1.0).contains(&pct_conn), "pct_conn must be 0.0-1.0");
+
+        let d_beg = self.num_d;
+        self.num_d += num_new;
+        self.num_r = self.num_d * self.num_rpd;
+        self.state.resize(self.num_d);
+        self.r_addrs.reserve(num_new * self.num_rpd);
+        self.r_perms.reserve(num_new * self.num_rpd);
+
+        // Initialize each new dendrite as in init_pooled()
+        let num_init = (self.num_rpd as f64 * pct_conn) as usize;
+        let mut rand_addrs: Vec<usize> = (0..self.num_i).collect();
+
+        for _ in 0..num_new {
+            crate::utils::shuffle_indices(&mut rand_addrs, rng);
+
+            for (j, &addr) in rand_addrs.iter().take(self.num_rpd).enumerate() {
+                self.r_addrs.push(addr);
+                self.r_perms.push(if j < num_init {
+                    self.perm_thr
+                } else {
+                    self.perm_thr.saturating_sub(1)
+                });
+            }
+        }
+
+        if self.conns_flag {
+            self.d_conns.resize(self.num_d, BitField::new(self.num_i));
+            for d in d_beg..self.num_d {
+                self.update_conns(d);
+            }
+        }
+
+        if self.index_flag {
+            self.rebuild_index();
+        }
+    }
+
+    /// Initialize with local (topological) connectivity.
+    ///
+    /// Each dendrite's receptors address exactly the input bits listed in its
+    /// receptive field instead of a random pool. All fields must have the same
+    /// size, which becomes `num_rpd`.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_i` - Number of input bits
+    /// * `rng` - Random number generator
+    /// * `fields` - Receptive field (input bit addresses) for each dendrite
+    /// * `pct_conn` - Initially connected percentage (0.0-1.0, typically 0.5)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::BlockMemory;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut memory = BlockMemory::new(2, 0, 20, 2, 1, 0.3);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    ///
+    /// // Dendrite 0 watches bits 0-3, dendrite 1 watches bits 4-7
+    /// let fields = vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]];
+    /// memory.init_local(8, &mut rng, &fields, 0.5);
+    /// assert!(memory.addrs(1).iter().all(|&a| a >= 4));
+    /// ```
+    pub fn init_local(
+        &mut self,
+        num_i: usize,
+        rng: &mut StdRng,
+        fields: &[Vec<usize>],
+        pct_conn: f64,
+    ) {
+        assert!(num_i > 0, "num_i must be > 0");
+        assert_eq!(fields.len(), self.num_d, "need one receptive field per dendrite");
+        assert!((0.0..=1.0).contains(&pct_conn), "pct_conn must be 0.0-1.0");
+
+        let num_rpd = fields[0].len();
+        assert!(
+            fields.iter().all(|f| f.len() == num_rpd),
+            "receptive fields must all have the same size"
+        );
+
+        self.num_i = num_i;
+        self.num_rpd = num_rpd;
+        self.num_r = self.num_d * self.num_rpd;
+
+        // Resize arrays
+        self.r_addrs.clear();
+        self.r_addrs.resize(self.num_r, 0);
+        self.r_perms.clear();
+        self.r_perms.resize(self.num_r, 0);
+        self.lmask.resize(self.num_rpd);
+
+        // Setup learning mask
+        let num_learn = (self.num_rpd as f64 * self.pct_learn) as usize;
+        self.lmask.clear_all();
+        self.lmask.set_range(0, num_learn);
+
+        // Initialize each dendrite from its receptive field
+        let num_init = (self.num_rpd as f64 * pct_conn) as usize;
+        let mut field_addrs: Vec<usize> = Vec::with_capacity(self.num_rpd);
+
+        for (d, field) in fields.iter().enumerate() {
+            // Shuffle so the initially connected subset is random within the field
+            field_addrs.clear();
+            field_addrs.extend_from_slice(field);
+            crate::utils::shuffle_indices(&mut field_addrs, rng);
+
+            let r_beg = d * self.num_rpd;
+
+            for (j, &addr) in field_addrs.iter().enumerate() {
+                assert!(addr < num_i, "receptive field address out of bounds");
+                let r = r_beg + j;
+                self.r_addrs[r] = addr;
+
+                if j < num_init {
+                    self.r_perms[r] = self.perm_thr; // Connected
+                } else {
+                    self.r_perms[r] = self.perm_thr.saturating_sub(1); // Just below threshold
+                }
+            }
+        }
+
+        self.init_flag = true;
+
+        if self.index_flag {
+            self.rebuild_index();
+        }
+    }
+
+    /// Initialize local connectivity with connection BitFields.
+    pub fn init_local_conn(
+        &mut self,
+        num_i: usize,
+        rng: &mut StdRng,
+        fields: &[Vec<usize>],
+        pct_conn: f64,
+    ) {
+        self.init_local(num_i, rng, fields, pct_conn);
+
+        // Allocate and update connection BitFields
+        self.d_conns.clear();
+        self.d_conns.resize(self.num_d, BitField::new(num_i));
+
+        self.conns_flag = true;
+
+        for d in 0..self.num_d {
+            self.update_conns(d);
+        }
+    }
+
     /// Compute overlap between dendrite and input.
     ///
     /// Returns count of receptors that are both:
@@ -324,6 +534,132 @@ impl BlockMemory {
         self.d_conns[d].num_similar(input)
     }
 
+    /// Enable the inverted index (input bit -> connected receptors).
+    ///
+    /// Call after one of the `init*()` methods. Once enabled, the index is kept
+    /// in sync by every learning method and rebuilt by later `init*()` calls
+    /// and `set_all_permanences()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::BlockMemory;
+    /// use gnomics::BitField;
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let mut memory = BlockMemory::new(64, 0, 20, 2, 1, 0.3);
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// memory.init_pooled(1024, &mut rng, 0.8, 0.5);
+    /// memory.init_index();
+    ///
+    /// let mut input = BitField::new(1024);
+    /// input.set_acts(&[3, 50, 400, 777]);
+    ///
+    /// let mut overlaps = vec![0; 64];
+    /// memory.overlap_all(&input, &mut overlaps);
+    /// assert_eq!(overlaps[5], memory.overlap(5, &input));
+    /// ```
+    pub fn init_index(&mut self) {
+        assert!(self.init_flag, "must call init() first");
+
+        self.index_flag = true;
+        self.rebuild_index();
+    }
+
+    /// Check if the inverted index is enabled.
+    #[inline]
+    pub fn has_index(&self) -> bool {
+        self.index_flag
+    }
+
+    /// Compute overlaps for all dendrites at once.
+    ///
+    /// Uses the inverted index when it is enabled and the active input bits
+    /// reach few enough connected receptors to beat the dense path; otherwise
+    /// uses `overlap_conn()` (if connection BitFields exist) or `overlap()` per
+    /// dendrite. Results are identical either way.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input BitField
+    /// * `overlaps` - Output overlap score per dendrite (length `num_d`)
+    pub fn overlap_all(&self, input: &BitField, overlaps: &mut [usize]) {
+        assert!(self.init_flag, "must call init() first");
+        assert_eq!(overlaps.len(), self.num_d, "overlaps length must be num_d");
+
+        if self.index_flag && self.index_cheaper(input) {
+            self.overlap_index(input, overlaps);
+        } else if self.conns_flag {
+            for (d, overlap) in overlaps.iter_mut().enumerate() {
+                *overlap = self.d_conns[d].num_similar(input);
+            }
+        } else {
+            for (d, overlap) in overlaps.iter_mut().enumerate() {
+                *overlap = self.overlap(d, input);
+            }
+        }
+    }
+
+    /// Estimate whether the inverted index beats the dense path for `input`.
+    ///
+    /// Index work is the number of connected receptors on active bits; dense
+    /// work is one word per dendrite connection BitField or one check per receptor.
+    fn index_cheaper(&self, input: &BitField) -> bool {
+        let dense_work = if self.conns_flag {
+            self.num_d * self.num_i.div_ceil(BITS_PER_WORD)
+        } else {
+            self.num_r
+        };
+
+        let mut index_work = 0;
+        for (w, &word) in input.words().iter().enumerate() {
+            let mut bits = word;
+            while bits != 0 {
+                let addr = w * BITS_PER_WORD + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+
+                if addr < self.num_i {
+                    index_work += self.r_index[addr].len() * INDEX_COST;
+                    if index_work >= dense_work {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Compute overlaps for all dendrites using the inverted index.
+    ///
+    /// Cost is proportional to the number of connected receptors on active
+    /// input bits rather than to `num_d * num_rpd`. Results match `overlap()`.
+    ///
+    /// Requires `init_index()`.
+    pub fn overlap_index(&self, input: &BitField, overlaps: &mut [usize]) {
+        assert!(self.init_flag && self.index_flag, "must call init_index() first");
+        assert_eq!(overlaps.len(), self.num_d, "overlaps length must be num_d");
+
+        overlaps.fill(0);
+
+        for (w, &word) in input.words().iter().enumerate() {
+            let mut bits = word;
+            while bits != 0 {
+                let addr = w * BITS_PER_WORD + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+
+                if addr >= self.num_i {
+                    continue;
+                }
+
+                for &r in &self.r_index[addr] {
+                    overlaps[r as usize / self.num_rpd] += 1;
+                }
+            }
+        }
+    }
+
     /// Learn pattern on dendrite.
     ///
     /// Updates receptor permanences:
@@ -351,6 +687,7 @@ impl BlockMemory {
         for (l, r) in (r_beg..r_end).enumerate() {
             if self.lmask.get_bit(l) > 0 {
                 let addr = self.r_addrs[r];
+                let was_conn = self.r_perms[r] >= self.perm_thr;
 
                 if input.get_bit(addr) > 0 {
                     // Active: increment permanence
@@ -359,6 +696,8 @@ impl BlockMemory {
                     // Inactive: decrement permanence
                     self.r_perms[r] = max(self.r_perms[r], self.perm_dec) - self.perm_dec;
                 }
+
+                self.update_index(r, addr, was_conn);
             }
         }
     }
@@ -392,19 +731,21 @@ impl BlockMemory {
         let r_end = r_beg + self.num_rpd;
 
         // Find available input bits (not already covered by receptors)
-        let mut available = input.clone();
+        self.available.copy_from(input);
         for r in r_beg..r_end {
             if self.r_perms[r] > 0 {
-                available.clear_bit(self.r_addrs[r]);
+                self.available.clear_bit(self.r_addrs[r]);
             }
         }
 
         // Learn
         for (l, r) in (r_beg..r_end).enumerate() {
             if self.lmask.get_bit(l) > 0 {
+                let addr = self.r_addrs[r];
+                let was_conn = self.r_perms[r] >= self.perm_thr;
+
                 if self.r_perms[r] > 0 {
                     // Normal learning
-                    let addr = self.r_addrs[r];
                     if input.get_bit(addr) > 0 {
                         self.r_perms[r] = min(self.r_perms[r] + self.perm_inc, PERM_MAX);
                     } else {
@@ -414,10 +755,10 @@ impl BlockMemory {
                     // Move receptor to new active bit
                     let mut search_addr = next_addr;
                     loop {
-                        if available.get_bit(search_addr) > 0 {
+                        if self.available.get_bit(search_addr) > 0 {
                             self.r_addrs[r] = search_addr;
                             self.r_perms[r] = self.perm_thr;
-                            available.clear_bit(search_addr);
+                            self.available.clear_bit(search_addr);
                             next_addr = rng.gen_range(0..self.num_i);
                             break;
                         }
@@ -427,6 +768,8 @@ impl BlockMemory {
                         }
                     }
                 }
+
+                self.update_index(r, addr, was_conn);
             }
         }
     }
@@ -459,7 +802,9 @@ impl BlockMemory {
                 let addr = self.r_addrs[r];
                 if input.get_bit(addr) > 0 {
                     // Active: decrement by perm_inc (stronger punishment)
+                    let was_conn = self.r_perms[r] >= self.perm_thr;
                     self.r_perms[r] = max(self.r_perms[r], self.perm_inc) - self.perm_inc;
+                    self.update_index(r, addr, was_conn);
                 }
             }
         }
@@ -472,6 +817,39 @@ impl BlockMemory {
         self.update_conns(d);
     }
 
+    /// Imprint a pattern on a dendrite, replacing what it has learned.
+    ///
+    /// Receptors on active input bits are set to the permanence threshold
+    /// (just connected) and all others to zero. Used to allocate a dendrite
+    /// to a new pattern in one step.
+    pub fn imprint(&mut self, d: usize, input: &BitField) {
+        assert!(self.init_flag);
+        assert!(d < self.num_d);
+
+        let r_beg = d * self.num_rpd;
+        let r_end = r_beg + self.num_rpd;
+
+        for r in r_beg..r_end {
+            let addr = self.r_addrs[r];
+            let was_conn = self.r_perms[r] >= self.perm_thr;
+
+            self.r_perms[r] = if input.get_bit(addr) > 0 {
+                self.perm_thr
+            } else {
+                0
+            };
+
+            self.update_index(r, addr, was_conn);
+        }
+    }
+
+    /// Imprint with connection update.
+    pub fn imprint_conn(&mut self, d: usize, input: &BitField) {
+        assert!(self.conns_flag);
+        self.imprint(d, input);
+        self.update_conns(d);
+    }
+
     /// Clear dendrite activation state.
     pub fn clear(&mut self) {
         self.state.clear_all();
@@ -483,6 +861,12 @@ impl BlockMemory {
         self.num_d
     }
 
+    /// Get number of receptors per dendrite.
+    #[inline]
+    pub fn num_rpd(&self) -> usize {
+        self.num_rpd
+    }
+
     /// Get receptor addresses for a dendrite.
     pub fn addrs(&self, d: usize) -> Vec<usize> {
         assert!(self.init_flag);
@@ -522,11 +906,21 @@ impl BlockMemory {
         bytes += self.r_addrs.capacity() * std::mem::size_of::<usize>();
         bytes += self.r_perms.capacity() * std::mem::size_of::<u8>();
         bytes += self.lmask.memory_usage();
+        bytes += self.available.memory_usage();
 
         if self.conns_flag && !self.d_conns.is_empty() {
             bytes += self.d_conns.len() * self.d_conns[0].memory_usage();
         }
 
+        if self.index_flag {
+            bytes += self.r_index.capacity() * std::mem::size_of::<Vec<u32>>();
+            bytes += self
+                .r_index
+                .iter()
+                .map(|rs| rs.capacity() * std::mem::size_of::<u32>())
+                .sum::<usize>();
+        }
+
         bytes
     }
 
@@ -584,6 +978,73 @@ impl BlockMemory {
             }
         }
 
+        if self.index_flag {
+            self.rebuild_index();
+        }
+
+        Ok(())
+    }
+
+    /// Export all receptor addresses for serialization.
+    ///
+    /// Returns a 2D array of input bit addresses: `[dendrite][receptor]`.
+    /// `learn_move()` relocates receptors, so learned state that only keeps
+    /// permanences loses what moved receptors point at.
+    pub fn get_all_addresses(&self) -> Vec<Vec<usize>> {
+        self.r_addrs
+            .chunks(self.num_rpd.max(1))
+            .take(self.num_d)
+            .map(<[usize]>::to_vec)
+            .collect()
+    }
+
+    /// Import all receptor addresses from serialization.
+    ///
+    /// Sets receptor addresses from a 2D array: `[dendrite][receptor]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if dimensions don't match or an address is not an input bit.
+    pub fn set_all_addresses(&mut self, addresses: &[Vec<usize>]) -> crate::Result<()> {
+        if addresses.len() != self.num_d {
+            return Err(crate::GnomicsError::Other(format!(
+                "Address array length mismatch: expected {} dendrites, got {}",
+                self.num_d,
+                addresses.len()
+            )));
+        }
+
+        for (d, dendrite_addrs) in addresses.iter().enumerate() {
+            if dendrite_addrs.len() != self.num_rpd {
+                return Err(crate::GnomicsError::Other(format!(
+                    "Address receptor count mismatch for dendrite {}: expected {}, got {}",
+                    d,
+                    self.num_rpd,
+                    dendrite_addrs.len()
+                )));
+            }
+            if let Some(&addr) = dendrite_addrs.iter().find(|&&a| a >= self.num_i) {
+                return Err(crate::GnomicsError::Other(format!(
+                    "Receptor address {} for dendrite {} exceeds input size {}",
+                    addr, d, self.num_i
+                )));
+            }
+        }
+
+        for (d, dendrite_addrs) in addresses.iter().enumerate() {
+            let r_beg = d * self.num_rpd;
+            let r_end = r_beg + self.num_rpd;
+            self.r_addrs[r_beg..r_end].copy_from_slice(dendrite_addrs);
+
+            if self.conns_flag {
+                self.update_conns(d);
+            }
+        }
+
+        if self.index_flag {
+            self.rebuild_index();
+        }
+
         Ok(())
     }
 
@@ -605,6 +1066,57 @@ impl BlockMemory {
             }
         }
     }
+
+    /// Rebuild the inverted index from receptor addresses and permanences.
+    fn rebuild_index(&mut self) {
+        assert!(self.index_flag);
+        assert!(self.num_r <= u32::MAX as usize, "too many receptors for index");
+
+        self.r_index.clear();
+        self.r_index.resize(self.num_i, Vec::new());
+
+        // Reserve room for every receptor currently at each address, so
+        // permanence changes from learn() never reallocate. learn_move() can
+        // still grow a list past its reservation when it relocates receptors.
+        let mut counts = vec![0usize; self.num_i];
+        for &addr in &self.r_addrs {
+            counts[addr] += 1;
+        }
+        for (rs, &n) in self.r_index.iter_mut().zip(&counts) {
+            rs.reserve_exact(n);
+        }
+
+        for r in 0..self.num_r {
+            if self.r_perms[r] >= self.perm_thr {
+                self.r_index[self.r_addrs[r]].push(r as u32);
+            }
+        }
+    }
+
+    /// Update the inverted index after receptor `r` changed.
+    ///
+    /// `old_addr` and `was_conn` describe the receptor before the change.
+    #[inline]
+    fn update_index(&mut self, r: usize, old_addr: usize, was_conn: bool) {
+        if !self.index_flag {
+            return;
+        }
+
+        let addr = self.r_addrs[r];
+        let is_conn = self.r_perms[r] >= self.perm_thr;
+        let moved = addr != old_addr;
+
+        if was_conn && (!is_conn || moved) {
+            let rs = &mut self.r_index[old_addr];
+            if let Some(pos) = rs.iter().position(|&x| x as usize == r) {
+                rs.swap_remove(pos);
+            }
+        }
+
+        if is_conn && (!was_conn || moved) {
+            self.r_index[addr].push(r as u32);
+        }
+    }
 }
 
 /// Trait for blocks that contain a BlockMemory
@@ -655,6 +1167,55 @@ mod tests {
         assert_eq!(memory.num_rpd, 500);
     }
 
+    #[test]
+    fn test_add_dendrites_pooled() {
+        let mut memory = BlockMemory::new(4, 0, 20, 2, 1, 0.5);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        memory.init_pooled_conn(256, &mut rng, 0.5, 0.5);
+        memory.init_index();
+        let before = memory.get_all_permanences();
+
+        memory.add_dendrites_pooled(2, &mut rng, 0.5);
+        assert_eq!(memory.num_dendrites(), 6);
+        assert_eq!(memory.r_addrs.len(), 6 * 128);
+        assert_eq!(memory.get_all_permanences()[..4], before[..]);
+
+        // Grown dendrites take part in overlap like the originals
+        let mut input = BitField::new(256);
+        input.set_acts(&memory.addrs(5)[..10]);
+        let mut overlaps = vec![0; 6];
+        memory.overlap_all(&input, &mut overlaps);
+        assert_eq!(overlaps[5], memory.overlap(5, &input));
+        assert_eq!(overlaps[5], memory.overlap_conn(5, &input));
+    }
+
+    #[test]
+    fn test_init_local() {
+        let mut memory = BlockMemory::new(3, 0, 20, 2, 1, 0.3);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let fields = vec![vec![0, 1, 2, 3], vec![2, 3, 4, 5], vec![6, 7, 8, 9]];
+        memory.init_local_conn(10, &mut rng, &fields, 1.0);
+
+        assert!(memory.init_flag);
+        assert_eq!(memory.num_rpd, 4);
+
+        // Receptors stay inside their receptive field
+        for (d, field) in fields.iter().enumerate() {
+            let mut addrs = memory.addrs(d);
+            addrs.sort();
+            assert_eq!(&addrs, field);
+        }
+
+        // All connected, so overlap counts only in-field active bits
+        let mut input = BitField::new(10);
+        input.set_acts(&[0, 1, 9]);
+        assert_eq!(memory.overlap_conn(0, &input), 2);
+        assert_eq!(memory.overlap_conn(1, &input), 0);
+        assert_eq!(memory.overlap_conn(2, &input), 1);
+    }
+
     #[test]
     fn test_overlap() {
         let mut memory = BlockMemory::new(1, 10, 20, 2, 1, 1.0);
@@ -676,6 +1237,40 @@ mod tests {
         assert_eq!(overlap, 5);
     }
 
+    #[test]
+    fn test_overlap_index() {
+        let mut memory = BlockMemory::new(32, 0, 20, 2, 1, 0.5);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        memory.init_pooled_conn(256, &mut rng, 0.5, 0.5);
+        memory.init_index();
+
+        let mut input = BitField::new(256);
+        let mut overlaps = vec![0; 32];
+
+        // Index must stay in sync through every kind of learning
+        for step in 0..50 {
+            input.random_set_num(&mut rng, 12);
+            let d = step % 32;
+            match step % 3 {
+                0 => memory.learn_conn(d, &input, &mut rng),
+                1 => memory.learn_move_conn(d, &input, &mut rng),
+                _ => memory.punish_conn(d, &input, &mut rng),
+            }
+
+            memory.overlap_index(&input, &mut overlaps);
+            for (d, &ov) in overlaps.iter().enumerate() {
+                assert_eq!(ov, memory.overlap(d, &input));
+            }
+        }
+
+        // Rebuilt after loading permanences
+        let perms = vec![vec![0; memory.perms(0).len()]; 32];
+        memory.set_all_permanences(&perms).unwrap();
+        memory.overlap_all(&input, &mut overlaps);
+        assert!(overlaps.iter().all(|&ov| ov == 0));
+    }
+
     #[test]
     fn test_learn() {
         let mut memory = BlockMemory::new(1, 10, 20, 2, 1, 1.0);
@@ -736,6 +1331,26 @@ mod tests {
         assert_eq!(perms_after[1], perms_before[1]);
     }
 
+    #[test]
+    fn test_imprint() {
+        let mut memory = BlockMemory::new(2, 0, 20, 2, 1, 0.3);
+        let mut rng = StdRng::seed_from_u64(42);
+        memory.init_pooled_conn(64, &mut rng, 1.0, 0.5);
+        memory.init_index();
+
+        let mut input = BitField::new(64);
+        input.set_acts(&[1, 5, 9]);
+
+        memory.imprint_conn(1, &input);
+
+        assert_eq!(memory.conns(1).unwrap().get_acts(), vec![1, 5, 9]);
+        assert!(memory.perms(1).iter().all(|&p| p == 0 || p == 20));
+        let mut overlaps = vec![0; 2];
+        memory.overlap_all(&input, &mut overlaps);
+        assert_eq!(overlaps[1], 3);
+        assert_eq!(overlaps[1], memory.overlap(1, &input));
+    }
+
     #[test]
     fn test_memory_usage() {
         let mut memory = BlockMemory::new(100, 50, 20, 2, 1, 0.3);
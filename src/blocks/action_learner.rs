@@ -0,0 +1,617 @@
+//! ActionLearner - Reinforcement learning of state values and action preferences.
+//!
+//! This module provides the `ActionLearner` block. It learns, from a scalar
+//! reward signal, how valuable each action is in the state described by its
+//! input SDR, and selects an action every step. The selected action is written
+//! to the output with the same layout as a `DiscreteTransformer` over `num_a`
+//! values, so it can drive other blocks or be decoded directly.
+//!
+//! # Algorithm
+//!
+//! The learner is linear SARSA(λ) over active input bits. Each step:
+//! 1. `Q(s, a) = sum of weights[i][a] over active bits i` for every action `a`
+//! 2. An action is selected epsilon-greedily: with probability `epsilon` a random
+//!    action, otherwise the highest valued one (ties broken at random)
+//! 3. During learning, the TD error of the previous step is computed from the
+//!    reward set since then: `delta = reward + gamma * Q(s, a) - Q(s', a')`,
+//!    where `(s', a')` is the previous state and action
+//! 4. Every recent state/action pair is moved towards the target along its
+//!    eligibility trace: `weights[i][a] += alpha * delta * (gamma * lambda)^k / n`
+//!    for the `n` active bits `i` of the pair seen `k` steps before the previous one
+//!
+//! Traces are kept until `(gamma * lambda)^k` falls below 0.01. Call
+//! [`ActionLearner::end_episode`] when a terminal state is reached: it learns the
+//! final reward without bootstrapping and clears the traces. With `num_a = 1` the
+//! block learns state values with TD(λ).
+//!
+//! All random choices use the block's RNG, so runs are deterministic for a
+//! given seed.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{ActionLearner, DiscreteTransformer};
+//! use gnomics::{Block, InputAccess, OutputAccess};
+//!
+//! let mut state = DiscreteTransformer::new(2, 64, 2, 0);
+//! let mut agent = ActionLearner::new(2, 32, 0.5, 0.9, 0.0, 0.2, 2, 0);
+//!
+//! agent.input_mut().add_child(state.output(), 0);
+//! agent.init().unwrap();
+//!
+//! // One-step episodes: action 1 pays off in state 0, action 0 in state 1
+//! for step in 0..200 {
+//!     state.set_value(step % 2);
+//!     state.execute(false).unwrap();
+//!     agent.execute(true).unwrap();
+//!     let correct = agent.get_action() == 1 - step % 2;
+//!     agent.set_reward(if correct { 1.0 } else { 0.0 });
+//!     agent.end_episode();
+//! }
+//!
+//! agent.set_epsilon(0.0);
+//! state.set_value(0);
+//! state.execute(false).unwrap();
+//! agent.execute(false).unwrap();
+//! assert_eq!(agent.get_action(), 1);
+//! ```
+
+use crate::{Block, BlockBase, BlockBaseAccess, BlockInput, BlockOutput, Result};
+use crate::{InputAccess, OutputAccess};
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Eligibility below which a past state/action pair is dropped.
+const TRACE_MIN: f64 = 0.01;
+
+/// Upper bound on the number of traced state/action pairs.
+const MAX_TRACE: usize = 256;
+
+/// Epsilon-greedy SARSA(λ) learner from active input bits to actions.
+///
+/// # Performance
+///
+/// - Compute time: O(active bits × num_a)
+/// - Learning time: O(traced steps × active bits)
+/// - Memory: one `f64` weight per input bit and action
+pub struct ActionLearner {
+    base: BlockBase,
+
+    /// Block input connection point
+    input: BlockInput,
+
+    /// Block output with history (DiscreteTransformer layout of the selected action)
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_a: usize,     // Number of actions
+    num_s: usize,     // Number of output statelets
+    num_as: usize,    // Active statelets per action (num_s / num_a)
+    dif_s: usize,     // num_s - num_as
+    alpha: f64,       // Learning rate
+    gamma: f64,       // Reward discount factor
+    lambda: f64,      // Eligibility trace decay
+    epsilon: f64,     // Exploration probability
+    num_t: usize,     // History depth
+    max_trace: usize, // Traced state/action pairs kept
+
+    // State
+    weights: Vec<f64>,                     // Weights: [input bit][action]
+    q_values: Vec<f64>,                    // Action values for the current input
+    action: usize,                         // Selected action
+    reward: f64,                           // Reward since the previous learning step
+    td_error: f64,                         // TD error of the last update
+    acts: Vec<usize>,                      // Active input bits of the current step
+    traces: VecDeque<(Vec<usize>, usize)>, // Recent active bits and actions, newest first
+}
+
+impl ActionLearner {
+    /// Create a new ActionLearner.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_a` - Number of actions
+    /// * `num_s` - Number of output statelets (must be >= num_a)
+    /// * `alpha` - Learning rate (typically 0.05-0.5)
+    /// * `gamma` - Reward discount factor (0.0-1.0)
+    /// * `lambda` - Eligibility trace decay (0.0 = one-step SARSA)
+    /// * `epsilon` - Exploration probability (0.0-1.0)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_a` == 0 or `num_s` < `num_a`
+    /// - `alpha` <= 0.0
+    /// - `gamma`, `lambda` or `epsilon` is outside [0.0, 1.0]
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::ActionLearner;
+    ///
+    /// // Four actions of 16 statelets each
+    /// let agent = ActionLearner::new(4, 64, 0.1, 0.9, 0.8, 0.1, 2, 0);
+    /// assert_eq!(agent.num_as(), 16);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_a: usize,
+        num_s: usize,
+        alpha: f64,
+        gamma: f64,
+        lambda: f64,
+        epsilon: f64,
+        num_t: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(num_a > 0, "num_a must be > 0");
+        assert!(num_s >= num_a, "num_s must be >= num_a");
+        assert!(alpha > 0.0, "alpha must be > 0");
+        assert!((0.0..=1.0).contains(&gamma), "gamma must be in [0.0, 1.0]");
+        assert!(
+            (0.0..=1.0).contains(&lambda),
+            "lambda must be in [0.0, 1.0]"
+        );
+        assert!(
+            (0.0..=1.0).contains(&epsilon),
+            "epsilon must be in [0.0, 1.0]"
+        );
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let num_as = num_s / num_a;
+        let decay = gamma * lambda;
+        let max_trace = if decay < TRACE_MIN {
+            1
+        } else if decay >= 1.0 {
+            MAX_TRACE
+        } else {
+            (1 + (TRACE_MIN.ln() / decay.ln()) as usize).min(MAX_TRACE)
+        };
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            input: BlockInput::new(),
+            output,
+            num_a,
+            num_s,
+            num_as,
+            dif_s: num_s - num_as,
+            alpha,
+            gamma,
+            lambda,
+            epsilon,
+            num_t,
+            max_trace,
+            weights: Vec::new(),
+            q_values: vec![0.0; num_a],
+            action: 0,
+            reward: 0.0,
+            td_error: 0.0,
+            acts: Vec::new(),
+            traces: VecDeque::with_capacity(max_trace),
+        }
+    }
+
+    /// Set the reward received since the previous step.
+    ///
+    /// The reward is learned by the next learning step (or [`end_episode`](Self::end_episode))
+    /// and then consumed; steps without a reward have a reward of 0.0.
+    pub fn set_reward(&mut self, reward: f64) {
+        self.reward = reward;
+    }
+
+    /// Get the reward waiting to be learned.
+    pub fn get_reward(&self) -> f64 {
+        self.reward
+    }
+
+    /// Learn the pending reward as the outcome of a terminal state and clear the traces.
+    ///
+    /// The last selected action is credited with the reward alone (no value
+    /// follows a terminal state). The next step starts a new episode. Weights are kept.
+    pub fn end_episode(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "ActionLearner must be initialized before learning"
+        );
+
+        let last = self.traces.front();
+        if let Some(delta) = last.map(|(acts, a)| self.reward - self.value_of(acts, *a)) {
+            self.update(delta);
+        }
+        self.reward = 0.0;
+        self.traces.clear();
+    }
+
+    /// Set the exploration probability (e.g. 0.0 to evaluate the greedy policy).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is outside [0.0, 1.0].
+    pub fn set_epsilon(&mut self, epsilon: f64) {
+        assert!(
+            (0.0..=1.0).contains(&epsilon),
+            "epsilon must be in [0.0, 1.0]"
+        );
+        self.epsilon = epsilon;
+    }
+
+    /// Get the selected action.
+    pub fn get_action(&self) -> usize {
+        self.action
+    }
+
+    /// Get the value of each action for the current input.
+    pub fn get_q_values(&self) -> &[f64] {
+        &self.q_values
+    }
+
+    /// Get the value of the selected action (the state value when `num_a` == 1).
+    pub fn get_value(&self) -> f64 {
+        self.q_values[self.action]
+    }
+
+    /// Get the TD error of the last update.
+    pub fn get_td_error(&self) -> f64 {
+        self.td_error
+    }
+
+    /// Get the weights, `num_a` per input bit.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Get number of actions.
+    pub fn num_a(&self) -> usize {
+        self.num_a
+    }
+
+    /// Get number of output statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets per action.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+
+    /// Get the learning rate.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Get the reward discount factor.
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Get the eligibility trace decay.
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Get the exploration probability.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Summed weights of `action` over the bits in `acts`.
+    fn value_of(&self, acts: &[usize], action: usize) -> f64 {
+        acts.iter()
+            .map(|&i| self.weights[i * self.num_a + action])
+            .sum()
+    }
+
+    /// Move every traced state/action pair by `delta` along its eligibility.
+    fn update(&mut self, delta: f64) {
+        self.td_error = delta;
+        let decay = self.gamma * self.lambda;
+        let mut eligibility = 1.0;
+        for (acts, action) in &self.traces {
+            if !acts.is_empty() {
+                let step = self.alpha * delta * eligibility / acts.len() as f64;
+                for &i in acts {
+                    self.weights[i * self.num_a + action] += step;
+                }
+            }
+            eligibility *= decay;
+        }
+    }
+
+    /// Highest valued action, ties broken at random.
+    fn greedy_action(&mut self) -> usize {
+        let max = self.q_values.iter().copied().fold(f64::MIN, f64::max);
+        let num_ties = self.q_values.iter().filter(|&&q| q == max).count();
+        let pick = if num_ties > 1 {
+            self.base.rng().gen_range(0..num_ties)
+        } else {
+            0
+        };
+        self.q_values
+            .iter()
+            .enumerate()
+            .filter(|&(_, &q)| q == max)
+            .nth(pick)
+            .map_or(0, |(a, _)| a)
+    }
+
+    /// First statelet of an action's window (same layout as `DiscreteTransformer`).
+    fn window_start(&self, action: usize) -> usize {
+        let percent = if self.num_a > 1 {
+            (action as f64) / ((self.num_a - 1) as f64)
+        } else {
+            0.0
+        };
+
+        ((self.dif_s as f64) * percent) as usize
+    }
+}
+
+impl Block for ActionLearner {
+    fn init(&mut self) -> Result<()> {
+        self.weights = vec![0.0; self.input.num_bits() * self.num_a];
+        self.base.set_initialized(true);
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement save
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement load
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.borrow_mut().clear();
+        self.q_values.fill(0.0);
+        self.action = 0;
+        self.reward = 0.0;
+        self.td_error = 0.0;
+        self.acts.clear();
+        self.traces.clear();
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        self.input.pull();
+    }
+
+    fn compute(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "ActionLearner must be initialized before encoding"
+        );
+
+        self.input.state.get_acts_into(&mut self.acts);
+        self.q_values.fill(0.0);
+        for &i in &self.acts {
+            let row = &self.weights[i * self.num_a..(i + 1) * self.num_a];
+            for (q, &w) in self.q_values.iter_mut().zip(row) {
+                *q += w;
+            }
+        }
+
+        let explore = self.epsilon > 0.0 && self.base.rng().gen::<f64>() < self.epsilon;
+        self.action = if explore {
+            let num_a = self.num_a;
+            self.base.rng().gen_range(0..num_a)
+        } else {
+            self.greedy_action()
+        };
+
+        let beg = self.window_start(self.action);
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        output.state.set_range(beg, self.num_as);
+    }
+
+    fn learn(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "ActionLearner must be initialized before learning"
+        );
+
+        let target = self.reward + self.gamma * self.q_values[self.action];
+        let prev = self.traces.front();
+        if let Some(delta) = prev.map(|(acts, a)| target - self.value_of(acts, *a)) {
+            self.update(delta);
+        }
+        self.reward = 0.0;
+
+        // The oldest pair's buffer is reused once the traces are full
+        let mut acts = if self.traces.len() >= self.max_trace {
+            self.traces
+                .pop_back()
+                .map(|(acts, _)| acts)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        acts.clone_from(&self.acts);
+        self.traces.push_front((acts, self.action));
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.weights.capacity() + self.q_values.capacity()) * std::mem::size_of::<f64>()
+            + (self.acts.capacity()
+                + self
+                    .traces
+                    .iter()
+                    .map(|(acts, _)| acts.capacity())
+                    .sum::<usize>())
+                * std::mem::size_of::<usize>()
+            + self.input.memory_usage()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        self.input.get_source_blocks()
+    }
+}
+
+impl BlockBaseAccess for ActionLearner {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl InputAccess for ActionLearner {
+    fn input(&self) -> &BlockInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut BlockInput {
+        &mut self.input
+    }
+}
+
+impl OutputAccess for ActionLearner {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl crate::network_config::BlockConfigurable for ActionLearner {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::ActionLearner {
+            num_a: self.num_a,
+            num_s: self.num_s,
+            alpha: self.alpha,
+            gamma: self.gamma,
+            lambda: self.lambda,
+            epsilon: self.epsilon,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "ActionLearner"
+    }
+}
+
+impl crate::network_config::BlockStateful for ActionLearner {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::ActionLearner {
+            weights: self.weights.clone(),
+        })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::ActionLearner { weights } = state {
+            if weights.len() != self.weights.len() {
+                return Err(crate::GnomicsError::InvalidInputSize {
+                    expected: self.weights.len(),
+                    actual: weights.len(),
+                });
+            }
+            self.weights.clone_from(weights);
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for ActionLearner".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_support::{feed, with_source};
+
+    fn learner_with_input(num_i: usize, lambda: f64) -> (ActionLearner, Rc<RefCell<BlockOutput>>) {
+        with_source(ActionLearner::new(2, 8, 0.5, 0.5, lambda, 0.0, 2, 0), num_i)
+    }
+
+    #[test]
+    fn test_output_uses_discrete_layout() {
+        let (mut l, source) = learner_with_input(16, 0.0);
+        feed(&source, [1, 2]);
+        l.execute(false).unwrap();
+        let beg = l.get_action() * 4;
+        assert_eq!(
+            l.output().borrow().state.get_acts(),
+            (beg..beg + 4).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_first_step_only_starts_trace() {
+        let (mut l, source) = learner_with_input(16, 0.0);
+        feed(&source, [1, 2]);
+        l.set_reward(1.0);
+        l.execute(true).unwrap();
+        assert!(l.weights().iter().all(|&w| w == 0.0));
+        assert_eq!(l.get_reward(), 0.0);
+    }
+
+    #[test]
+    fn test_end_episode_credits_last_action() {
+        let (mut l, source) = learner_with_input(16, 0.0);
+        feed(&source, [1, 3]);
+        l.execute(true).unwrap();
+        let a = l.get_action();
+        l.set_reward(1.0);
+        l.end_episode();
+
+        // alpha * reward split over the two active bits
+        assert_eq!(l.weights()[2 + a], 0.25);
+        assert_eq!(l.weights()[6 + a], 0.25);
+        assert_eq!(l.weights().iter().filter(|&&w| w != 0.0).count(), 2);
+        assert_eq!(l.get_td_error(), 1.0);
+    }
+
+    #[test]
+    fn test_traces_reach_earlier_steps() {
+        // gamma * lambda = 0.25 also credits the first pair, lambda = 0 only the last
+        let (mut traced, source) = learner_with_input(16, 0.5);
+        let (mut plain, plain_source) = learner_with_input(16, 0.0);
+        for (l, s) in [(&mut traced, &source), (&mut plain, &plain_source)] {
+            feed(s, [0]);
+            l.execute(true).unwrap();
+            feed(s, [1]);
+            l.execute(true).unwrap();
+            l.set_reward(1.0);
+            l.end_episode();
+        }
+
+        assert!(traced.weights()[..2].iter().any(|&w| w > 0.0));
+        assert!(plain.weights()[..2].iter().all(|&w| w == 0.0));
+        assert!(plain.weights()[2..4].iter().any(|&w| w > 0.0));
+    }
+}
@@ -0,0 +1,305 @@
+//! BitFieldSource - Injects externally computed SDRs into a network.
+//!
+//! This module provides the `BitFieldSource` block, a source block with no
+//! encoding logic of its own. Its output is whatever pattern was last set with
+//! `set_state()` or `set_acts()`, which lets SDRs computed by another system or
+//! loaded from a precomputed dataset drive poolers, learners and classifiers.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::BitFieldSource;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! let mut source = BitFieldSource::new(1024, 2, 0);
+//!
+//! source.set_acts(&[3, 17, 512]);
+//! source.execute(false).unwrap();
+//!
+//! assert_eq!(source.output().borrow().state.get_acts(), vec![3, 17, 512]);
+//! ```
+
+use crate::{BitField, Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Source block whose output is set directly from a `BitField`.
+///
+/// The width is fixed at construction. The pattern persists across steps
+/// until it is replaced, like the value of a transformer.
+#[derive(Clone)]
+pub struct BitFieldSource {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_s: usize,
+    num_t: usize,
+
+    // State
+    pattern: BitField,
+    scratch: BitField, // Candidate pattern for set_acts
+    dirty: bool,       // For change detection optimization
+}
+
+impl BitFieldSource {
+    /// Create a new BitFieldSource.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_s` - Number of statelets (output bits)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed (unused, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_s` == 0 or `num_t` < 2
+    pub fn new(num_s: usize, num_t: usize, seed: u64) -> Self {
+        assert!(num_s > 0, "num_s must be > 0");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        let mut source = Self {
+            base: BlockBase::new(seed),
+            output,
+            num_s,
+            num_t,
+            pattern: BitField::new(num_s),
+            scratch: BitField::new(num_s),
+            dirty: false,
+        };
+
+        source.base.set_initialized(true);
+
+        source
+    }
+
+    /// Set the output pattern from a BitField.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `state` does not have exactly `num_s` bits
+    pub fn set_state(&mut self, state: &BitField) {
+        assert_eq!(state.num_bits(), self.num_s, "state must have num_s bits");
+        if *state != self.pattern {
+            self.pattern.copy_from(state);
+            self.dirty = true;
+        }
+    }
+
+    /// Set the output pattern from a list of active bit indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any index is >= `num_s`
+    pub fn set_acts(&mut self, acts: &[usize]) {
+        assert!(
+            acts.iter().all(|&a| a < self.num_s),
+            "active index must be < num_s"
+        );
+        // Build the candidate in scratch so duplicate indices compare correctly
+        self.scratch.set_acts(acts);
+        if self.scratch != self.pattern {
+            std::mem::swap(&mut self.pattern, &mut self.scratch);
+            self.dirty = true;
+        }
+    }
+
+    /// Get the pattern that will be output on the next compute.
+    pub fn get_state(&self) -> &BitField {
+        &self.pattern
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+}
+
+impl Block for BitFieldSource {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.pattern.clear_all();
+        self.dirty = false;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - source block
+    }
+
+    fn compute(&mut self) {
+        // Optimization: Only copy if the pattern changed
+        if !self.dirty {
+            return;
+        }
+
+        // Word copy bumps the output version, so store() sees the change
+        self.output
+            .borrow_mut()
+            .state
+            .words_mut()
+            .copy_from_slice(self.pattern.words());
+        self.dirty = false;
+    }
+
+    fn learn(&mut self) {
+        // No learning in source block
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.pattern.memory_usage()
+            + self.scratch.memory_usage()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for BitFieldSource {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for BitFieldSource {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for BitFieldSource {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::BitFieldSource {
+            num_s: self.num_s,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "BitFieldSource"
+    }
+}
+
+impl crate::network_config::BlockStateful for BitFieldSource {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Source blocks have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let source = BitFieldSource::new(64, 3, 0);
+        assert_eq!(source.num_s(), 64);
+        assert_eq!(source.output().borrow().num_t(), 3);
+        assert_eq!(source.get_state().num_set(), 0);
+    }
+
+    #[test]
+    fn test_set_state_copies_pattern() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        let mut bf = BitField::new(32);
+        bf.set_acts(&[1, 8, 31]);
+
+        source.set_state(&bf);
+        bf.clear_all();
+        source.compute();
+        assert_eq!(source.output().borrow().state.get_acts(), vec![1, 8, 31]);
+    }
+
+    #[test]
+    fn test_unchanged_pattern_not_dirty() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        source.set_acts(&[4]);
+        source.compute();
+        source.set_acts(&[4]);
+        assert!(!source.dirty);
+    }
+
+    #[test]
+    fn test_set_acts_replaces_pattern() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        source.set_acts(&[4, 9]);
+        source.compute();
+        source.set_acts(&[9, 12]);
+        assert!(source.dirty);
+        source.compute();
+        assert_eq!(source.output().borrow().state.get_acts(), vec![9, 12]);
+    }
+
+    #[test]
+    fn test_set_acts_with_duplicates() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        source.set_acts(&[1, 2]);
+        source.compute();
+        source.set_acts(&[1, 1]);
+        assert!(source.dirty);
+        source.compute();
+        assert_eq!(source.output().borrow().state.get_acts(), vec![1]);
+
+        source.set_acts(&[1, 1]);
+        assert!(!source.dirty);
+    }
+
+    #[test]
+    #[should_panic(expected = "state must have num_s bits")]
+    fn test_wrong_width() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        source.set_state(&BitField::new(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "active index must be < num_s")]
+    fn test_act_out_of_range() {
+        let mut source = BitFieldSource::new(32, 2, 0);
+        source.set_acts(&[32]);
+    }
+}
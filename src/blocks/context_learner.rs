@@ -16,6 +16,33 @@
 //!    - Increment anomaly score
 //! 3. **Learning**: Active dendrites learn the context pattern
 //!
+//! Besides the active statelets, the learner exposes a second output,
+//! `predicted()`: every statelet (in any column) with a dendrite over
+//! threshold on the current context. It is only computed once enabled with
+//! `set_predicted_enabled(true)`; Network's port-aware connect functions
+//! (e.g. `connect_predicted_to_input()`) enable it for you.
+//!
+//! # Apical Feedback
+//!
+//! An optional third input, `apical`, carries top-down patterns such as the
+//! output of a higher-level pooler or classifier. Each statelet then also has
+//! `num_dps` apical dendrites (a separate `BlockMemory` over the apical input).
+//! A statelet whose apical dendrite recognizes the apical pattern is
+//! *apically supported*:
+//!
+//! - If several statelets of a column are predicted by context, only the
+//!   supported ones fire (when any are supported)
+//! - On surprise, the supported statelets fire instead of a random statelet
+//!   plus historical ones (the column still counts as anomalous)
+//! - Supported statelets that fire learn on their recognizing apical
+//!   dendrites; unsupported predicted statelets, and the statelet chosen at
+//!   random on surprise (not the historical burst), learn on their next
+//!   available apical dendrite
+//!
+//! Apical sources are not dependencies of the learner, so feedback from a
+//! block that runs later does not form a cycle; the learner then sees that
+//! block's output from the previous step.
+//!
 //! # Architecture
 //!
 //! - **Columns** (`num_c`): Organize statelets into competitive groups
@@ -80,8 +107,8 @@
 use crate::bitfield::BitField;
 use crate::utils;
 use crate::{
-    Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput, ContextAccess,
-    InputAccess, MemoryAccess, OutputAccess, Result,
+    ApicalAccess, Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput,
+    ContextAccess, InputAccess, MemoryAccess, OutputAccess, OutputPort, Result,
 };
 use std::cell::RefCell;
 use std::path::Path;
@@ -89,9 +116,10 @@ use std::rc::Rc;
 
 /// Learns contextual associations and detects anomalies.
 ///
-/// ContextLearner has two inputs:
+/// ContextLearner has two inputs and an optional third:
 /// - `input`: Column activations (which column is active)
 /// - `context`: Contextual pattern (what context is it in)
+/// - `apical`: Top-down feedback biasing which statelets fire (optional)
 ///
 /// Dendrites learn to predict which statelets should activate given the context.
 /// Novel patterns (no matching dendrite) trigger surprise and learning.
@@ -111,12 +139,21 @@ pub struct ContextLearner {
     /// Block input for contextual pattern
     context: BlockInput,
 
+    /// Block input for top-down (apical) feedback
+    apical: BlockInput,
+
     /// Block output with history (wrapped for sharing)
     output: Rc<RefCell<BlockOutput>>,
 
+    /// Predicted statelets (dendrites over threshold on the current context)
+    predicted: Rc<RefCell<BlockOutput>>,
+
     /// Block memory with synaptic learning (one BlockMemory for all dendrites)
     memory: BlockMemory,
 
+    /// Apical dendrite memory (created by init() when apical input is connected)
+    apical_memory: Option<BlockMemory>,
+
     // Architecture parameters
     num_c: usize,   // Number of columns
     num_spc: usize, // Statelets per column
@@ -126,6 +163,7 @@ pub struct ContextLearner {
     num_s: usize,   // Total statelets (num_c × num_spc)
     num_d: usize,   // Total dendrites (num_s × num_dps)
     d_thresh: u32,  // Dendrite activation threshold
+    a_thresh: u32,  // Apical dendrite activation threshold
     num_t: usize,   // History depth
 
     // Learning parameters
@@ -134,14 +172,21 @@ pub struct ContextLearner {
     perm_dec: u8, // Permanence decrement
 
     // State
-    next_sd: Vec<usize>, // Next available dendrite per statelet
-    d_used: BitField,    // Dendrite usage mask (1=used, 0=available)
-    anomaly_score: f64,  // Current anomaly score (0.0-1.0)
-    always_update: bool, // Update even if inputs unchanged
+    next_sd: Vec<usize>,     // Next available dendrite per statelet
+    d_used: BitField,        // Dendrite usage mask (1=used, 0=available)
+    next_ad: Vec<usize>,     // Next available apical dendrite per statelet
+    a_used: BitField,        // Apical dendrite usage mask (1=used, 0=available)
+    anomaly_score: f64,      // Current anomaly score (0.0-1.0)
+    always_update: bool,     // Update even if inputs unchanged
+    predicted_enabled: bool, // Compute the predicted output
 
     // Working memory
     input_acts: Vec<usize>, // Active column indices
     d_acts: Vec<usize>,     // Active dendrite indices
+    a_acts: Vec<usize>,     // Recognizing apical dendrite indices
+    s_apical: BitField,     // Apically supported statelets
+    u_acts: Vec<usize>,     // Scratch: used dendrite indices
+    a_learn: Vec<usize>,    // Statelets to assign a new apical dendrite
     surprise_flag: bool,    // Surprise detected for current column
 }
 
@@ -210,12 +255,18 @@ impl ContextLearner {
 
         let pct_learn = 1.0; // Learn on all receptors
 
+        let mut predicted = BlockOutput::new();
+        predicted.set_source_port(OutputPort::Predicted);
+
         Self {
             base: BlockBase::new(seed),
             input: BlockInput::new(),
             context: BlockInput::new(),
+            apical: BlockInput::new(),
             output: Rc::new(RefCell::new(BlockOutput::new())),
+            predicted: Rc::new(RefCell::new(predicted)),
             memory: BlockMemory::new(num_d, num_rpd, perm_thr, perm_inc, perm_dec, pct_learn),
+            apical_memory: None,
             num_c,
             num_spc,
             num_dps,
@@ -224,16 +275,24 @@ impl ContextLearner {
             num_s,
             num_d,
             d_thresh,
+            a_thresh: d_thresh,
             num_t,
             perm_thr,
             perm_inc,
             perm_dec,
             next_sd: vec![0; num_s],
             d_used: BitField::new(num_d),
+            next_ad: Vec::new(),
+            a_used: BitField::new(0),
             anomaly_score: 0.0,
             always_update,
+            predicted_enabled: false,
             input_acts: Vec::new(),
             d_acts: Vec::new(),
+            a_acts: Vec::new(),
+            s_apical: BitField::new(0),
+            u_acts: Vec::new(),
+            a_learn: Vec::new(),
             surprise_flag: false,
         }
     }
@@ -296,14 +355,113 @@ impl ContextLearner {
         self.d_thresh
     }
 
+    /// Set the apical dendrite activation threshold (defaults to `d_thresh`).
+    ///
+    /// Apical dendrites have `num_rpd` receptors like distal ones, so the
+    /// apical pattern needs at least this many active bits to be recognized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a_thresh` >= `num_rpd`.
+    pub fn set_apical_threshold(&mut self, a_thresh: u32) {
+        assert!(a_thresh < self.num_rpd as u32, "a_thresh must be < num_rpd");
+        self.a_thresh = a_thresh;
+    }
+
+    /// Builder-style variant of `set_apical_threshold()`.
+    pub fn with_apical_threshold(mut self, a_thresh: u32) -> Self {
+        self.set_apical_threshold(a_thresh);
+        self
+    }
+
+    /// Get apical dendrite activation threshold.
+    pub fn apical_threshold(&self) -> u32 {
+        self.a_thresh
+    }
+
+    /// Check whether the apical input was connected when the block was initialized.
+    pub fn has_apical(&self) -> bool {
+        self.apical_memory.is_some()
+    }
+
+    /// Get the apical dendrite memory, if the apical input is connected.
+    pub fn apical_memory(&self) -> Option<&BlockMemory> {
+        self.apical_memory.as_ref()
+    }
+
+    /// Get the predicted statelets output.
+    ///
+    /// Holds every statelet, in any column, with a dendrite over `d_thresh`
+    /// on the current context, whether or not its column is active. Stays
+    /// empty unless predictions are enabled (see `set_predicted_enabled()`).
+    /// Connect it downstream through Network (e.g.
+    /// `Network::connect_predicted_to_input()`), which enables predictions
+    /// and keeps the connection in `to_config()`.
+    pub fn predicted(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.predicted)
+    }
+
+    /// Enable or disable computing the predicted output.
+    ///
+    /// Predicting scans every used dendrite, so it is off by default.
+    /// Predictions take effect from the next `compute()`; disabling clears
+    /// the predicted output.
+    pub fn set_predicted_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.predicted.borrow_mut().clear();
+        }
+        self.predicted_enabled = enabled;
+    }
+
+    /// Check whether the predicted output is computed.
+    pub fn predicted_enabled(&self) -> bool {
+        self.predicted_enabled
+    }
+
+    /// Get the statelets whose apical dendrites recognized the current
+    /// apical pattern, among the active columns.
+    pub fn get_apical_support(&self) -> &BitField {
+        &self.s_apical
+    }
+
+    /// Apical phase: mark statelets of column `c` supported by the apical pattern.
+    ///
+    /// Returns true if any statelet in the column is supported.
+    fn apical_support(&mut self, c: usize) -> bool {
+        let Some(memory) = &self.apical_memory else {
+            return false;
+        };
+        if self.apical.state.num_set() == 0 {
+            return false;
+        }
+
+        let mut supported = false;
+        let d_beg = c * self.num_dpc;
+        let d_end = d_beg + self.num_dpc;
+
+        for d in d_beg..d_end {
+            if self.a_used.get_bit(d) > 0
+                && memory.overlap(d, &self.apical.state) >= self.a_thresh as usize
+            {
+                self.a_acts.push(d);
+                self.s_apical.set_bit(d / self.num_dps);
+                supported = true;
+            }
+        }
+
+        supported
+    }
+
     /// Recognition phase: check if any dendrite predicts the column.
     ///
     /// For the given column, checks all its dendrites against the context.
     /// If any dendrite overlap exceeds threshold, activates the statelet and
     /// clears the surprise flag.
-    fn recognition(&mut self, c: usize) {
+    fn recognition(&mut self, c: usize, apical: bool) {
         let d_beg = c * self.num_dpc;
         let d_end = d_beg + self.num_dpc;
+        let a_beg = self.d_acts.len();
+        let mut any_apical = false;
 
         // For every dendrite on the column
         for d in d_beg..d_end {
@@ -313,20 +471,43 @@ impl ContextLearner {
 
                 // If dendrite overlap exceeds threshold
                 if overlap >= self.d_thresh as usize {
-                    let s = d / self.num_dps;
                     self.d_acts.push(d);
-                    self.output.borrow_mut().state.set_bit(s);
+                    any_apical |= apical && self.s_apical.get_bit(d / self.num_dps) > 0;
                     self.surprise_flag = false;
                 }
             }
         }
+
+        // Apically supported predictions win over unsupported ones
+        if any_apical {
+            let mut w = a_beg;
+            for r in a_beg..self.d_acts.len() {
+                let d = self.d_acts[r];
+                if self.s_apical.get_bit(d / self.num_dps) > 0 {
+                    self.d_acts[w] = d;
+                    w += 1;
+                }
+            }
+            self.d_acts.truncate(w);
+        }
+
+        let mut output = self.output.borrow_mut();
+        for &d in &self.d_acts[a_beg..] {
+            let s = d / self.num_dps;
+            output.state.set_bit(s);
+
+            // Unsupported predictions learn the current apical pattern
+            if !any_apical && self.apical_memory.is_some() && self.a_learn.last() != Some(&s) {
+                self.a_learn.push(s);
+            }
+        }
     }
 
     /// Surprise phase: handle unexpected column activation.
     ///
     /// When no dendrite predicted the column, activate statelets and assign
     /// dendrites to learn this new pattern.
-    fn surprise(&mut self, c: usize) {
+    fn surprise(&mut self, c: usize, apical: bool) {
         // Update anomaly score
         let num_input_acts = self.input_acts.len();
         if num_input_acts > 0 {
@@ -337,6 +518,17 @@ impl ContextLearner {
         let s_beg = c * self.num_spc;
         let s_end = s_beg + self.num_spc;
 
+        // Top-down feedback picks the statelets instead of chance
+        if apical {
+            for s in s_beg..s_end {
+                if self.s_apical.get_bit(s) > 0 {
+                    self.output.borrow_mut().state.set_bit(s);
+                    self.set_next_available_dendrite(s);
+                }
+            }
+            return;
+        }
+
         // Choose random statelet in column
         let s_rand = if self.num_spc > 1 {
             utils::rand_uint(s_beg as u32, (s_end - 1) as u32, self.base.rng()) as usize
@@ -350,6 +542,11 @@ impl ContextLearner {
         // Assign next available dendrite to random statelet
         self.set_next_available_dendrite(s_rand);
 
+        // Only the chosen statelet learns the apical pattern, not the burst
+        if self.apical_memory.is_some() {
+            self.a_learn.push(s_rand);
+        }
+
         // Activate historical statelets (those with at least one dendrite)
         for s in s_beg..s_end {
             if s != s_rand && self.next_sd[s] > 0 {
@@ -373,6 +570,64 @@ impl ContextLearner {
             self.next_sd[s] += 1;
         }
     }
+
+    /// Predict phase: mark every statelet with a used dendrite over threshold.
+    ///
+    /// Unlike recognition, this covers all columns, not just the active ones.
+    fn predict(&mut self) {
+        let mut predicted = self.predicted.borrow_mut();
+        predicted.state.clear_all();
+
+        self.d_used.get_acts_into(&mut self.u_acts);
+        for &d in &self.u_acts {
+            let s = d / self.num_dps;
+            if predicted.state.get_bit(s) == 0
+                && self.memory.overlap(d, &self.context.state) >= self.d_thresh as usize
+            {
+                predicted.state.set_bit(s);
+            }
+        }
+    }
+
+    /// Check whether any input changed (or updates are forced).
+    fn inputs_changed(&self) -> bool {
+        self.always_update
+            || self.input.children_changed()
+            || self.context.children_changed()
+            || self.apical.children_changed()
+    }
+
+    /// Apical learning: active statelets learn the apical pattern.
+    ///
+    /// Supported statelets learn on their recognizing apical dendrites.
+    /// Unsupported predicted statelets, and the statelet chosen on surprise,
+    /// are assigned their next available apical dendrite.
+    fn learn_apical(&mut self) {
+        let Some(memory) = &mut self.apical_memory else {
+            return;
+        };
+        if self.apical.state.num_set() == 0 {
+            return;
+        }
+
+        let output = self.output.borrow();
+        for &d in &self.a_acts {
+            if output.state.get_bit(d / self.num_dps) > 0 {
+                memory.learn_move(d, &self.apical.state, self.base.rng());
+            }
+        }
+
+        for &s in &self.a_learn {
+            let d = s * self.num_dps + self.next_ad[s];
+            memory.learn_move(d, &self.apical.state, self.base.rng());
+            self.a_used.set_bit(d);
+
+            // Saturate at num_dps-1 like distal dendrites
+            if self.next_ad[s] < self.num_dps - 1 {
+                self.next_ad[s] += 1;
+            }
+        }
+    }
 }
 
 impl Block for ContextLearner {
@@ -384,13 +639,32 @@ impl Block for ContextLearner {
             "input size must equal num_c"
         );
 
-        // Initialize output
+        // Initialize outputs
         self.output.borrow_mut().setup(self.num_t, self.num_s);
+        self.predicted.borrow_mut().setup(self.num_t, self.num_s);
 
         // Initialize memory (dendrites learn from context)
         let num_context_bits = self.context.num_bits();
         self.memory.init(num_context_bits, self.base.rng());
 
+        // Initialize apical dendrites only if feedback is connected
+        let num_apical_bits = self.apical.num_bits();
+        if num_apical_bits > 0 {
+            let mut memory = BlockMemory::new(
+                self.num_d,
+                self.num_rpd,
+                self.perm_thr,
+                self.perm_inc,
+                self.perm_dec,
+                1.0,
+            );
+            memory.init(num_apical_bits, self.base.rng());
+            self.apical_memory = Some(memory);
+            self.next_ad = vec![0; self.num_s];
+            self.a_used = BitField::new(self.num_d);
+        }
+        self.s_apical = BitField::new(self.num_s);
+
         self.base.set_initialized(true);
         Ok(())
     }
@@ -408,48 +682,65 @@ impl Block for ContextLearner {
     fn clear(&mut self) {
         self.input.clear();
         self.context.clear();
+        self.apical.clear();
         self.output.borrow_mut().clear();
+        self.predicted.borrow_mut().clear();
         self.memory.clear();
         self.anomaly_score = 0.0;
         self.input_acts.clear();
         self.d_acts.clear();
+        self.a_acts.clear();
+        self.s_apical.clear_all();
     }
 
     fn step(&mut self) {
         self.output.borrow_mut().step();
+        self.predicted.borrow_mut().step();
     }
 
     fn pull(&mut self) {
         self.input.pull();
         self.context.pull();
+        self.apical.pull();
     }
 
     fn compute(&mut self) {
         assert!(self.base.is_initialized(), "must call init() first");
 
         // Check if any input changed
-        if self.always_update || self.input.children_changed() || self.context.children_changed() {
+        if self.inputs_changed() {
             // Get active columns
-            self.input_acts = self.input.state.get_acts();
+            self.input.state.get_acts_into(&mut self.input_acts);
 
             // Clear state
             self.anomaly_score = 0.0;
             self.output.borrow_mut().state.clear_all();
             self.d_acts.clear();
+            self.a_acts.clear();
+            self.a_learn.clear();
+            self.s_apical.clear_all();
 
             // Process each active column
-            let input_acts = self.input_acts.clone();
-            for c in input_acts {
+            // Index loop: recognition/surprise need &mut self
+            for i in 0..self.input_acts.len() {
+                let c = self.input_acts[i];
                 self.surprise_flag = true;
 
+                // Find top-down support
+                let apical = self.apical_support(c);
+
                 // Try recognition
-                self.recognition(c);
+                self.recognition(c, apical);
 
                 // Handle surprise if no dendrite predicted
                 if self.surprise_flag {
-                    self.surprise(c);
+                    self.surprise(c, apical);
                 }
             }
+
+            if self.predicted_enabled {
+                self.predict();
+            }
         }
     }
 
@@ -457,31 +748,45 @@ impl Block for ContextLearner {
         assert!(self.base.is_initialized(), "must call init() first");
 
         // Check if any input changed
-        if self.always_update || self.input.children_changed() || self.context.children_changed() {
+        if self.inputs_changed() {
             // Learn on all active dendrites
-            let d_acts = self.d_acts.clone();
-            for d in d_acts {
+            for &d in &self.d_acts {
                 self.memory
                     .learn_move(d, &self.context.state, self.base.rng());
                 self.d_used.set_bit(d);
             }
+
+            self.learn_apical();
         }
     }
 
     fn store(&mut self) {
         self.output.borrow_mut().store();
+        self.predicted.borrow_mut().store();
     }
 
     fn memory_usage(&self) -> usize {
         let mut bytes = std::mem::size_of::<Self>();
         bytes += self.input.memory_usage();
         bytes += self.context.memory_usage();
+        bytes += self.apical.memory_usage();
         bytes += self.output.borrow().memory_usage();
+        bytes += self.predicted.borrow().memory_usage();
         bytes += self.memory.memory_usage();
+        bytes += self
+            .apical_memory
+            .as_ref()
+            .map_or(0, BlockMemory::memory_usage);
         bytes += self.next_sd.capacity() * std::mem::size_of::<usize>();
         bytes += self.d_used.memory_usage();
+        bytes += self.next_ad.capacity() * std::mem::size_of::<usize>();
+        bytes += self.a_used.memory_usage();
         bytes += self.input_acts.capacity() * std::mem::size_of::<usize>();
         bytes += self.d_acts.capacity() * std::mem::size_of::<usize>();
+        bytes += self.a_acts.capacity() * std::mem::size_of::<usize>();
+        bytes += self.s_apical.memory_usage();
+        bytes += self.u_acts.capacity() * std::mem::size_of::<usize>();
+        bytes += self.a_learn.capacity() * std::mem::size_of::<usize>();
         bytes
     }
 
@@ -494,6 +799,7 @@ impl Block for ContextLearner {
     }
 
     fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        // Apical sources are feedback, not dependencies (see module docs)
         let mut deps = self.input.get_source_blocks();
         deps.extend(self.context.get_source_blocks());
         deps
@@ -530,6 +836,16 @@ impl ContextAccess for ContextLearner {
     }
 }
 
+impl ApicalAccess for ContextLearner {
+    fn apical(&self) -> &BlockInput {
+        &self.apical
+    }
+
+    fn apical_mut(&mut self) -> &mut BlockInput {
+        &mut self.apical
+    }
+}
+
 impl MemoryAccess for ContextLearner {
     fn memory(&self) -> &BlockMemory {
         &self.memory
@@ -560,6 +876,8 @@ impl crate::network_config::BlockConfigurable for ContextLearner {
             num_t: self.num_t,
             always_update: self.always_update,
             seed: self.base().seed(),
+            apical_thresh: Some(self.a_thresh),
+            predicted_enabled: self.predicted_enabled,
         }
     }
 
@@ -571,12 +889,77 @@ impl crate::network_config::BlockConfigurable for ContextLearner {
 impl crate::network_config::BlockStateful for ContextLearner {
     fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
         let permanences = self.memory.get_all_permanences();
-        Ok(crate::network_config::BlockState::ContextLearner { permanences })
+        let apical_permanences = self
+            .apical_memory
+            .as_ref()
+            .map_or_else(Vec::new, BlockMemory::get_all_permanences);
+        let apical_addresses = self
+            .apical_memory
+            .as_ref()
+            .map_or_else(Vec::new, BlockMemory::get_all_addresses);
+        Ok(crate::network_config::BlockState::ContextLearner {
+            permanences,
+            apical_permanences,
+            addresses: self.memory.get_all_addresses(),
+            apical_addresses,
+            next_sd: self.next_sd.clone(),
+            d_used: self.d_used.get_acts(),
+            next_ad: self.next_ad.clone(),
+            a_used: self.a_used.get_acts(),
+        })
     }
 
     fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
-        if let crate::network_config::BlockState::ContextLearner { permanences } = state {
+        if let crate::network_config::BlockState::ContextLearner {
+            permanences,
+            apical_permanences,
+            addresses,
+            apical_addresses,
+            next_sd,
+            d_used,
+            next_ad,
+            a_used,
+        } = state
+        {
+            // Addresses and usage may be empty in states saved before they were persisted
+            let check_usage = |next: &[usize], used: &[usize]| -> crate::Result<()> {
+                if !next.is_empty() && next.len() != self.num_s {
+                    return Err(crate::GnomicsError::InvalidInputSize {
+                        expected: self.num_s,
+                        actual: next.len(),
+                    });
+                }
+                if next.iter().any(|&n| n >= self.num_dps) || used.iter().any(|&d| d >= self.num_d)
+                {
+                    return Err(crate::GnomicsError::Other(
+                        "ContextLearner dendrite usage out of range".into(),
+                    ));
+                }
+                Ok(())
+            };
+            check_usage(next_sd, d_used)?;
+            check_usage(next_ad, a_used)?;
+
+            if !addresses.is_empty() {
+                self.memory.set_all_addresses(addresses)?;
+            }
             self.memory.set_all_permanences(permanences)?;
+            if !next_sd.is_empty() {
+                self.next_sd.clone_from(next_sd);
+                self.d_used.set_acts(d_used);
+            }
+            if let Some(memory) = &mut self.apical_memory {
+                if !apical_addresses.is_empty() {
+                    memory.set_all_addresses(apical_addresses)?;
+                }
+                if !apical_permanences.is_empty() {
+                    memory.set_all_permanences(apical_permanences)?;
+                }
+                if !next_ad.is_empty() {
+                    self.next_ad.clone_from(next_ad);
+                    self.a_used.set_acts(a_used);
+                }
+            }
             Ok(())
         } else {
             Err(crate::GnomicsError::Other(
@@ -630,4 +1013,20 @@ mod tests {
     fn test_new_thresh_too_high() {
         ContextLearner::new(10, 4, 8, 32, 32, 20, 2, 1, 2, false, 0);
     }
+
+    #[test]
+    fn test_apical_threshold() {
+        let learner = ContextLearner::new(10, 4, 8, 32, 20, 20, 2, 1, 2, false, 0);
+        assert_eq!(learner.apical_threshold(), 20);
+        assert!(!learner.has_apical());
+
+        let learner = learner.with_apical_threshold(12);
+        assert_eq!(learner.apical_threshold(), 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "a_thresh must be < num_rpd")]
+    fn test_apical_threshold_too_high() {
+        ContextLearner::new(10, 4, 8, 32, 20, 20, 2, 1, 2, false, 0).with_apical_threshold(32);
+    }
 }
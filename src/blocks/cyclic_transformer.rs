@@ -0,0 +1,437 @@
+//! CyclicTransformer - Encodes periodic values with a wrap-around window.
+//!
+//! This module provides the `CyclicTransformer` block for values that repeat
+//! with a fixed period: angles, time-of-day, day-of-week, phase. Like
+//! `ScalarTransformer` it activates a contiguous window of `num_as` bits, but
+//! the window wraps past the end of the statelet space, so values on either
+//! side of the period boundary overlap.
+//!
+//! # Semantic Properties
+//!
+//! - **Periodic**: `value` and `value + k * period` encode identically
+//! - **Wrap-Around Overlap**: 359° and 1° share most active bits
+//! - **Continuous Gradation**: Overlap falls off linearly with cyclic distance
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::CyclicTransformer;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! // Angle in degrees
+//! let mut a = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+//! let mut b = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+//!
+//! a.set_value(359.0);
+//! b.set_value(1.0);
+//! a.execute(false).unwrap();
+//! b.execute(false).unwrap();
+//!
+//! // Values across the 0/360 boundary still overlap
+//! let overlap = a.output().borrow().state.num_similar(&b.output().borrow().state);
+//! assert!(overlap > 50);
+//! ```
+
+use crate::{BitField, Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Encodes periodic values into wrap-around overlapping binary patterns.
+///
+/// # Algorithm
+///
+/// 1. Phase: `phase = (value mod period) / period` in [0, 1)
+/// 2. Start position: `beg = floor(phase * num_s)`
+/// 3. Activate `num_as` bits starting at `beg`, wrapping modulo `num_s`
+///
+/// # Performance
+///
+/// - Encoding time: ~500ns for 1024 bits, 128 active (same as ScalarTransformer)
+/// - Memory: ~1KB for 1024 bits with history depth 2
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct CyclicTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    period: f64,   // Length of one cycle in value units
+    num_s: usize,  // Number of statelets
+    num_as: usize, // Number of active statelets
+
+    // State
+    value: f64,
+    beg_prev: Option<usize>, // For change detection optimization
+}
+
+impl CyclicTransformer {
+    /// Create a new CyclicTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Length of one cycle (e.g. 360.0 for degrees, 24.0 for hours)
+    /// * `num_s` - Number of statelets (output bits)
+    /// * `num_as` - Number of active statelets
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility (unused in transformer, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `period` is not positive and finite
+    /// - `num_as` == 0 or `num_as` > `num_s`
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::CyclicTransformer;
+    ///
+    /// // Hour of day
+    /// let hours = CyclicTransformer::new(24.0, 1024, 64, 2, 0);
+    ///
+    /// // Day of week
+    /// let weekday = CyclicTransformer::new(7.0, 512, 64, 2, 0);
+    /// ```
+    pub fn new(period: f64, num_s: usize, num_as: usize, num_t: usize, seed: u64) -> Self {
+        assert!(
+            period > 0.0 && period.is_finite(),
+            "period must be positive and finite"
+        );
+        assert!(num_as > 0, "num_as must be > 0");
+        assert!(num_as <= num_s, "num_as must be <= num_s");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            output,
+            period,
+            num_s,
+            num_as,
+            value: 0.0,
+            beg_prev: None,
+        }
+    }
+
+    /// Set the current value to encode.
+    ///
+    /// Any finite value is accepted and reduced modulo `period`. Non-finite
+    /// values (NaN, infinity) encode as an empty pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::CyclicTransformer;
+    ///
+    /// let mut ct = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+    /// ct.set_value(-90.0);
+    /// assert_eq!(ct.get_value(), -90.0);
+    /// assert_eq!(ct.phase(), 0.75);
+    /// ```
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Get the current value (as set, not reduced modulo `period`).
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Get the phase of the current value in [0, 1).
+    pub fn phase(&self) -> f64 {
+        cyclic_phase(self.value, self.period)
+    }
+
+    /// Get period.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+
+    /// Decode active statelets into the value range they encode.
+    ///
+    /// Like `ScalarTransformer::decode_range()`, but the active run may wrap
+    /// past the last statelet: it is taken to start just after the widest
+    /// circular gap between active statelets. Both ends are reduced to
+    /// [0, `period`), so a range that crosses the period boundary comes back
+    /// with `lo > hi`. Returns `None` if `acts` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::CyclicTransformer;
+    /// use gnomics::{Block, OutputAccess};
+    ///
+    /// let mut ct = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+    /// ct.set_value(355.0);
+    /// ct.execute(false).unwrap();
+    ///
+    /// // The window wraps past 360, but still decodes to 355
+    /// let acts = ct.output().borrow().state.get_acts();
+    /// let (lo, hi) = ct.decode_range(&acts).unwrap();
+    /// assert!((lo - 355.0).abs() < 0.5 && (hi - 355.0).abs() < 0.5);
+    /// ```
+    pub fn decode_range(&self, acts: &[usize]) -> Option<(f64, f64)> {
+        let mut acts: Vec<usize> = acts.iter().map(|&a| a % self.num_s).collect();
+        acts.sort_unstable();
+        acts.dedup();
+        let n = acts.len();
+
+        // The run starts just after the widest gap, wrapping from last to first
+        let (mut first, mut last, mut widest) = (*acts.first()?, *acts.last()?, 0);
+        for i in 0..n {
+            let prev = acts[(i + n - 1) % n];
+            let gap = (acts[i] + self.num_s - prev - 1) % self.num_s;
+            if gap >= widest {
+                (first, last, widest) = (acts[i], prev, gap);
+            }
+        }
+
+        // Windows starting at `first` and ending at `last`; a run shorter
+        // than one window fits between them, so swap
+        let span = (last + self.num_s - first) % self.num_s + 1;
+        let beg_last = (last + 1 + self.num_s - self.num_as) % self.num_s;
+        let (lo, hi) = if span < self.num_as {
+            (beg_last, first)
+        } else {
+            (first, beg_last)
+        };
+        Some((self.value_at(lo), self.value_at(hi)))
+    }
+
+    /// Smallest value in [0, `period`) whose window starts at `beg`.
+    fn value_at(&self, beg: usize) -> f64 {
+        beg as f64 / self.num_s as f64 * self.period
+    }
+}
+
+/// Reduce `value` modulo `period` and normalize to a phase in [0, 1).
+#[inline]
+pub(crate) fn cyclic_phase(value: f64, period: f64) -> f64 {
+    let phase = value.rem_euclid(period) / period;
+    // rem_euclid can round up to exactly `period` for tiny negative values
+    if phase >= 1.0 {
+        0.0
+    } else {
+        phase
+    }
+}
+
+/// Window start position for `phase` in a `num_s`-bit field.
+#[inline]
+pub(crate) fn cyclic_start(phase: f64, num_s: usize) -> usize {
+    ((phase * num_s as f64) as usize).min(num_s - 1)
+}
+
+/// Activate a wrap-around window of `num_as` bits starting at `beg` within
+/// the `num_s`-bit field that begins at bit `offset` of `state`.
+///
+/// Shared by `CyclicTransformer` and the periodic fields of
+/// `DateTimeTransformer`.
+#[inline]
+pub(crate) fn set_cyclic_window(
+    state: &mut BitField,
+    offset: usize,
+    num_s: usize,
+    num_as: usize,
+    beg: usize,
+) {
+    let end = beg + num_as;
+
+    if end <= num_s {
+        state.set_range(offset + beg, num_as);
+    } else {
+        state.set_range(offset + beg, num_s - beg);
+        state.set_range(offset, end - num_s);
+    }
+}
+
+impl Block for CyclicTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.value = 0.0;
+        self.beg_prev = None;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        if !self.value.is_finite() {
+            self.output.borrow_mut().state.clear_all();
+            self.beg_prev = None;
+            return;
+        }
+
+        // Optimization: Only encode if window position changed
+        let beg = cyclic_start(self.phase(), self.num_s);
+        if self.beg_prev == Some(beg) {
+            return;
+        }
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        set_cyclic_window(&mut output.state, 0, self.num_s, self.num_as, beg);
+
+        self.beg_prev = Some(beg);
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for CyclicTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for CyclicTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for CyclicTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::CyclicTransformer {
+            period: self.period,
+            num_s: self.num_s,
+            num_as: self.num_as,
+            num_t: self.output.borrow().num_t(),
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "CyclicTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for CyclicTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Transformers have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let ct = CyclicTransformer::new(24.0, 1024, 64, 2, 0);
+        assert_eq!(ct.period(), 24.0);
+        assert_eq!(ct.num_s(), 1024);
+        assert_eq!(ct.num_as(), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be positive and finite")]
+    fn test_invalid_period() {
+        CyclicTransformer::new(0.0, 1024, 64, 2, 0);
+    }
+
+    #[test]
+    fn test_phase_wraps() {
+        let mut ct = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+        ct.set_value(450.0);
+        assert_eq!(ct.phase(), 0.25);
+        ct.set_value(-1e-18);
+        assert!(ct.phase() < 1.0);
+    }
+
+    #[test]
+    fn test_window_wraps_around_end() {
+        let mut ct = CyclicTransformer::new(100.0, 100, 10, 2, 0);
+        ct.set_value(95.0);
+        ct.compute();
+
+        let acts = ct.output().borrow().state.get_acts();
+        assert_eq!(acts, vec![0, 1, 2, 3, 4, 95, 96, 97, 98, 99]);
+    }
+
+    #[test]
+    fn test_decode_range_wraps() {
+        let ct = CyclicTransformer::new(100.0, 100, 10, 2, 0);
+
+        // Windows at 95 and 98 both wrap past the end
+        let acts: Vec<usize> = (95..100).chain(0..8).collect();
+        assert_eq!(ct.decode_range(&acts), Some((95.0, 98.0)));
+
+        // Windows at 90 and 5 cross the boundary: lo > hi
+        let acts: Vec<usize> = (90..100).chain(0..15).collect();
+        assert_eq!(ct.decode_range(&acts), Some((90.0, 5.0)));
+
+        assert_eq!(ct.decode_range(&[]), None);
+    }
+
+    #[test]
+    fn test_non_finite_value() {
+        let mut ct = CyclicTransformer::new(24.0, 1024, 64, 2, 0);
+        ct.set_value(f64::NAN);
+        ct.compute();
+        assert_eq!(ct.output().borrow().state.num_set(), 0);
+    }
+}
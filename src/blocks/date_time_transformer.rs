@@ -0,0 +1,516 @@
+//! DateTimeTransformer - Encodes Unix timestamps as calendar features.
+//!
+//! This module provides the `DateTimeTransformer` block that turns a Unix
+//! timestamp (seconds) into one concatenated pattern made of independent
+//! calendar sub-fields. Each enabled field has its own width and active-bit
+//! count, and fields are laid out in a fixed order:
+//!
+//! 1. **time of day** - cyclic over 24 hours
+//! 2. **day of week** - cyclic over 7 days (Monday = 0)
+//! 3. **weekend** - binary (Saturday/Sunday vs weekday)
+//! 4. **holiday** - binary (date in a user-supplied list vs not)
+//! 5. **season** - cyclic over the calendar year
+//!
+//! Disabled fields take no bits. The cyclic fields wrap around, so 23:59 and
+//! 00:01 (or Sunday and Monday) share most of their active bits.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{DateTimeFields, DateTimeTransformer};
+//! use gnomics::{Block, OutputAccess};
+//!
+//! let fields = DateTimeFields::default()
+//!     .time_of_day(512, 32)
+//!     .day_of_week(256, 32)
+//!     .weekend(64, 32);
+//! let mut dt = DateTimeTransformer::new(fields, 2, 0);
+//! assert_eq!(dt.num_s(), 512 + 256 + 64);
+//!
+//! // Saturday 2024-06-15 12:00:00 UTC
+//! dt.set_value(1_718_452_800);
+//! dt.execute(false).unwrap();
+//! assert_eq!(dt.output().borrow().state.num_set(), 32 * 3);
+//! assert!(dt.is_weekend());
+//! ```
+
+use super::cyclic_transformer::{cyclic_phase, cyclic_start, set_cyclic_window};
+use crate::{BitField, Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Width and active-bit count of one calendar sub-field.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DateTimeField {
+    /// Number of statelets in this field
+    pub num_s: usize,
+    /// Number of active statelets in this field
+    pub num_as: usize,
+}
+
+/// Sub-field layout for a `DateTimeTransformer`.
+///
+/// Starts with every field disabled; enable fields with the builder methods.
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::blocks::DateTimeFields;
+///
+/// // Business calendar in UTC-5 with two holidays
+/// let fields = DateTimeFields::default()
+///     .time_of_day(512, 32)
+///     .weekend(64, 32)
+///     .holidays(64, 32, &[(2024, 7, 4), (2024, 12, 25)])
+///     .utc_offset(-5 * 3600);
+/// assert_eq!(fields.num_s(), 512 + 64 + 64);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DateTimeFields {
+    /// Time-of-day field (cyclic, 24 hours)
+    pub time_of_day: Option<DateTimeField>,
+    /// Day-of-week field (cyclic, 7 days)
+    pub day_of_week: Option<DateTimeField>,
+    /// Weekend flag field (binary)
+    pub weekend: Option<DateTimeField>,
+    /// Holiday flag field (binary)
+    pub holiday: Option<DateTimeField>,
+    /// Season field (cyclic, one calendar year)
+    pub season: Option<DateTimeField>,
+    /// Holiday dates as days since 1970-01-01
+    pub holiday_days: Vec<i64>,
+    /// Offset from UTC in seconds applied before extracting calendar fields
+    pub utc_offset: i64,
+}
+
+impl DateTimeFields {
+    /// Enable the time-of-day field.
+    pub fn time_of_day(mut self, num_s: usize, num_as: usize) -> Self {
+        self.time_of_day = Some(DateTimeField { num_s, num_as });
+        self
+    }
+
+    /// Enable the day-of-week field.
+    pub fn day_of_week(mut self, num_s: usize, num_as: usize) -> Self {
+        self.day_of_week = Some(DateTimeField { num_s, num_as });
+        self
+    }
+
+    /// Enable the weekend flag field.
+    pub fn weekend(mut self, num_s: usize, num_as: usize) -> Self {
+        self.weekend = Some(DateTimeField { num_s, num_as });
+        self
+    }
+
+    /// Enable the holiday flag field with a list of `(year, month, day)` dates.
+    pub fn holidays(mut self, num_s: usize, num_as: usize, dates: &[(i32, u32, u32)]) -> Self {
+        self.holiday = Some(DateTimeField { num_s, num_as });
+        self.holiday_days = dates
+            .iter()
+            .map(|&(y, m, d)| days_from_civil(y, m, d))
+            .collect();
+        self.holiday_days.sort_unstable();
+        self.holiday_days.dedup();
+        self
+    }
+
+    /// Enable the season field.
+    pub fn season(mut self, num_s: usize, num_as: usize) -> Self {
+        self.season = Some(DateTimeField { num_s, num_as });
+        self
+    }
+
+    /// Set the offset from UTC in seconds (e.g. `-5 * 3600` for UTC-5).
+    pub fn utc_offset(mut self, seconds: i64) -> Self {
+        self.utc_offset = seconds;
+        self
+    }
+
+    /// Total number of statelets across enabled fields.
+    pub fn num_s(&self) -> usize {
+        self.iter().map(|f| f.num_s).sum()
+    }
+
+    /// Enabled fields in layout order.
+    fn iter(&self) -> impl Iterator<Item = DateTimeField> {
+        [
+            self.time_of_day,
+            self.day_of_week,
+            self.weekend,
+            self.holiday,
+            self.season,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Calendar year containing `days` since 1970-01-01.
+fn year_from_days(days: i64) -> i32 {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let y = yoe + era * 400 + if mp >= 10 { 1 } else { 0 };
+    y as i32
+}
+
+/// Encodes Unix timestamps into concatenated calendar sub-field patterns.
+///
+/// # Algorithm
+///
+/// 1. Shift the timestamp by `utc_offset` and split it into whole days since
+///    1970-01-01 and seconds into the day
+/// 2. Cyclic fields activate a wrap-around window at their phase (same as
+///    `CyclicTransformer`)
+/// 3. Binary fields activate the first `num_as` bits when false and the last
+///    `num_as` bits when true
+/// 4. Concatenate fields in layout order
+///
+/// # Performance
+///
+/// - Encoding time: <1us (a few integer divisions per field)
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct DateTimeTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    fields: DateTimeFields,
+    num_s: usize, // Total number of statelets
+
+    // State
+    value: i64,
+    starts_prev: Option<[usize; 5]>, // Per-field window starts, for change detection
+}
+
+impl DateTimeTransformer {
+    /// Create a new DateTimeTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Sub-field layout
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility (unused in transformer, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - No field is enabled
+    /// - A field has `num_as` == 0 or `num_as` > `num_s`
+    /// - A binary field (weekend, holiday) has `2 * num_as` > `num_s`
+    /// - `num_t` < 2
+    pub fn new(mut fields: DateTimeFields, num_t: usize, seed: u64) -> Self {
+        assert!(
+            fields.iter().next().is_some(),
+            "at least one field must be enabled"
+        );
+        for f in fields.iter() {
+            assert!(f.num_as > 0, "num_as must be > 0");
+            assert!(f.num_as <= f.num_s, "num_as must be <= num_s");
+        }
+        for f in [fields.weekend, fields.holiday].into_iter().flatten() {
+            assert!(
+                2 * f.num_as <= f.num_s,
+                "binary fields need num_s >= 2 * num_as"
+            );
+        }
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        // `holiday_days` is public, so it may arrive unsorted; `is_holiday`
+        // relies on binary search
+        fields.holiday_days.sort_unstable();
+        fields.holiday_days.dedup();
+
+        let num_s = fields.num_s();
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            output,
+            fields,
+            num_s,
+            value: 0,
+            starts_prev: None,
+        }
+    }
+
+    /// Set the Unix timestamp (seconds since 1970-01-01 UTC) to encode.
+    pub fn set_value(&mut self, timestamp: i64) {
+        self.value = timestamp;
+    }
+
+    /// Get the current Unix timestamp.
+    pub fn get_value(&self) -> i64 {
+        self.value
+    }
+
+    /// Get the sub-field layout.
+    pub fn fields(&self) -> &DateTimeFields {
+        &self.fields
+    }
+
+    /// Get total number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Day of week of the current value in local time (Monday = 0).
+    pub fn weekday(&self) -> usize {
+        // 1970-01-01 was a Thursday
+        (self.local_days() + 3).rem_euclid(7) as usize
+    }
+
+    /// Whether the current value falls on a Saturday or Sunday in local time.
+    pub fn is_weekend(&self) -> bool {
+        self.weekday() >= 5
+    }
+
+    /// Whether the current value falls on a listed holiday in local time.
+    pub fn is_holiday(&self) -> bool {
+        self.fields
+            .holiday_days
+            .binary_search(&self.local_days())
+            .is_ok()
+    }
+
+    fn local_secs(&self) -> i64 {
+        self.value.saturating_add(self.fields.utc_offset)
+    }
+
+    fn local_days(&self) -> i64 {
+        self.local_secs().div_euclid(SECS_PER_DAY)
+    }
+
+    /// Window start of every field, in layout order (0 for disabled fields).
+    fn field_starts(&self) -> [usize; 5] {
+        let days = self.local_days();
+        let secs = self.local_secs().rem_euclid(SECS_PER_DAY);
+        let day_frac = secs as f64 / SECS_PER_DAY as f64;
+
+        let binary = |f: DateTimeField, flag: bool| if flag { f.num_s - f.num_as } else { 0 };
+
+        let mut starts = [0; 5];
+        if let Some(f) = self.fields.time_of_day {
+            starts[0] = cyclic_start(day_frac, f.num_s);
+        }
+        if let Some(f) = self.fields.day_of_week {
+            let phase = cyclic_phase(self.weekday() as f64 + day_frac, 7.0);
+            starts[1] = cyclic_start(phase, f.num_s);
+        }
+        if let Some(f) = self.fields.weekend {
+            starts[2] = binary(f, self.is_weekend());
+        }
+        if let Some(f) = self.fields.holiday {
+            starts[3] = binary(f, self.is_holiday());
+        }
+        if let Some(f) = self.fields.season {
+            let year = year_from_days(days);
+            let jan1 = days_from_civil(year, 1, 1);
+            let len = days_from_civil(year + 1, 1, 1) - jan1;
+            let phase = ((days - jan1) as f64 + day_frac) / len as f64;
+            starts[4] = cyclic_start(phase, f.num_s);
+        }
+        starts
+    }
+}
+
+impl Block for DateTimeTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.value = 0;
+        self.starts_prev = None;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        // Optimization: Only encode if some field window moved
+        let starts = self.field_starts();
+        if self.starts_prev == Some(starts) {
+            return;
+        }
+
+        let mut output = self.output.borrow_mut();
+        let state: &mut BitField = &mut output.state;
+        state.clear_all();
+
+        let fields = [
+            self.fields.time_of_day,
+            self.fields.day_of_week,
+            self.fields.weekend,
+            self.fields.holiday,
+            self.fields.season,
+        ];
+        let mut offset = 0;
+        for (field, &beg) in fields.iter().zip(starts.iter()) {
+            if let Some(f) = field {
+                set_cyclic_window(state, offset, f.num_s, f.num_as, beg);
+                offset += f.num_s;
+            }
+        }
+
+        self.starts_prev = Some(starts);
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.fields.holiday_days.capacity() * std::mem::size_of::<i64>()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for DateTimeTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for DateTimeTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for DateTimeTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::DateTimeTransformer {
+            fields: self.fields.clone(),
+            num_t: self.output.borrow().num_t(),
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "DateTimeTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for DateTimeTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Transformers have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_day_conversion() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+
+        for days in [-800_000, -1, 0, 59, 11_016, 19_723, 800_000] {
+            let year = year_from_days(days);
+            assert!(days_from_civil(year, 1, 1) <= days);
+            assert!(days < days_from_civil(year + 1, 1, 1));
+        }
+    }
+
+    #[test]
+    fn test_weekday() {
+        let mut dt = DateTimeTransformer::new(DateTimeFields::default().weekend(64, 32), 2, 0);
+
+        // 1970-01-01 was a Thursday
+        dt.set_value(0);
+        assert_eq!(dt.weekday(), 3);
+        assert!(!dt.is_weekend());
+
+        // 1969-12-28 was a Sunday
+        dt.set_value(-4 * SECS_PER_DAY);
+        assert_eq!(dt.weekday(), 6);
+        assert!(dt.is_weekend());
+    }
+
+    #[test]
+    fn test_utc_offset() {
+        // 2024-06-17 02:00 UTC is Sunday evening in UTC-5
+        let fields = DateTimeFields::default()
+            .weekend(64, 32)
+            .utc_offset(-5 * 3600);
+        let mut dt = DateTimeTransformer::new(fields, 2, 0);
+        dt.set_value(days_from_civil(2024, 6, 17) * SECS_PER_DAY + 2 * 3600);
+        assert!(dt.is_weekend());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one field must be enabled")]
+    fn test_no_fields() {
+        DateTimeTransformer::new(DateTimeFields::default(), 2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "binary fields need num_s >= 2 * num_as")]
+    fn test_binary_field_too_narrow() {
+        DateTimeTransformer::new(DateTimeFields::default().weekend(40, 32), 2, 0);
+    }
+}
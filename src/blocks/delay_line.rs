@@ -0,0 +1,389 @@
+//! DelayLine - Buffers recent input frames and outputs them side by side.
+//!
+//! This module provides the `DelayLine` block. It keeps the last input states
+//! in a buffer of its own and outputs the states at a set of lags as one
+//! concatenated pattern, so a pooler or classifier downstream sees a window of
+//! recent frames through a single connection. The window length is independent
+//! of the `num_t` history depth of the block feeding it.
+//!
+//! # Layout
+//!
+//! Lag `k` is the input state `k` steps ago (lag 0 is the current input). The
+//! frame for the `j`-th configured lag starts at word `j * num_w`, where `num_w`
+//! is the number of words in one input frame; frames start on word boundaries
+//! like the children of a `BlockInput`. [`DelayLine::frame_offset`] gives the
+//! first bit of each frame. Lags reaching back before the first step are empty.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{DelayLine, DiscreteTransformer};
+//! use gnomics::{Block, InputAccess, OutputAccess};
+//!
+//! let mut encoder = DiscreteTransformer::new(4, 64, 2, 0);
+//! let mut delay = DelayLine::new(64, 3, 2, 0);
+//! delay.input_mut().add_child(encoder.output(), 0);
+//! delay.init().unwrap();
+//!
+//! for value in [1, 2, 3] {
+//!     encoder.set_value(value);
+//!     encoder.execute(false).unwrap();
+//!     delay.execute(false).unwrap();
+//! }
+//!
+//! // Frames hold values 3, 2 and 1 (16 bits each)
+//! let acts = delay.output().borrow().state.get_acts();
+//! assert_eq!(acts[0], delay.frame_offset(0) + 48);
+//! assert_eq!(acts[16], delay.frame_offset(1) + 32);
+//! assert_eq!(acts[32], delay.frame_offset(2) + 16);
+//! ```
+
+use crate::bitfield::{bitfield_copy_words, BITS_PER_WORD};
+use crate::{BitField, Block, BlockBase, BlockBaseAccess, BlockInput, BlockOutput, Result};
+use crate::{InputAccess, OutputAccess};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Window of recent input frames, concatenated into one output.
+///
+/// # Performance
+///
+/// - Compute time: O(lags × input words)
+/// - Memory: `max lag + 1` buffered input frames
+pub struct DelayLine {
+    base: BlockBase,
+
+    /// Block input connection point
+    input: BlockInput,
+
+    /// Block output with history (one frame per lag)
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_i: usize,     // Number of input bits per frame
+    num_w: usize,     // Number of words per frame
+    lags: Vec<usize>, // Lags output, in frame order
+    num_t: usize,     // History depth
+
+    // State
+    frames: Vec<BitField>, // Ring buffer of recent input states
+    head: usize,           // Index of the current input state in frames
+}
+
+impl DelayLine {
+    /// Create a new DelayLine over lags `0..num_k`.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_i` - Number of input bits (the width of everything connected to the input)
+    /// * `num_k` - Number of most recent frames output (window length)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed (unused, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_i` == 0, `num_k` == 0 or `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::DelayLine;
+    /// use gnomics::OutputAccess;
+    ///
+    /// // The last 4 frames of a 100-bit input, each padded to 128 bits
+    /// let delay = DelayLine::new(100, 4, 2, 0);
+    /// assert_eq!(delay.frame_offset(3), 384);
+    /// assert_eq!(delay.output().borrow().state.num_bits(), 484);
+    /// ```
+    pub fn new(num_i: usize, num_k: usize, num_t: usize, seed: u64) -> Self {
+        assert!(num_k > 0, "num_k must be > 0");
+        Self::with_lag_list(num_i, (0..num_k).collect(), num_t, seed)
+    }
+
+    /// Output only the given lags, in the given order.
+    ///
+    /// Replaces the window set in [`new`](Self::new), so it must be called
+    /// before the output is connected to other blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lags` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::DelayLine;
+    ///
+    /// // Current frame plus the frames 5 and 10 steps ago
+    /// let delay = DelayLine::new(64, 1, 2, 0).with_lags(&[0, 5, 10]);
+    /// assert_eq!(delay.lags(), &[0, 5, 10]);
+    /// assert_eq!(delay.max_lag(), 10);
+    /// ```
+    pub fn with_lags(self, lags: &[usize]) -> Self {
+        Self::with_lag_list(self.num_i, lags.to_vec(), self.num_t, self.base.seed())
+    }
+
+    fn with_lag_list(num_i: usize, lags: Vec<usize>, num_t: usize, seed: u64) -> Self {
+        assert!(num_i > 0, "num_i must be > 0");
+        assert!(!lags.is_empty(), "lags must not be empty");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let num_w = num_i.div_ceil(BITS_PER_WORD);
+        let max_lag = lags.iter().copied().max().unwrap_or(0);
+        let num_s = (lags.len() - 1) * num_w * BITS_PER_WORD + num_i;
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        let mut delay = Self {
+            base: BlockBase::new(seed),
+            input: BlockInput::new(),
+            output,
+            num_i,
+            num_w,
+            lags,
+            num_t,
+            frames: vec![BitField::new(num_i); max_lag + 1],
+            head: 0,
+        };
+
+        delay.base.set_initialized(true);
+
+        delay
+    }
+
+    /// Get the first output bit of the frame for the `j`-th lag.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` >= the number of lags.
+    pub fn frame_offset(&self, j: usize) -> usize {
+        assert!(j < self.lags.len(), "frame index must be < number of lags");
+        j * self.num_w * BITS_PER_WORD
+    }
+
+    /// Get the input state `lag` steps ago (lag 0 is the current input).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lag` > [`max_lag`](Self::max_lag).
+    pub fn get_frame(&self, lag: usize) -> &BitField {
+        assert!(lag < self.frames.len(), "lag must be <= max_lag");
+        let depth = self.frames.len();
+        &self.frames[(self.head + depth - lag) % depth]
+    }
+
+    /// Get the lags output, in frame order.
+    pub fn lags(&self) -> &[usize] {
+        &self.lags
+    }
+
+    /// Get the largest lag buffered.
+    pub fn max_lag(&self) -> usize {
+        self.frames.len() - 1
+    }
+
+    /// Get number of input bits per frame.
+    pub fn num_i(&self) -> usize {
+        self.num_i
+    }
+}
+
+impl Block for DelayLine {
+    fn init(&mut self) -> Result<()> {
+        if self.input.num_bits() != self.num_i {
+            return Err(crate::GnomicsError::InvalidInputSize {
+                expected: self.num_i,
+                actual: self.input.num_bits(),
+            });
+        }
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.borrow_mut().clear();
+        for frame in &mut self.frames {
+            frame.clear_all();
+        }
+        self.head = 0;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        self.input.pull();
+    }
+
+    fn compute(&mut self) {
+        assert_eq!(
+            self.input.num_bits(),
+            self.num_i,
+            "DelayLine input must have num_i bits"
+        );
+
+        // Every step shifts the window, even if the input repeats
+        self.head = (self.head + 1) % self.frames.len();
+        self.frames[self.head].copy_from(&self.input.state);
+
+        let mut output = self.output.borrow_mut();
+        for (j, &lag) in self.lags.iter().enumerate() {
+            let depth = self.frames.len();
+            let frame = &self.frames[(self.head + depth - lag) % depth];
+            bitfield_copy_words(&mut output.state, frame, j * self.num_w, 0, self.num_w);
+        }
+    }
+
+    fn learn(&mut self) {
+        // No learning in delay line
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.lags.capacity() * std::mem::size_of::<usize>()
+            + self.frames.iter().map(|f| f.memory_usage()).sum::<usize>()
+            + self.input.memory_usage()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        self.input.get_source_blocks()
+    }
+}
+
+impl BlockBaseAccess for DelayLine {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl InputAccess for DelayLine {
+    fn input(&self) -> &BlockInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut BlockInput {
+        &mut self.input
+    }
+}
+
+impl OutputAccess for DelayLine {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl crate::network_config::BlockConfigurable for DelayLine {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::DelayLine {
+            num_i: self.num_i,
+            lags: self.lags.clone(),
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "DelayLine"
+    }
+}
+
+impl crate::network_config::BlockStateful for DelayLine {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Buffered frames are transient, like block output history
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_support::{feed, with_source};
+
+    fn delay_with_input(delay: DelayLine) -> (DelayLine, Rc<RefCell<BlockOutput>>) {
+        let num_i = delay.num_i();
+        with_source(delay, num_i)
+    }
+
+    #[test]
+    fn test_frames_are_word_aligned() {
+        // 80 bits pad to 96 with 32-bit words and to 128 with 64-bit words
+        let (mut d, source) = delay_with_input(DelayLine::new(80, 3, 2, 0));
+        let stride = 80usize.next_multiple_of(BITS_PER_WORD);
+        assert_eq!(d.frame_offset(1), stride);
+        assert_eq!(d.frame_offset(2), 2 * stride);
+        assert_eq!(d.output().borrow().state.num_bits(), 2 * stride + 80);
+
+        for i in 0..3 {
+            feed(&source, [i]);
+            d.execute(false).unwrap();
+        }
+        let expected = vec![2, d.frame_offset(1) + 1, d.frame_offset(2)];
+        assert_eq!(d.output().borrow().state.get_acts(), expected);
+    }
+
+    #[test]
+    fn test_lags_before_first_step_are_empty() {
+        let (mut d, source) = delay_with_input(DelayLine::new(32, 1, 2, 0).with_lags(&[0, 4]));
+        feed(&source, [7]);
+        d.execute(false).unwrap();
+        assert_eq!(d.output().borrow().state.get_acts(), vec![7]);
+        assert_eq!(d.get_frame(4).num_set(), 0);
+    }
+
+    #[test]
+    fn test_repeated_input_still_shifts() {
+        let (mut d, source) = delay_with_input(DelayLine::new(32, 2, 2, 0));
+        feed(&source, [1]);
+        d.execute(false).unwrap();
+        // Source unchanged: the pulled input is the same, but the window moves
+        d.execute(false).unwrap();
+        assert_eq!(d.get_frame(1).get_acts(), vec![1]);
+        assert_eq!(
+            d.output().borrow().state.get_acts(),
+            vec![1, d.frame_offset(1) + 1]
+        );
+    }
+
+    #[test]
+    fn test_init_rejects_wrong_input_size() {
+        let source = Rc::new(RefCell::new(BlockOutput::new()));
+        source.borrow_mut().setup(2, 16);
+        let mut d = DelayLine::new(32, 2, 2, 0);
+        d.input_mut().add_child(source, 0);
+        assert!(d.init().is_err());
+    }
+}
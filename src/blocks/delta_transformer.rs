@@ -0,0 +1,459 @@
+//! DeltaTransformer - Encodes the signed change between consecutive values.
+//!
+//! This module provides the `DeltaTransformer` block that encodes the first
+//! difference of a scalar signal (or its rate of change, when the time between
+//! samples is given) as an overlapping window, like `ScalarTransformer` does for
+//! the value itself. Rising, flat and falling signals map to distinct regions.
+//!
+//! # Semantic Properties
+//!
+//! - **Signed Encoding**: Negative, zero and positive deltas occupy different bits
+//! - **Configurable Sensitivity**: `[min_delta, max_delta]` sets the encoded range;
+//!   larger deltas saturate at the ends
+//! - **Optional Smoothing**: Exponential moving average of the delta
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::DeltaTransformer;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+//!
+//! dt.set_value(10.0);
+//! dt.execute(false).unwrap();
+//! dt.set_value(10.5);
+//! dt.execute(false).unwrap();
+//!
+//! assert_eq!(dt.get_delta(), 0.5);
+//! assert_eq!(dt.output().borrow().state.num_set(), 128);
+//! ```
+
+use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Encodes the signed change between consecutive scalar values.
+///
+/// # Algorithm
+///
+/// 1. `raw = (value - previous) / dt` (`dt = 1` unless given)
+/// 2. `delta = smoothing * raw + (1 - smoothing) * delta` (`smoothing = 1` disables)
+/// 3. `percent = (clamp(delta) - min_delta) / (max_delta - min_delta)`
+/// 4. Activate `num_as` bits starting at `beg = dif_s * percent`
+///
+/// The first value after construction or `clear()` has no predecessor and
+/// encodes a delta of zero. Non-finite values (NaN, infinity) encode as an
+/// empty pattern and are skipped: the delta and the previous value are kept,
+/// so the next finite value is compared against the last finite one.
+#[derive(Clone)]
+pub struct DeltaTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    min_delta: f64,
+    max_delta: f64,
+    num_s: usize,   // Number of statelets
+    num_as: usize,  // Number of active statelets
+    dif_s: usize,   // num_s - num_as
+    smoothing: f64, // EMA factor in (0, 1]; 1 = no smoothing
+    num_t: usize,
+
+    // State
+    value: f64,
+    dt: f64,
+    prev_value: Option<f64>,
+    delta: f64,
+    beg_prev: usize, // For change detection optimization
+}
+
+impl DeltaTransformer {
+    /// Create a new DeltaTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_delta` - Most negative delta encoded (smaller deltas saturate)
+    /// * `max_delta` - Most positive delta encoded (larger deltas saturate)
+    /// * `num_s` - Number of statelets (output bits)
+    /// * `num_as` - Number of active statelets
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed (unused, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `max_delta` <= `min_delta`
+    /// - `num_as` > `num_s`
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::DeltaTransformer;
+    ///
+    /// // Temperature changes of up to 2 degrees per step in either direction
+    /// let dt = DeltaTransformer::new(-2.0, 2.0, 2048, 256, 2, 0);
+    /// ```
+    pub fn new(
+        min_delta: f64,
+        max_delta: f64,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            max_delta > min_delta,
+            "max_delta must be greater than min_delta"
+        );
+        assert!(num_as <= num_s, "num_as must be <= num_s");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        let mut dt = Self {
+            base: BlockBase::new(seed),
+            output,
+            min_delta,
+            max_delta,
+            num_s,
+            num_as,
+            dif_s: num_s - num_as,
+            smoothing: 1.0,
+            num_t,
+            value: 0.0,
+            dt: 1.0,
+            prev_value: None,
+            delta: 0.0,
+            beg_prev: usize::MAX,
+        };
+
+        dt.base.set_initialized(true);
+
+        dt
+    }
+
+    /// Set the exponential smoothing factor applied to the delta.
+    ///
+    /// `1.0` (the default) encodes the raw delta; smaller values average over
+    /// roughly `1 / smoothing` steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `smoothing` is not in (0.0, 1.0]
+    pub fn set_smoothing(&mut self, smoothing: f64) {
+        assert!(
+            smoothing > 0.0 && smoothing <= 1.0,
+            "smoothing must be in (0.0, 1.0]"
+        );
+        self.smoothing = smoothing;
+    }
+
+    /// Builder form of [`set_smoothing`](Self::set_smoothing).
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.set_smoothing(smoothing);
+        self
+    }
+
+    /// Set the next value, one step after the previous one.
+    ///
+    /// Non-finite values encode as an empty pattern and leave the delta
+    /// untouched.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+        self.dt = 1.0;
+    }
+
+    /// Set the next value `dt` time units after the previous one.
+    ///
+    /// The encoded quantity becomes a rate of change, `delta / dt`, which stays
+    /// comparable when samples arrive at irregular intervals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dt` <= 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::DeltaTransformer;
+    /// use gnomics::Block;
+    ///
+    /// let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    /// dt.set_value(0.0);
+    /// dt.execute(false).unwrap();
+    /// dt.set_value_dt(3.0, 6.0); // +3 over 6 time units
+    /// dt.execute(false).unwrap();
+    /// assert_eq!(dt.get_delta(), 0.5);
+    /// ```
+    pub fn set_value_dt(&mut self, value: f64, dt: f64) {
+        assert!(dt > 0.0, "dt must be > 0");
+        self.value = value;
+        self.dt = dt;
+    }
+
+    /// Get the current value.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Get the (smoothed) delta encoded by the last compute.
+    pub fn get_delta(&self) -> f64 {
+        self.delta
+    }
+
+    /// Get minimum encoded delta.
+    pub fn min_delta(&self) -> f64 {
+        self.min_delta
+    }
+
+    /// Get maximum encoded delta.
+    pub fn max_delta(&self) -> f64 {
+        self.max_delta
+    }
+
+    /// Get the smoothing factor.
+    pub fn smoothing(&self) -> f64 {
+        self.smoothing
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+}
+
+impl Block for DeltaTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.value = 0.0;
+        self.dt = 1.0;
+        self.prev_value = None;
+        self.delta = 0.0;
+        self.beg_prev = usize::MAX;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        // Non-finite values encode as empty and must not reach the EMA
+        if !self.value.is_finite() {
+            self.output.borrow_mut().state.clear_all();
+            self.beg_prev = usize::MAX;
+            return;
+        }
+
+        let raw = match self.prev_value {
+            Some(prev) => (self.value - prev) / self.dt,
+            None => 0.0,
+        };
+        self.prev_value = Some(self.value);
+        self.dt = 1.0;
+
+        self.delta = self.smoothing * raw + (1.0 - self.smoothing) * self.delta;
+
+        // Encode clamped delta as a window position
+        let clamped = self.delta.clamp(self.min_delta, self.max_delta);
+        let percent = (clamped - self.min_delta) / (self.max_delta - self.min_delta);
+        let beg = ((self.dif_s as f64) * percent) as usize;
+
+        // Optimization: Leave output untouched if the window did not move
+        if beg == self.beg_prev {
+            return;
+        }
+        self.beg_prev = beg;
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        output.state.set_range(beg, self.num_as);
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for DeltaTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for DeltaTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for DeltaTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::DeltaTransformer {
+            min_delta: self.min_delta,
+            max_delta: self.max_delta,
+            num_s: self.num_s,
+            num_as: self.num_as,
+            smoothing: self.smoothing,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "DeltaTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for DeltaTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Transformers have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+        assert_eq!(dt.min_delta(), -1.0);
+        assert_eq!(dt.max_delta(), 1.0);
+        assert_eq!(dt.smoothing(), 1.0);
+        assert_eq!(dt.num_s(), 1024);
+        assert_eq!(dt.num_as(), 128);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_delta must be greater than min_delta")]
+    fn test_invalid_range() {
+        DeltaTransformer::new(1.0, -1.0, 1024, 128, 2, 0);
+    }
+
+    #[test]
+    fn test_first_value_is_zero_delta() {
+        let mut dt = DeltaTransformer::new(-1.0, 1.0, 1000, 100, 2, 0);
+        dt.set_value(42.0);
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.0);
+        assert_eq!(dt.output.borrow().state.get_acts()[0], 450);
+    }
+
+    #[test]
+    fn test_saturates_at_range_ends() {
+        let mut dt = DeltaTransformer::new(-1.0, 1.0, 1000, 100, 2, 0);
+        dt.set_value(0.0);
+        dt.compute();
+
+        dt.set_value(10.0);
+        dt.compute();
+        assert_eq!(dt.output.borrow().state.get_acts()[0], 900);
+
+        dt.set_value(-10.0);
+        dt.compute();
+        assert_eq!(dt.output.borrow().state.get_acts()[0], 0);
+    }
+
+    #[test]
+    fn test_smoothing_averages_delta() {
+        let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0).with_smoothing(0.5);
+        dt.set_value(0.0);
+        dt.compute();
+
+        dt.set_value(1.0);
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.5);
+
+        // Flat input decays the smoothed delta
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.25);
+    }
+
+    #[test]
+    fn test_non_finite_value_is_skipped() {
+        let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0).with_smoothing(0.5);
+        dt.set_value(0.0);
+        dt.compute();
+        dt.set_value(1.0);
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.5);
+
+        dt.set_value(f64::NAN);
+        dt.compute();
+        assert_eq!(dt.output.borrow().state.num_set(), 0);
+        assert_eq!(dt.get_delta(), 0.5);
+
+        // Next finite value is compared against 1.0 and re-encodes
+        dt.set_value(1.5);
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.5);
+        assert_eq!(dt.output.borrow().state.num_set(), 128);
+    }
+
+    #[test]
+    fn test_clear_forgets_previous_value() {
+        let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+        dt.set_value(5.0);
+        dt.compute();
+        dt.clear();
+
+        dt.set_value(0.0);
+        dt.compute();
+        assert_eq!(dt.get_delta(), 0.0);
+    }
+}
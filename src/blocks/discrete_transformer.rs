@@ -35,6 +35,7 @@
 //! assert_eq!(overlap, 0);  // No overlap
 //! ```
 
+use crate::bitfield::BitField;
 use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
 use std::cell::RefCell;
 use std::path::Path;
@@ -175,6 +176,52 @@ impl DiscreteTransformer {
     pub fn num_as(&self) -> usize {
         self.num_as
     }
+
+    /// Decode active statelets into the categories they encode.
+    ///
+    /// Returns every value whose window has more than half of its statelets
+    /// in `acts`, in increasing order. Used to read predictions made over
+    /// this transformer's output (e.g. `Network::explain_anomaly()`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::DiscreteTransformer;
+    /// use gnomics::{Block, OutputAccess};
+    ///
+    /// let mut dt = DiscreteTransformer::new(4, 64, 2, 0);
+    /// dt.set_value(2);
+    /// dt.execute(false).unwrap();
+    ///
+    /// let acts = dt.output().borrow().state.get_acts();
+    /// assert_eq!(dt.decode_values(&acts), vec![2]);
+    /// ```
+    pub fn decode_values(&self, acts: &[usize]) -> Vec<usize> {
+        let mut active = BitField::new(self.num_s);
+        active.set_acts(acts);
+
+        (0..self.num_v)
+            .filter(|&v| {
+                let beg = self.window_start(v);
+                let num_on = (beg..beg + self.num_as)
+                    .filter(|&s| active.get_bit(s) > 0)
+                    .count();
+                2 * num_on > self.num_as
+            })
+            .collect()
+    }
+
+    /// First statelet of a value's window.
+    fn window_start(&self, value: usize) -> usize {
+        // Calculate percentage position in value space
+        let percent = if self.num_v > 1 {
+            (value as f64) / ((self.num_v - 1) as f64)
+        } else {
+            0.0
+        };
+
+        ((self.dif_s as f64) * percent) as usize
+    }
 }
 
 impl Block for DiscreteTransformer {
@@ -213,15 +260,8 @@ impl Block for DiscreteTransformer {
 
         // Optimization: Only encode if value changed (matches C++ implementation)
         if self.value != self.value_prev {
-            // Calculate percentage position in value space
-            let percent = if self.num_v > 1 {
-                (self.value as f64) / ((self.num_v - 1) as f64)
-            } else {
-                0.0
-            };
-
             // Calculate starting position in statelet space
-            let beg = ((self.dif_s as f64) * percent) as usize;
+            let beg = self.window_start(self.value);
 
             // Clear output and activate contiguous window
             let mut output = self.output.borrow_mut();
@@ -0,0 +1,469 @@
+//! HyperGridTransformer - Encodes N-dimensional vectors with multi-scale grids.
+//!
+//! This module provides the `HyperGridTransformer` block that encodes a
+//! continuous vector as a whole instead of one `ScalarTransformer` per
+//! dimension. The vector is projected onto `num_grids` randomly oriented
+//! subspaces, each tiled by a periodic grid at its own scale (like grid cells).
+//! Each grid contributes exactly one active bit (the cell the projection falls
+//! in), so joint structure between dimensions is preserved.
+//!
+//! # Semantic Properties
+//!
+//! - **Distance-Preserving**: Overlap falls off with Euclidean distance
+//! - **Multi-Scale**: Fine grids separate close points, coarse grids keep
+//!   far-apart points partially similar
+//! - **Fixed Sparsity**: Always `num_grids` active bits for finite input
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::HyperGridTransformer;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! // 3D position, 64 grids of 8x8 cells, periods from 1.0 to 20.0
+//! let mut hg = HyperGridTransformer::new(3, 64, 8, 2, 1.0, 20.0, 2, 0);
+//! assert_eq!(hg.num_s(), 64 * 8 * 8);
+//!
+//! hg.set_value(&[0.5, -2.0, 3.25]);
+//! hg.execute(false).unwrap();
+//! assert_eq!(hg.output().borrow().state.num_set(), 64);
+//! ```
+
+use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use rand::Rng;
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Encodes N-dimensional vectors by projecting onto random periodic grids.
+///
+/// # Algorithm
+///
+/// For each grid `g` (with period `p_g`, random orthonormal basis
+/// `u_g1..u_gk` and random phase offsets `o_g1..o_gk`):
+///
+/// 1. Project: `c_a = frac(dot(u_ga, x) / p_g + o_ga)` for each grid axis `a`
+/// 2. Bin: `b_a = floor(c_a * num_bins)`
+/// 3. Activate bit `g * num_bins^k + sum(b_a * num_bins^a)`
+///
+/// Periods are spaced geometrically from `min_period` to `max_period`.
+///
+/// # Performance
+///
+/// - Encoding time: O(num_grids * grid_dims * num_dims)
+/// - Memory: projections are `num_grids * grid_dims * num_dims` f64 values
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct HyperGridTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_dims: usize,  // Input vector dimension
+    num_grids: usize, // Number of grids (= active statelets)
+    num_bins: usize,  // Bins per grid axis
+    grid_dims: usize, // Dimension of each grid subspace
+    min_period: f64,  // Period of the finest grid
+    max_period: f64,  // Period of the coarsest grid
+    num_s: usize,     // Number of statelets
+
+    // Derived from seed
+    basis: Vec<f64>,   // [grid][axis][dim] unit projection vectors
+    offsets: Vec<f64>, // [grid][axis] phase offsets in [0, 1)
+    periods: Vec<f64>, // [grid] grid periods
+
+    // State
+    value: Vec<f64>,
+    cells: Vec<usize>,              // Active bit per grid (scratch)
+    cells_prev: Option<Vec<usize>>, // For change detection optimization
+}
+
+impl HyperGridTransformer {
+    /// Create a new HyperGridTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_dims` - Input vector dimension
+    /// * `num_grids` - Number of grids (each adds one active bit)
+    /// * `num_bins` - Bins per grid axis
+    /// * `grid_dims` - Dimension of each grid subspace (1-3, must be <= `num_dims`)
+    /// * `min_period` - Period of the finest grid, in input units
+    /// * `max_period` - Period of the coarsest grid, in input units
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for grid orientations and offsets
+    ///
+    /// The output has `num_grids * num_bins^grid_dims` statelets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_dims`, `num_grids` or `grid_dims` is 0, or `num_bins` < 2
+    /// - `grid_dims` > `num_dims` or `grid_dims` > 3
+    /// - `min_period` is not positive and finite, or `max_period` < `min_period`
+    /// - `num_t` < 2
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_dims: usize,
+        num_grids: usize,
+        num_bins: usize,
+        grid_dims: usize,
+        min_period: f64,
+        max_period: f64,
+        num_t: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(num_dims > 0, "num_dims must be > 0");
+        assert!(num_grids > 0, "num_grids must be > 0");
+        assert!(num_bins >= 2, "num_bins must be at least 2");
+        assert!(
+            grid_dims > 0 && grid_dims <= 3,
+            "grid_dims must be between 1 and 3"
+        );
+        assert!(grid_dims <= num_dims, "grid_dims must be <= num_dims");
+        assert!(
+            min_period > 0.0 && min_period.is_finite(),
+            "min_period must be positive and finite"
+        );
+        assert!(
+            max_period >= min_period && max_period.is_finite(),
+            "max_period must be finite and >= min_period"
+        );
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let num_s = num_grids * num_bins.pow(grid_dims as u32);
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        let mut hg = Self {
+            base: BlockBase::new(seed),
+            output,
+            num_dims,
+            num_grids,
+            num_bins,
+            grid_dims,
+            min_period,
+            max_period,
+            num_s,
+            basis: Vec::with_capacity(num_grids * grid_dims * num_dims),
+            offsets: Vec::with_capacity(num_grids * grid_dims),
+            periods: Vec::with_capacity(num_grids),
+            value: vec![0.0; num_dims],
+            cells: vec![0; num_grids],
+            cells_prev: None,
+        };
+        hg.init_grids();
+        hg
+    }
+
+    /// Draw grid orientations, offsets and periods from the block RNG.
+    fn init_grids(&mut self) {
+        let ratio = self.max_period / self.min_period;
+
+        for g in 0..self.num_grids {
+            let t = if self.num_grids > 1 {
+                g as f64 / (self.num_grids - 1) as f64
+            } else {
+                0.0
+            };
+            self.periods.push(self.min_period * ratio.powf(t));
+
+            // Random orthonormal basis via Gram-Schmidt on Gaussian vectors
+            let beg = self.basis.len();
+            for a in 0..self.grid_dims {
+                let mut v: Vec<f64> = loop {
+                    let mut v: Vec<f64> = (0..self.num_dims)
+                        .map(|_| gaussian(self.base.rng()))
+                        .collect();
+                    for b in 0..a {
+                        let u = &self.basis[beg + b * self.num_dims..beg + (b + 1) * self.num_dims];
+                        let d: f64 = u.iter().zip(&v).map(|(x, y)| x * y).sum();
+                        v.iter_mut().zip(u).for_each(|(x, y)| *x -= d * y);
+                    }
+                    if norm(&v) > 1e-6 {
+                        break v;
+                    }
+                };
+                let n = norm(&v);
+                v.iter_mut().for_each(|x| *x /= n);
+                self.basis.extend_from_slice(&v);
+
+                let offset: f64 = self.base.rng().gen();
+                self.offsets.push(offset);
+            }
+        }
+    }
+
+    /// Set the vector to encode.
+    ///
+    /// Vectors containing non-finite components encode as an empty pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value.len() != num_dims`.
+    pub fn set_value(&mut self, value: &[f64]) {
+        assert_eq!(
+            value.len(),
+            self.num_dims,
+            "value must have num_dims components"
+        );
+        self.value.copy_from_slice(value);
+    }
+
+    /// Get the current vector.
+    pub fn get_value(&self) -> &[f64] {
+        &self.value
+    }
+
+    /// Get grid periods, finest first.
+    pub fn periods(&self) -> &[f64] {
+        &self.periods
+    }
+
+    /// Get input vector dimension.
+    pub fn num_dims(&self) -> usize {
+        self.num_dims
+    }
+
+    /// Get number of grids.
+    pub fn num_grids(&self) -> usize {
+        self.num_grids
+    }
+
+    /// Get number of bins per grid axis.
+    pub fn num_bins(&self) -> usize {
+        self.num_bins
+    }
+
+    /// Get grid subspace dimension.
+    pub fn grid_dims(&self) -> usize {
+        self.grid_dims
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_grids
+    }
+}
+
+/// Standard normal sample (Box-Muller).
+fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+impl Block for HyperGridTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.value.iter_mut().for_each(|v| *v = 0.0);
+        self.cells_prev = None;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        if !self.value.iter().all(|v| v.is_finite()) {
+            self.output.borrow_mut().state.clear_all();
+            self.cells_prev = None;
+            return;
+        }
+
+        let cells_per_grid = self.num_bins.pow(self.grid_dims as u32);
+        for g in 0..self.num_grids {
+            let mut cell = 0;
+            let mut stride = 1;
+            for a in 0..self.grid_dims {
+                let ga = g * self.grid_dims + a;
+                let u = &self.basis[ga * self.num_dims..(ga + 1) * self.num_dims];
+                let proj: f64 = u.iter().zip(&self.value).map(|(x, y)| x * y).sum();
+                let phase = (proj / self.periods[g] + self.offsets[ga]).rem_euclid(1.0);
+                let bin = ((phase * self.num_bins as f64) as usize).min(self.num_bins - 1);
+                cell += bin * stride;
+                stride *= self.num_bins;
+            }
+            self.cells[g] = g * cells_per_grid + cell;
+        }
+
+        // Optimization: Only encode if some grid cell changed
+        if self.cells_prev.as_ref() == Some(&self.cells) {
+            return;
+        }
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        for &c in &self.cells {
+            output.state.set_bit(c);
+        }
+
+        match &mut self.cells_prev {
+            Some(prev) => prev.copy_from_slice(&self.cells),
+            None => self.cells_prev = Some(self.cells.clone()),
+        }
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.basis.capacity()
+                + self.offsets.capacity()
+                + self.periods.capacity()
+                + self.value.capacity())
+                * std::mem::size_of::<f64>()
+            + self.cells.capacity() * 2 * std::mem::size_of::<usize>()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for HyperGridTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for HyperGridTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for HyperGridTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::HyperGridTransformer {
+            num_dims: self.num_dims,
+            num_grids: self.num_grids,
+            num_bins: self.num_bins,
+            grid_dims: self.grid_dims,
+            min_period: self.min_period,
+            max_period: self.max_period,
+            num_t: self.output.borrow().num_t(),
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "HyperGridTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for HyperGridTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        // Grids are regenerated from the seed
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Transformers have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let hg = HyperGridTransformer::new(4, 16, 8, 2, 1.0, 10.0, 2, 0);
+        assert_eq!(hg.num_s(), 16 * 64);
+        assert_eq!(hg.num_as(), 16);
+        assert_eq!(hg.periods().len(), 16);
+        assert!((hg.periods()[0] - 1.0).abs() < 1e-12);
+        assert!((hg.periods()[15] - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_basis_orthonormal() {
+        let hg = HyperGridTransformer::new(5, 8, 4, 3, 1.0, 4.0, 2, 3);
+        let d = hg.num_dims;
+
+        for g in 0..hg.num_grids {
+            for a in 0..3 {
+                for b in 0..3 {
+                    let u = &hg.basis[(g * 3 + a) * d..(g * 3 + a + 1) * d];
+                    let v = &hg.basis[(g * 3 + b) * d..(g * 3 + b + 1) * d];
+                    let dot: f64 = u.iter().zip(v).map(|(x, y)| x * y).sum();
+                    let expected = if a == b { 1.0 } else { 0.0 };
+                    assert!((dot - expected).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_one_bit_per_grid() {
+        let mut hg = HyperGridTransformer::new(2, 10, 6, 2, 0.5, 5.0, 2, 0);
+        hg.set_value(&[1.3, -7.9]);
+        hg.compute();
+
+        let acts = hg.output().borrow().state.get_acts();
+        assert_eq!(acts.len(), 10);
+        for (g, &a) in acts.iter().enumerate() {
+            assert_eq!(a / 36, g);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "grid_dims must be <= num_dims")]
+    fn test_grid_dims_too_large() {
+        HyperGridTransformer::new(1, 8, 8, 2, 1.0, 2.0, 2, 0);
+    }
+
+    #[test]
+    fn test_non_finite_value() {
+        let mut hg = HyperGridTransformer::new(2, 8, 8, 2, 1.0, 2.0, 2, 0);
+        hg.set_value(&[0.0, f64::NAN]);
+        hg.compute();
+        assert_eq!(hg.output().borrow().state.num_set(), 0);
+    }
+}
@@ -0,0 +1,472 @@
+//! ImageTransformer - Encodes 2D grayscale or binary images.
+//!
+//! This module provides the `ImageTransformer` block that turns a
+//! `width x height` array of pixel intensities into a row-major binary
+//! pattern. Pixels can be average-pooled (downsampled) and are then binarized
+//! by a fixed threshold, the image mean, or edge strength. The output keeps
+//! the 2D layout, and [`ImageTransformer::pooler_topology`] hands that shape to
+//! a topological `PatternPooler`.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{ImageBinarize, ImageTransformer};
+//! use gnomics::{Block, OutputAccess};
+//!
+//! // 28x28 grayscale image, 2x downsampling, threshold at 0.5
+//! let mut it = ImageTransformer::new(28, 28, 2, 0)
+//!     .with_downsample(2)
+//!     .with_binarize(ImageBinarize::Threshold(0.5));
+//! assert_eq!(it.output_shape(), (14, 14));
+//!
+//! let mut pixels = vec![0.0; 28 * 28];
+//! pixels[0] = 1.0;
+//! pixels[1] = 1.0;
+//! it.set_pixels(&pixels);
+//! it.execute(false).unwrap();
+//!
+//! // The top-left 2x2 cell averages to 0.5 and is active
+//! assert_eq!(it.output().borrow().state.get_acts(), vec![0]);
+//! ```
+
+use crate::blocks::PoolerTopology;
+use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// How (downsampled) pixel intensities are turned into bits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ImageBinarize {
+    /// Active where intensity >= threshold (binary data: 0/1 with 0.5)
+    Threshold(f64),
+    /// Active where intensity >= the image mean (ignores global brightness)
+    Mean,
+    /// Active where the Sobel gradient magnitude >= threshold (outlines)
+    Edge(f64),
+}
+
+impl Default for ImageBinarize {
+    fn default() -> Self {
+        ImageBinarize::Threshold(0.5)
+    }
+}
+
+/// Encodes 2D pixel arrays into row-major binary patterns.
+///
+/// # Algorithm
+///
+/// 1. Average-pool `downsample x downsample` pixel blocks (partial blocks at
+///    the right and bottom edges average the pixels they cover)
+/// 2. Binarize each pooled pixel by threshold, mean or edge strength
+/// 3. Set bit `y * out_width + x` for every active pooled pixel
+///
+/// # Performance
+///
+/// - Encoding time: O(width * height)
+/// - Memory: one f64 per input pixel plus output history
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct ImageTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    width: usize,      // Input width in pixels
+    height: usize,     // Input height in pixels
+    downsample: usize, // Pooling factor
+    out_width: usize,  // Output width (ceil(width / downsample))
+    out_height: usize, // Output height (ceil(height / downsample))
+    binarize: ImageBinarize,
+    num_t: usize,
+
+    // State
+    pixels: Vec<f64>, // Input intensities, row-major
+    pooled: Vec<f64>, // Downsampled intensities (scratch)
+    dirty: bool,      // For change detection optimization
+}
+
+impl ImageTransformer {
+    /// Create a new ImageTransformer with threshold 0.5 and no downsampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Image width in pixels
+    /// * `height` - Image height in pixels
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility (unused in transformer, for consistency)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is 0, or `num_t` < 2.
+    pub fn new(width: usize, height: usize, num_t: usize, seed: u64) -> Self {
+        assert!(width > 0 && height > 0, "image must be non-empty");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, width * height);
+
+        Self {
+            base: BlockBase::new(seed),
+            output,
+            width,
+            height,
+            downsample: 1,
+            out_width: width,
+            out_height: height,
+            binarize: ImageBinarize::default(),
+            num_t,
+            pixels: vec![0.0; width * height],
+            pooled: vec![0.0; width * height],
+            dirty: true,
+        }
+    }
+
+    /// Set the downsampling factor (1 = none).
+    ///
+    /// Changes the output size, so call before connecting to other blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is 0.
+    pub fn set_downsample(&mut self, factor: usize) {
+        assert!(factor > 0, "downsample factor must be > 0");
+        self.downsample = factor;
+        self.out_width = self.width.div_ceil(factor);
+        self.out_height = self.height.div_ceil(factor);
+        self.pooled = vec![0.0; self.out_width * self.out_height];
+        self.output
+            .borrow_mut()
+            .setup(self.num_t, self.out_width * self.out_height);
+        self.dirty = true;
+    }
+
+    /// Builder form of [`set_downsample`](Self::set_downsample).
+    pub fn with_downsample(mut self, factor: usize) -> Self {
+        self.set_downsample(factor);
+        self
+    }
+
+    /// Set the binarization mode.
+    pub fn set_binarize(&mut self, binarize: ImageBinarize) {
+        self.binarize = binarize;
+        self.dirty = true;
+    }
+
+    /// Builder form of [`set_binarize`](Self::set_binarize).
+    pub fn with_binarize(mut self, binarize: ImageBinarize) -> Self {
+        self.set_binarize(binarize);
+        self
+    }
+
+    /// Set pixel intensities (row-major, typically in [0, 1]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels.len() != width * height`.
+    pub fn set_pixels(&mut self, pixels: &[f64]) {
+        assert_eq!(
+            pixels.len(),
+            self.pixels.len(),
+            "pixels must have width * height values"
+        );
+        self.pixels.copy_from_slice(pixels);
+        self.dirty = true;
+    }
+
+    /// Set 8-bit pixel intensities (row-major, scaled to [0, 1]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels.len() != width * height`.
+    pub fn set_pixels_u8(&mut self, pixels: &[u8]) {
+        assert_eq!(
+            pixels.len(),
+            self.pixels.len(),
+            "pixels must have width * height values"
+        );
+        for (p, &v) in self.pixels.iter_mut().zip(pixels) {
+            *p = v as f64 / 255.0;
+        }
+        self.dirty = true;
+    }
+
+    /// Get pixel intensities (row-major).
+    pub fn get_pixels(&self) -> &[f64] {
+        &self.pixels
+    }
+
+    /// Get input shape as `(width, height)`.
+    pub fn input_shape(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Get output shape as `(width, height)` after downsampling.
+    pub fn output_shape(&self) -> (usize, usize) {
+        (self.out_width, self.out_height)
+    }
+
+    /// Get downsampling factor.
+    pub fn downsample(&self) -> usize {
+        self.downsample
+    }
+
+    /// Get binarization mode.
+    pub fn binarize(&self) -> ImageBinarize {
+        self.binarize
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.out_width * self.out_height
+    }
+
+    /// Build a `PoolerTopology` whose input grid is this block's output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::{ImageTransformer, PatternPooler};
+    ///
+    /// let image = ImageTransformer::new(32, 32, 2, 0);
+    /// let pooler = PatternPooler::new(256, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+    ///     .with_topology(image.pooler_topology(16, 16, 2, 3));
+    /// assert_eq!(pooler.topology().unwrap().num_inputs(), 32 * 32);
+    /// ```
+    pub fn pooler_topology(
+        &self,
+        output_width: usize,
+        output_height: usize,
+        rf_radius: usize,
+        inhibition_radius: usize,
+    ) -> PoolerTopology {
+        PoolerTopology::new(
+            self.out_width,
+            self.out_height,
+            output_width,
+            output_height,
+            rf_radius,
+            inhibition_radius,
+        )
+    }
+
+    /// Average-pool `pixels` into `pooled`.
+    fn pool(&mut self) {
+        let d = self.downsample;
+        if d == 1 {
+            self.pooled.copy_from_slice(&self.pixels);
+            return;
+        }
+
+        for oy in 0..self.out_height {
+            for ox in 0..self.out_width {
+                let (mut sum, mut count) = (0.0, 0);
+                for y in oy * d..((oy + 1) * d).min(self.height) {
+                    for x in ox * d..((ox + 1) * d).min(self.width) {
+                        sum += self.pixels[y * self.width + x];
+                        count += 1;
+                    }
+                }
+                self.pooled[oy * self.out_width + ox] = sum / count as f64;
+            }
+        }
+    }
+
+    /// Sobel gradient magnitude of `pooled` at `(x, y)` (edges clamped).
+    fn gradient(&self, x: usize, y: usize) -> f64 {
+        let (w, h) = (self.out_width, self.out_height);
+        let p = |dx: isize, dy: isize| {
+            let xx = (x as isize + dx).clamp(0, w as isize - 1) as usize;
+            let yy = (y as isize + dy).clamp(0, h as isize - 1) as usize;
+            self.pooled[yy * w + xx]
+        };
+        let gx = (p(1, -1) + 2.0 * p(1, 0) + p(1, 1)) - (p(-1, -1) + 2.0 * p(-1, 0) + p(-1, 1));
+        let gy = (p(-1, 1) + 2.0 * p(0, 1) + p(1, 1)) - (p(-1, -1) + 2.0 * p(0, -1) + p(1, -1));
+        (gx * gx + gy * gy).sqrt()
+    }
+}
+
+impl Block for ImageTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.output.borrow_mut().clear();
+        self.pixels.iter_mut().for_each(|p| *p = 0.0);
+        self.dirty = true;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        // Optimization: Only encode if pixels or settings changed
+        if !self.dirty {
+            return;
+        }
+
+        self.pool();
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+
+        match self.binarize {
+            ImageBinarize::Threshold(thr) => {
+                for (i, &p) in self.pooled.iter().enumerate() {
+                    if p >= thr {
+                        output.state.set_bit(i);
+                    }
+                }
+            }
+            ImageBinarize::Mean => {
+                let mean = self.pooled.iter().sum::<f64>() / self.pooled.len() as f64;
+                for (i, &p) in self.pooled.iter().enumerate() {
+                    if p >= mean {
+                        output.state.set_bit(i);
+                    }
+                }
+            }
+            ImageBinarize::Edge(thr) => {
+                for y in 0..self.out_height {
+                    for x in 0..self.out_width {
+                        if self.gradient(x, y) >= thr {
+                            output.state.set_bit(y * self.out_width + x);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.dirty = false;
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.pixels.capacity() + self.pooled.capacity()) * std::mem::size_of::<f64>()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for ImageTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for ImageTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for ImageTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::ImageTransformer {
+            width: self.width,
+            height: self.height,
+            downsample: self.downsample,
+            binarize: self.binarize,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "ImageTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for ImageTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::NoState)
+    }
+
+    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
+        // Transformers have no learned state
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let it = ImageTransformer::new(10, 6, 2, 0);
+        assert_eq!(it.input_shape(), (10, 6));
+        assert_eq!(it.output_shape(), (10, 6));
+        assert_eq!(it.num_s(), 60);
+        assert_eq!(it.binarize(), ImageBinarize::Threshold(0.5));
+    }
+
+    #[test]
+    fn test_downsample_partial_blocks() {
+        let mut it = ImageTransformer::new(5, 3, 2, 0).with_downsample(2);
+        assert_eq!(it.output_shape(), (3, 2));
+        assert_eq!(it.output().borrow().state.num_bits(), 6);
+
+        // Right column block covers pixels x = 4 only
+        let mut pixels = vec![0.0; 15];
+        pixels[4] = 1.0;
+        pixels[9] = 1.0;
+        it.set_pixels(&pixels);
+        it.compute();
+        assert_eq!(it.pooled, vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sobel_flat_image_has_no_edges() {
+        let mut it = ImageTransformer::new(8, 8, 2, 0).with_binarize(ImageBinarize::Edge(0.1));
+        it.set_pixels(&[0.7; 64]);
+        it.compute();
+        assert_eq!(it.output().borrow().state.num_set(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "pixels must have width * height values")]
+    fn test_wrong_pixel_count() {
+        let mut it = ImageTransformer::new(4, 4, 2, 0);
+        it.set_pixels(&[0.0; 15]);
+    }
+}
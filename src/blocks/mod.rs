@@ -8,9 +8,21 @@
 //! Transformer blocks encode inputs into binary patterns (Sparse Distributed
 //! Representations - SDRs):
 //!
-//! - `ScalarTransformer` - Encodes continuous scalars with overlapping patterns
+//! - `ScalarTransformer` - Encodes continuous scalars with overlapping patterns (linear, log,
+//!   sqrt or custom scale; fixed or adaptive range)
 //! - `DiscreteTransformer` - Encodes categorical values with distinct patterns
 //! - `PersistenceTransformer` - Encodes temporal persistence of values
+//! - `DeltaTransformer` - Encodes the signed change (or rate of change) between values
+//! - `RdseTransformer` - Encodes unbounded scalars by hashing value buckets
+//! - `CyclicTransformer` - Encodes periodic values (angles, time of day) with wrap-around
+//! - `DateTimeTransformer` - Encodes Unix timestamps as concatenated calendar fields
+//! - `HyperGridTransformer` - Encodes N-dimensional vectors on random multi-scale grids
+//! - `SymbolTransformer` - Encodes open-vocabulary symbols as hashed random patterns
+//! - `ImageTransformer` - Encodes 2D grayscale or binary images (threshold, mean or edges)
+//!
+//! # Source Blocks
+//!
+//! - `BitFieldSource` - Outputs externally computed SDRs set directly by the caller
 //!
 //! # Learning Blocks
 //!
@@ -18,6 +30,9 @@
 //!
 //! - `PatternPooler` - Learns sparse representations via competitive learning
 //! - `PatternClassifier` - Supervised classification of binary patterns
+//! - `SdrClassifier` - Learned linear readout from any BitField to labels or values
+//! - `PatternClusterer` - Discovers clusters of recurring patterns without labels
+//! - `ActionLearner` - Learns action values from rewards and selects actions
 //!
 //! # Temporal Blocks
 //!
@@ -25,6 +40,8 @@
 //!
 //! - `ContextLearner` - Learns contextual associations and detects anomalies
 //! - `SequenceLearner` - Learns temporal sequences and predicts next patterns
+//! - `TemporalPooler` - Learns stable representations of whole sequences
+//! - `DelayLine` - Buffers recent input frames and outputs them side by side
 //!
 //! # Examples
 //!
@@ -43,15 +60,44 @@
 pub mod scalar_transformer;
 pub mod discrete_transformer;
 pub mod persistence_transformer;
+pub mod delta_transformer;
+pub mod rdse_transformer;
+pub mod cyclic_transformer;
+pub mod date_time_transformer;
+pub mod hypergrid_transformer;
+pub mod symbol_transformer;
+pub mod image_transformer;
+pub mod bitfield_source;
 pub mod pattern_pooler;
 pub mod pattern_classifier;
+pub mod sdr_classifier;
+pub mod pattern_clusterer;
+pub mod action_learner;
 pub mod context_learner;
 pub mod sequence_learner;
+pub mod temporal_pooler;
+pub mod delay_line;
+
+#[cfg(test)]
+mod test_support;
 
-pub use scalar_transformer::ScalarTransformer;
+pub use scalar_transformer::{ScalarAdapt, ScalarScale, ScalarTransformer};
 pub use discrete_transformer::DiscreteTransformer;
 pub use persistence_transformer::PersistenceTransformer;
-pub use pattern_pooler::PatternPooler;
-pub use pattern_classifier::PatternClassifier;
+pub use delta_transformer::DeltaTransformer;
+pub use rdse_transformer::RdseTransformer;
+pub use cyclic_transformer::CyclicTransformer;
+pub use date_time_transformer::{DateTimeField, DateTimeFields, DateTimeTransformer};
+pub use hypergrid_transformer::HyperGridTransformer;
+pub use symbol_transformer::SymbolTransformer;
+pub use image_transformer::{ImageBinarize, ImageTransformer};
+pub use bitfield_source::BitFieldSource;
+pub use pattern_pooler::{PatternPooler, PoolerTopology};
+pub use pattern_classifier::{MultiLabelMetrics, PatternClassifier};
+pub use sdr_classifier::SdrClassifier;
+pub use pattern_clusterer::PatternClusterer;
+pub use action_learner::ActionLearner;
 pub use context_learner::ContextLearner;
-pub use sequence_learner::SequenceLearner;
+pub use sequence_learner::{AnomalyExplanation, DendriteMatch, SequenceLearner};
+pub use temporal_pooler::TemporalPooler;
+pub use delay_line::DelayLine;
@@ -10,12 +10,14 @@
 //! - Each group represents one label/class
 //! - During encoding, each group activates its top `num_as` dendrites
 //! - During learning, only the group corresponding to the current label is updated
+//! - `add_label()` appends a new group at runtime without touching existing ones
 //!
 //! # Usage Pattern
 //!
 //! ```ignore
 //! classifier.set_label(label);     // Set ground truth
 //! classifier.execute(true);    // Encode and learn
+//! classifier.clear_label();    // Following steps are unlabeled
 //! let probs = classifier.get_probabilities();  // Get predictions
 //! ```
 //!
@@ -51,10 +53,18 @@
 
 use crate::{Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput, Result};
 use crate::{InputAccess, MemoryAccess, OutputAccess};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 
+/// Default softmax temperature for calibrated confidences.
+pub const DEFAULT_TEMPERATURE: f64 = 0.1;
+
+/// Default per-label score threshold for multi-label predictions.
+pub const DEFAULT_LABEL_THRESHOLD: f64 = 0.5;
+
 /// Supervised learning classifier for binary patterns.
 ///
 /// Divides statelets into `num_l` groups, one per label. During encoding, each
@@ -93,9 +103,21 @@ pub struct PatternClassifier {
     pct_learn: f64, // Learning percentage
     num_t: usize,   // History depth
 
+    // Constructor sizes (labels past num_l_init were added with add_label)
+    num_l_init: usize,
+    num_s_init: usize,
+
+    // Calibration
+    temperature: f64,           // Softmax temperature for get_confidences
+    unknown_threshold: f64,     // Minimum calibrated score for a known prediction
+    learned_max: Vec<f64>,      // Best match fraction seen while learning each label
+    label_thresholds: Vec<f64>, // Minimum score for each label in predict_labels
+
     // State
-    label: Option<usize>,        // Current label for supervised learning
+    labels: Vec<usize>,          // Current labels for supervised learning
+    label_names: Vec<String>,    // Name of each label
     overlaps: Vec<usize>,        // Overlap scores per dendrite
+    group: Vec<usize>,           // Scratch: one label group ranked by overlap
     statelet_labels: Vec<usize>, // Which label each statelet belongs to
 }
 
@@ -165,14 +187,6 @@ impl PatternClassifier {
 
         let num_rpd = 128; // Typical receptors per dendrite
 
-        // Setup statelet labels: each statelet knows which label it represents
-        let mut statelet_labels = vec![0; num_s];
-        for s in 0..num_s {
-            let label = s / num_spl;
-            // Ensure we don't exceed num_l due to rounding
-            statelet_labels[s] = if label >= num_l { 0 } else { label };
-        }
-
         let output = Rc::new(RefCell::new(BlockOutput::new()));
         output.borrow_mut().setup(num_t, num_s);
 
@@ -193,15 +207,40 @@ impl PatternClassifier {
             pct_conn,
             pct_learn,
             num_t,
-            label: None,
+            num_l_init: num_l,
+            num_s_init: num_s,
+            temperature: DEFAULT_TEMPERATURE,
+            unknown_threshold: 0.0,
+            learned_max: vec![0.0; num_l],
+            label_thresholds: vec![DEFAULT_LABEL_THRESHOLD; num_l],
+            labels: Vec::new(),
+            label_names: (0..num_l).map(|l| l.to_string()).collect(),
             overlaps: vec![0; num_s],
-            statelet_labels,
+            group: Vec::with_capacity(num_s),
+            statelet_labels: Self::assign_statelets(num_s, num_spl, num_l),
         }
     }
 
+    /// Map each statelet to the label group it belongs to.
+    fn assign_statelets(num_s: usize, num_spl: usize, num_l: usize) -> Vec<usize> {
+        (0..num_s)
+            .map(|s| {
+                let label = s / num_spl;
+                // Ensure we don't exceed num_l due to rounding
+                if label >= num_l {
+                    0
+                } else {
+                    label
+                }
+            })
+            .collect()
+    }
+
     /// Set the current label for supervised learning.
     ///
     /// Must be called before `feedforward(true)` to specify the ground truth label.
+    /// The label applies to every following learning step until it is changed
+    /// or cleared with [`clear_label`](Self::clear_label).
     ///
     /// # Panics
     ///
@@ -222,7 +261,209 @@ impl PatternClassifier {
             label,
             self.num_l
         );
-        self.label = Some(label);
+        self.labels.clear();
+        self.labels.push(label);
+    }
+
+    /// Set several current labels for multi-label learning.
+    ///
+    /// Every listed group learns the input, and winners in those groups are not
+    /// punished for each other. Winners in all other groups are punished as usual.
+    /// An empty slice clears the labels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any label >= `num_l`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::PatternClassifier;
+    ///
+    /// let mut classifier = PatternClassifier::new(4, 1024, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    /// classifier.set_labels(&[0, 2]);
+    /// assert_eq!(classifier.get_active_labels(), &[0, 2]);
+    /// ```
+    pub fn set_labels(&mut self, labels: &[usize]) {
+        for &label in labels {
+            assert!(
+                label < self.num_l,
+                "label must be < num_l (got {} >= {})",
+                label,
+                self.num_l
+            );
+        }
+        self.labels.clear();
+        self.labels.extend_from_slice(labels);
+        self.labels.sort_unstable();
+        self.labels.dedup();
+    }
+
+    /// Set several current labels by name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any name is unknown.
+    pub fn set_labels_by_name(&mut self, names: &[&str]) {
+        let labels: Vec<usize> = names
+            .iter()
+            .map(|name| {
+                self.label_index(name)
+                    .unwrap_or_else(|| panic!("unknown label name {:?}", name))
+            })
+            .collect();
+        self.set_labels(&labels);
+    }
+
+    /// Set the current label by name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no label has this name.
+    pub fn set_label_by_name(&mut self, name: &str) {
+        let label = self
+            .label_index(name)
+            .unwrap_or_else(|| panic!("unknown label name {:?}", name));
+        self.labels.clear();
+        self.labels.push(label);
+    }
+
+    /// Clear the current label.
+    ///
+    /// Learning steps without a label still encode but leave memory unchanged,
+    /// so unlabeled samples can be interleaved with labeled ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::PatternClassifier;
+    ///
+    /// let mut classifier = PatternClassifier::new(4, 1024, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    /// classifier.set_label(2);
+    /// classifier.clear_label();
+    /// assert_eq!(classifier.get_label(), None);
+    /// ```
+    pub fn clear_label(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Get the current label, if any (the lowest one when several are set).
+    pub fn get_label(&self) -> Option<usize> {
+        self.labels.first().copied()
+    }
+
+    /// Get all current labels, sorted.
+    pub fn get_active_labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    /// Add a new label at runtime and return its index.
+    ///
+    /// Allocates a new group of `num_spl` statelets (and their dendrites) at the
+    /// end of the output. Existing groups and their learned memory are left
+    /// untouched. The new group's dendrites are seeded from the block seed and
+    /// the label index, so a saved network restores them exactly.
+    ///
+    /// The output grows by `num_spl` bits, so blocks reading this classifier's
+    /// output must be connected after all labels are added.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the output has to grow while another block reads it;
+    /// nothing is changed in that case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a label with this name already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::PatternClassifier;
+    ///
+    /// let mut classifier = PatternClassifier::new(2, 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+    ///     .with_label_names(&["cat", "dog"]);
+    ///
+    /// let bird = classifier.add_label("bird").unwrap();
+    /// assert_eq!(bird, 2);
+    /// assert_eq!(classifier.num_s(), 96);
+    /// assert_eq!(classifier.label_index("bird"), Some(2));
+    /// ```
+    pub fn add_label(&mut self, name: &str) -> Result<usize> {
+        assert!(
+            self.label_index(name).is_none(),
+            "label name {:?} already exists",
+            name
+        );
+
+        // Leftover statelets from an uneven split are reused before growing
+        let num_s = self.num_s.max((self.num_l + 1) * self.num_spl);
+        if num_s > self.num_s && Rc::strong_count(&self.output) > 1 {
+            return Err(crate::GnomicsError::Other(format!(
+                "cannot add label {:?}: output is read by another block and would grow from {} to {} bits",
+                name, self.num_s, num_s
+            )));
+        }
+
+        let label = self.num_l;
+        self.num_l += 1;
+        self.label_names.push(name.to_string());
+        self.learned_max.push(0.0);
+        self.label_thresholds.push(DEFAULT_LABEL_THRESHOLD);
+
+        if num_s > self.num_s {
+            self.num_s = num_s;
+            self.overlaps.resize(num_s, 0);
+            self.output.borrow_mut().setup(self.num_t, num_s);
+        }
+        self.statelet_labels = Self::assign_statelets(self.num_s, self.num_spl, self.num_l);
+
+        if self.base.is_initialized() {
+            self.grow_memory(label);
+            self.activate();
+        }
+
+        Ok(label)
+    }
+
+    /// Set the names of the labels allocated so far.
+    ///
+    /// Labels are named by their index (`"0"`, `"1"`, ...) until renamed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `names.len()` != `num_l` or names are not unique.
+    pub fn set_label_names(&mut self, names: &[&str]) {
+        assert_eq!(names.len(), self.num_l, "names must have num_l entries");
+        for (i, name) in names.iter().enumerate() {
+            assert!(
+                !names[..i].contains(name),
+                "label name {:?} already exists",
+                name
+            );
+        }
+        self.label_names = names.iter().map(|n| n.to_string()).collect();
+    }
+
+    /// Builder-style variant of [`set_label_names`](Self::set_label_names).
+    pub fn with_label_names(mut self, names: &[&str]) -> Self {
+        self.set_label_names(names);
+        self
+    }
+
+    /// Get the name of each label.
+    pub fn label_names(&self) -> &[String] {
+        &self.label_names
+    }
+
+    /// Get the name of a label.
+    pub fn label_name(&self, label: usize) -> &str {
+        &self.label_names[label]
+    }
+
+    /// Get the index of the label with this name.
+    pub fn label_index(&self, name: &str) -> Option<usize> {
+        self.label_names.iter().position(|n| n == name)
     }
 
     /// Get classification probabilities for all labels.
@@ -295,6 +536,198 @@ impl PatternClassifier {
             .unwrap_or(0)
     }
 
+    /// Get the name of the predicted label.
+    pub fn get_predicted_label_name(&self) -> &str {
+        self.label_name(self.get_predicted_label())
+    }
+
+    /// Get calibrated per-label scores.
+    ///
+    /// A label's score is how much of what its active statelets learned to
+    /// look for is present in the current input, relative to the best match
+    /// seen while learning that label (1.0 = as good as the best training
+    /// sample). Scores are in `[0, 1]`, independent of each other and do not
+    /// sum to 1, so inputs carrying several labels score high on each of them.
+    /// Labels that were never trained score 0.
+    ///
+    /// Learned maxima are kept with the block state, so they survive a
+    /// network save and load.
+    pub fn get_scores(&self) -> Vec<f64> {
+        self.group_matches()
+            .iter()
+            .zip(&self.learned_max)
+            .map(|(&m, &max)| if max > 0.0 { (m / max).min(1.0) } else { 0.0 })
+            .collect()
+    }
+
+    /// Get calibrated confidences for all labels.
+    ///
+    /// Softmax of [`get_scores`](Self::get_scores) at the configured
+    /// temperature. Confidences sum to 1.0; lower temperatures sharpen them.
+    pub fn get_confidences(&self) -> Vec<f64> {
+        let scores = self.get_scores();
+        let max = scores.iter().copied().fold(0.0, f64::max);
+        let mut conf: Vec<f64> = scores
+            .iter()
+            .map(|&s| ((s - max) / self.temperature).exp())
+            .collect();
+        let total: f64 = conf.iter().sum();
+        for c in &mut conf {
+            *c /= total;
+        }
+        conf
+    }
+
+    /// Get the `k` most confident labels with their confidences.
+    ///
+    /// Sorted by descending confidence, ties by label index. Returns fewer than
+    /// `k` entries if there are fewer labels.
+    pub fn get_top_k(&self, k: usize) -> Vec<(usize, f64)> {
+        let mut ranked: Vec<(usize, f64)> =
+            self.get_confidences().into_iter().enumerate().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then(a.0.cmp(&b.0)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// Check whether the current input looks like none of the learned labels.
+    ///
+    /// True when the best calibrated score is below the unknown threshold.
+    /// Always false with the default threshold of 0.0.
+    pub fn is_unknown(&self) -> bool {
+        self.get_scores().iter().copied().fold(0.0, f64::max) < self.unknown_threshold
+    }
+
+    /// Get the predicted label, or `None` if the input is unknown.
+    ///
+    /// Unlike [`get_predicted_label`](Self::get_predicted_label), this ranks
+    /// labels by calibrated score and applies the unknown threshold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::{DiscreteTransformer, PatternClassifier};
+    /// use gnomics::{Block, InputAccess, OutputAccess};
+    ///
+    /// let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+    /// let mut classifier = PatternClassifier::new(2, 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+    ///     .with_unknown_threshold(0.8);
+    /// classifier.input_mut().add_child(encoder.output(), 0);
+    /// classifier.init().unwrap();
+    ///
+    /// // Label 1 is never trained, so nothing can be predicted as 1
+    /// encoder.set_value(0);
+    /// encoder.execute(false).unwrap();
+    /// classifier.set_label(0);
+    /// classifier.execute(true).unwrap();
+    ///
+    /// classifier.execute(false).unwrap();
+    /// assert_eq!(classifier.predict(), Some(0));
+    /// ```
+    pub fn predict(&self) -> Option<usize> {
+        if self.is_unknown() {
+            return None;
+        }
+        self.get_scores()
+            .iter()
+            .enumerate()
+            .max_by(|(ia, a), (ib, b)| a.partial_cmp(b).unwrap().then(ib.cmp(ia)))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Get every label whose calibrated score reaches its threshold.
+    ///
+    /// Each label is decided independently, so any number of labels (including
+    /// none) can be predicted. Sorted by label index.
+    pub fn predict_labels(&self) -> Vec<usize> {
+        self.get_scores()
+            .iter()
+            .zip(&self.label_thresholds)
+            .enumerate()
+            .filter(|(_, (&score, &threshold))| score > 0.0 && score >= threshold)
+            .map(|(l, _)| l)
+            .collect()
+    }
+
+    /// Set the score threshold of one label for [`predict_labels`](Self::predict_labels).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `label` >= `num_l` or `threshold` is outside `[0, 1]`.
+    pub fn set_label_threshold(&mut self, label: usize, threshold: f64) {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "label threshold must be in [0, 1]"
+        );
+        self.label_thresholds[label] = threshold;
+    }
+
+    /// Set the score threshold of every label allocated so far.
+    pub fn set_label_thresholds(&mut self, threshold: f64) {
+        for label in 0..self.num_l {
+            self.set_label_threshold(label, threshold);
+        }
+    }
+
+    /// Builder-style variant of [`set_label_thresholds`](Self::set_label_thresholds).
+    pub fn with_label_thresholds(mut self, threshold: f64) -> Self {
+        self.set_label_thresholds(threshold);
+        self
+    }
+
+    /// Get the score threshold of each label.
+    pub fn label_thresholds(&self) -> &[f64] {
+        &self.label_thresholds
+    }
+
+    /// Set the softmax temperature used by [`get_confidences`](Self::get_confidences).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `temperature` is not positive.
+    pub fn set_temperature(&mut self, temperature: f64) {
+        assert!(temperature > 0.0, "temperature must be > 0");
+        self.temperature = temperature;
+    }
+
+    /// Builder-style variant of [`set_temperature`](Self::set_temperature).
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.set_temperature(temperature);
+        self
+    }
+
+    /// Get the softmax temperature.
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Set the minimum calibrated score for a known prediction.
+    ///
+    /// Inputs whose best score falls below it are reported as unknown by
+    /// [`is_unknown`](Self::is_unknown) and [`predict`](Self::predict).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is outside `[0, 1]`.
+    pub fn set_unknown_threshold(&mut self, threshold: f64) {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "unknown threshold must be in [0, 1]"
+        );
+        self.unknown_threshold = threshold;
+    }
+
+    /// Builder-style variant of [`set_unknown_threshold`](Self::set_unknown_threshold).
+    pub fn with_unknown_threshold(mut self, threshold: f64) -> Self {
+        self.set_unknown_threshold(threshold);
+        self
+    }
+
+    /// Get the unknown threshold.
+    pub fn unknown_threshold(&self) -> f64 {
+        self.unknown_threshold
+    }
+
     /// Get the labels array (0, 1, 2, ..., num_l-1).
     pub fn get_labels(&self) -> Vec<usize> {
         (0..self.num_l).collect()
@@ -326,6 +759,73 @@ impl PatternClassifier {
     pub fn num_spl(&self) -> usize {
         self.num_spl
     }
+
+    /// Allocate dendrites for a label added after the constructor.
+    ///
+    /// Uses an RNG derived from the seed and label so rebuilding from config
+    /// reproduces the same receptors regardless of how much was learned since.
+    fn grow_memory(&mut self, label: usize) {
+        let needed = (label + 1) * self.num_spl;
+        let have = self.memory.num_dendrites();
+        if needed > have {
+            let seed = crate::utils::hash_u64(self.base.seed(), label as u64);
+            let mut rng = StdRng::seed_from_u64(seed);
+            self.memory
+                .add_dendrites_pooled(needed - have, &mut rng, self.pct_conn);
+        }
+    }
+
+    /// Match of each group's active statelets against the current input.
+    fn group_matches(&self) -> Vec<f64> {
+        (0..self.num_l).map(|l| self.group_match(l)).collect()
+    }
+
+    /// Match of one group's active statelets against the current input.
+    ///
+    /// The mean fraction of each active statelet's connected receptors that see
+    /// an active input bit. 1.0 means everything the group learned to look for
+    /// is present; bits from other labels in the same input do not lower it.
+    fn group_match(&self, label: usize) -> f64 {
+        let output = self.output.borrow();
+        let start = label * self.num_spl;
+        let mut sum = 0.0;
+        for d in (start..start + self.num_spl).filter(|&d| output.state.get_bit(d) == 1) {
+            let num_conn = self.memory.conns(d).map_or(0, |c| c.num_set());
+            if num_conn > 0 {
+                sum += self.overlaps[d] as f64 / num_conn as f64;
+            }
+        }
+        sum / self.num_as.max(1) as f64
+    }
+
+    /// Compute overlaps and activate the top `num_as` statelets of each group.
+    fn activate(&mut self) {
+        // Clear output
+        self.output.borrow_mut().state.clear_all();
+
+        // Compute overlaps for all dendrites
+        self.memory
+            .overlap_all(&self.input.state, &mut self.overlaps);
+
+        // For each label group, activate top num_as dendrites
+        for l in 0..self.num_l {
+            let start = l * self.num_spl;
+            let end = start + self.num_spl;
+
+            // Sort this group by overlap (descending, ties by index)
+            let overlaps = &self.overlaps;
+            self.group.clear();
+            self.group.extend(start..end);
+            self.group
+                .sort_unstable_by(|&a, &b| overlaps[b].cmp(&overlaps[a]).then(a.cmp(&b)));
+
+            // Activate top num_as in this group
+            let mut output = self.output.borrow_mut();
+            for &idx in self.group.iter().take(self.num_as) {
+                output.state.set_bit(idx);
+            }
+        }
+    }
 }
 
 impl Block for PatternClassifier {
@@ -340,6 +840,10 @@ impl Block for PatternClassifier {
             self.pct_pool,
             self.pct_conn,
         );
+        for label in self.num_l_init..self.num_l {
+            self.grow_memory(label);
+        }
+        self.memory.init_index();
 
         self.base.set_initialized(true);
         Ok(())
@@ -380,28 +884,7 @@ impl Block for PatternClassifier {
             return;
         }
 
-        // Clear output
-        self.output.borrow_mut().state.clear_all();
-
-        // Compute overlaps for all dendrites
-        for d in 0..self.num_s {
-            self.overlaps[d] = self.memory.overlap_conn(d, &self.input.state);
-        }
-
-        // For each label group, activate top num_as dendrites
-        for l in 0..self.num_l {
-            let start = l * self.num_spl;
-            let end = start + self.num_spl;
-
-            // Sort this group by overlap (descending)
-            let mut group: Vec<usize> = (start..end).collect();
-            group.sort_by(|&a, &b| self.overlaps[b].cmp(&self.overlaps[a]));
-
-            // Activate top num_as in this group
-            for &idx in group.iter().take(self.num_as) {
-                self.output.borrow_mut().state.set_bit(idx);
-            }
-        }
+        self.activate();
     }
 
     fn learn(&mut self) {
@@ -410,9 +893,22 @@ impl Block for PatternClassifier {
             "PatternClassifier must be initialized before learning"
         );
 
-        if let Some(label) = self.label {
-            // Only learn on the specified label's group
-            let start = label * self.num_spl;
+        if self.labels.is_empty() {
+            return;
+        }
+
+        // Track the best match each label has reached, for calibration
+        for i in 0..self.labels.len() {
+            let label = self.labels[i];
+            let matched = self.group_match(label);
+            if matched > self.learned_max[label] {
+                self.learned_max[label] = matched;
+            }
+        }
+
+        // Only learn on the specified labels' groups
+        for i in 0..self.labels.len() {
+            let start = self.labels[i] * self.num_spl;
             let end = start + self.num_spl;
 
             for d in start..end {
@@ -425,14 +921,16 @@ impl Block for PatternClassifier {
                     // C++ implementation has this commented out - we'll leave it out too
                 }
             }
+        }
 
-            // Optional: Punish winning dendrites in wrong label groups
-            // This helps create more distinct representations
-            for d in 0..self.num_s {
-                if self.statelet_labels[d] != label && self.output.borrow().state.get_bit(d) == 1 {
-                    self.memory
-                        .punish_conn(d, &self.input.state, self.base.rng());
-                }
+        // Optional: Punish winning dendrites in wrong label groups
+        // This helps create more distinct representations
+        for d in 0..self.num_s {
+            if !self.labels.contains(&self.statelet_labels[d])
+                && self.output.borrow().state.get_bit(d) == 1
+            {
+                self.memory
+                    .punish_conn(d, &self.input.state, self.base.rng());
             }
         }
     }
@@ -444,7 +942,9 @@ impl Block for PatternClassifier {
     fn memory_usage(&self) -> usize {
         let base_size = std::mem::size_of::<Self>();
         let overlaps_size = self.overlaps.len() * std::mem::size_of::<usize>();
-        let statelet_labels_size = self.statelet_labels.len() * std::mem::size_of::<usize>();
+        let statelet_labels_size = self.statelet_labels.len() * std::mem::size_of::<usize>()
+            + (self.learned_max.len() + self.label_thresholds.len()) * std::mem::size_of::<f64>()
+            + self.label_names.iter().map(|n| n.capacity()).sum::<usize>();
         let input_size = self.input.memory_usage();
         let output_size = self.output.borrow().memory_usage();
         let memory_size = self.memory.memory_usage();
@@ -504,8 +1004,8 @@ impl OutputAccess for PatternClassifier {
 impl crate::network_config::BlockConfigurable for PatternClassifier {
     fn to_config(&self) -> crate::network_config::BlockConfig {
         crate::network_config::BlockConfig::PatternClassifier {
-            num_l: self.num_l,
-            num_s: self.num_s,
+            num_l: self.num_l_init,
+            num_s: self.num_s_init,
             num_as: self.num_as,
             perm_thr: self.perm_thr,
             perm_inc: self.perm_inc,
@@ -515,6 +1015,10 @@ impl crate::network_config::BlockConfigurable for PatternClassifier {
             pct_learn: self.pct_learn,
             num_t: self.num_t,
             seed: self.base().seed(),
+            label_names: self.label_names.clone(),
+            temperature: self.temperature,
+            unknown_threshold: self.unknown_threshold,
+            label_thresholds: self.label_thresholds.clone(),
         }
     }
 
@@ -526,12 +1030,23 @@ impl crate::network_config::BlockConfigurable for PatternClassifier {
 impl crate::network_config::BlockStateful for PatternClassifier {
     fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
         let permanences = self.memory.get_all_permanences();
-        Ok(crate::network_config::BlockState::PatternClassifier { permanences })
+        Ok(crate::network_config::BlockState::PatternClassifier {
+            permanences,
+            learned_max: self.learned_max.clone(),
+        })
     }
 
     fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
-        if let crate::network_config::BlockState::PatternClassifier { permanences } = state {
+        if let crate::network_config::BlockState::PatternClassifier {
+            permanences,
+            learned_max,
+        } = state
+        {
             self.memory.set_all_permanences(permanences)?;
+            // States saved before calibration existed have no maxima
+            if learned_max.len() == self.num_l {
+                self.learned_max.clone_from(learned_max);
+            }
             Ok(())
         } else {
             Err(crate::GnomicsError::Other(
@@ -541,4 +1056,143 @@ impl crate::network_config::BlockStateful for PatternClassifier {
     }
 }
 
+/// Running evaluation metrics for multi-label predictions.
+///
+/// Feed each sample's predicted and true label sets with [`add`](Self::add),
+/// e.g. from [`PatternClassifier::predict_labels`].
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::blocks::MultiLabelMetrics;
+///
+/// let mut metrics = MultiLabelMetrics::new(3);
+/// metrics.add(&[0, 1], &[0, 1]);
+/// metrics.add(&[0], &[0, 2]);
+///
+/// assert_eq!(metrics.subset_accuracy(), 0.5);
+/// assert_eq!(metrics.hamming_loss(), 1.0 / 6.0);
+/// assert_eq!(metrics.recall(2), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiLabelMetrics {
+    num_samples: usize,
+    exact_matches: usize,
+    true_pos: Vec<usize>,
+    false_pos: Vec<usize>,
+    false_neg: Vec<usize>,
+}
+
+impl MultiLabelMetrics {
+    /// Create empty metrics for `num_l` labels.
+    pub fn new(num_l: usize) -> Self {
+        Self {
+            num_samples: 0,
+            exact_matches: 0,
+            true_pos: vec![0; num_l],
+            false_pos: vec![0; num_l],
+            false_neg: vec![0; num_l],
+        }
+    }
+
+    /// Record one sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any label >= `num_l`.
+    pub fn add(&mut self, predicted: &[usize], actual: &[usize]) {
+        let num_l = self.true_pos.len();
+        assert!(
+            predicted.iter().chain(actual).all(|&l| l < num_l),
+            "label must be < num_l"
+        );
+
+        let mut exact = true;
+        for l in 0..num_l {
+            match (predicted.contains(&l), actual.contains(&l)) {
+                (true, true) => self.true_pos[l] += 1,
+                (true, false) => {
+                    self.false_pos[l] += 1;
+                    exact = false;
+                }
+                (false, true) => {
+                    self.false_neg[l] += 1;
+                    exact = false;
+                }
+                (false, false) => {}
+            }
+        }
+        self.num_samples += 1;
+        self.exact_matches += exact as usize;
+    }
+
+    /// Get number of samples recorded.
+    pub fn num_samples(&self) -> usize {
+        self.num_samples
+    }
+
+    /// Fraction of samples whose predicted label set was exactly right.
+    pub fn subset_accuracy(&self) -> f64 {
+        ratio(self.exact_matches, self.num_samples)
+    }
+
+    /// Fraction of (sample, label) decisions that were wrong.
+    pub fn hamming_loss(&self) -> f64 {
+        let errors: usize =
+            self.false_pos.iter().sum::<usize>() + self.false_neg.iter().sum::<usize>();
+        ratio(errors, self.num_samples * self.true_pos.len())
+    }
+
+    /// Precision of one label (0 if it was never predicted).
+    pub fn precision(&self, label: usize) -> f64 {
+        ratio(
+            self.true_pos[label],
+            self.true_pos[label] + self.false_pos[label],
+        )
+    }
+
+    /// Recall of one label (0 if it never occurred).
+    pub fn recall(&self, label: usize) -> f64 {
+        ratio(
+            self.true_pos[label],
+            self.true_pos[label] + self.false_neg[label],
+        )
+    }
+
+    /// F1 score of one label.
+    pub fn f1(&self, label: usize) -> f64 {
+        let tp = self.true_pos[label];
+        ratio(
+            2 * tp,
+            2 * tp + self.false_pos[label] + self.false_neg[label],
+        )
+    }
+
+    /// F1 score over all decisions pooled together.
+    pub fn micro_f1(&self) -> f64 {
+        let tp: usize = self.true_pos.iter().sum();
+        let fp: usize = self.false_pos.iter().sum();
+        let fn_: usize = self.false_neg.iter().sum();
+        ratio(2 * tp, 2 * tp + fp + fn_)
+    }
+
+    /// Mean of the per-label F1 scores.
+    pub fn macro_f1(&self) -> f64 {
+        let num_l = self.true_pos.len();
+        if num_l == 0 {
+            return 0.0;
+        }
+        (0..num_l).map(|l| self.f1(l)).sum::<f64>() / num_l as f64
+    }
+}
+
+/// `num / den`, or 0 when `den` is 0.
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        0.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
 // Tests are in tests/test_pattern_classifier.rs
@@ -0,0 +1,731 @@
+//! PatternClusterer - Discovers clusters of recurring input patterns without labels.
+//!
+//! This module provides the `PatternClusterer` block. Each cluster is a
+//! prototype stored on one dendrite of a `BlockMemory` with pooled connectivity,
+//! matched against the input with the same overlap as `PatternPooler`. New
+//! prototypes are allocated online whenever an input matches none of them.
+//!
+//! # Algorithm
+//!
+//! Each step:
+//! 1. The match of each prototype is its overlap with the input divided by the
+//!    larger of its connected receptor count and the expected number of active
+//!    input bits in its pool (`num_set * pct_pool`)
+//! 2. If the best match reaches `match_thr`, the input belongs to that cluster
+//!    and the cluster's `num_spc` statelets are set in the output
+//! 3. During learning, the matched prototype learns the input. An input that
+//!    matched nothing is imprinted on a free prototype (or, when all are in use,
+//!    on the least recently used one) and becomes a new cluster
+//! 4. Every `maintenance_period` learning steps, prototypes idle for more than
+//!    `max_idle` steps are pruned and prototypes whose connections overlap by at
+//!    least `merge_thr` are merged into the more used one
+//!
+//! Cluster IDs are prototype indices, so they stay stable while a cluster
+//! exists. Pruned and merged-away IDs are reused by later clusters.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{DiscreteTransformer, PatternClusterer};
+//! use gnomics::{Block, InputAccess, OutputAccess};
+//!
+//! let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+//! let mut clusterer = PatternClusterer::new(8, 16, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0);
+//!
+//! clusterer.input_mut().add_child(encoder.output(), 0);
+//! clusterer.init().unwrap();
+//!
+//! for value in [0, 1, 0, 1] {
+//!     encoder.set_value(value);
+//!     encoder.execute(false).unwrap();
+//!     clusterer.execute(true).unwrap();
+//! }
+//!
+//! // Two distinct patterns became two clusters
+//! assert_eq!(clusterer.num_clusters(), 2);
+//! assert_eq!(clusterer.get_cluster(), Some(1));
+//! assert_eq!(clusterer.output().borrow().state.num_set(), 16);
+//! ```
+
+use crate::{Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput, Result};
+use crate::{InputAccess, MemoryAccess, OutputAccess};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Default overlap fraction at which two prototypes are merged.
+pub const DEFAULT_MERGE_THRESHOLD: f64 = 0.9;
+
+/// Default number of learning steps a prototype may go unused before pruning.
+pub const DEFAULT_MAX_IDLE: usize = 1000;
+
+/// Default number of learning steps between prune and merge passes.
+pub const DEFAULT_MAINTENANCE_PERIOD: usize = 100;
+
+/// Online unsupervised clustering with prototype allocation, pruning and merging.
+///
+/// # Performance
+///
+/// - Compute time: O(num_c) overlaps via connection BitFields
+/// - Learning time: one dendrite update per step, O(num_c²) per maintenance pass
+/// - Memory: one pooled dendrite per prototype
+pub struct PatternClusterer {
+    base: BlockBase,
+
+    /// Block input connection point
+    input: BlockInput,
+
+    /// Block output with history (`num_spc` statelets per cluster)
+    output: Rc<RefCell<BlockOutput>>,
+
+    /// Block memory with one prototype per dendrite
+    memory: BlockMemory,
+
+    // Parameters
+    num_c: usize,   // Maximum number of clusters
+    num_spc: usize, // Statelets per cluster
+    match_thr: f64, // Match needed to join a cluster
+    perm_thr: u8,   // Permanence threshold
+    perm_inc: u8,   // Permanence increment
+    perm_dec: u8,   // Permanence decrement
+    pct_pool: f64,  // Pooling percentage
+    pct_learn: f64, // Learning percentage
+    num_t: usize,   // History depth
+
+    // Maintenance
+    merge_thr: f64,            // Prototype overlap at which clusters merge
+    max_idle: usize,           // Idle learning steps before pruning (0 = never)
+    maintenance_period: usize, // Learning steps between passes (0 = never)
+
+    // State
+    active: Vec<bool>,      // Prototype in use per dendrite
+    counts: Vec<usize>,     // Inputs assigned per prototype
+    last_used: Vec<u64>,    // Learning step each prototype was last assigned
+    num_steps: u64,         // Learning steps taken
+    cluster: Option<usize>, // Cluster of the current input
+    best_match: f64,        // Best prototype match of the current input
+
+    // Working memory
+    overlaps: Vec<usize>, // Overlap scores per dendrite
+}
+
+impl PatternClusterer {
+    /// Create a new PatternClusterer.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_c` - Maximum number of clusters (prototypes)
+    /// * `num_spc` - Statelets per cluster in the output
+    /// * `match_thr` - Match needed to join a cluster (0.0-1.0, typically 0.5)
+    /// * `perm_thr` - Permanence threshold (typically 20/99)
+    /// * `perm_inc` - Permanence increment (typically 2)
+    /// * `perm_dec` - Permanence decrement (typically 1)
+    /// * `pct_pool` - Pooling percentage (typically 0.8)
+    /// * `pct_learn` - Learning percentage (typically 0.3)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_c` == 0 or `num_spc` == 0
+    /// - `match_thr` is not in (0.0, 1.0]
+    /// - `pct_pool` is not in (0.0, 1.0]
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::PatternClusterer;
+    ///
+    /// // Up to 32 clusters, 8 statelets each
+    /// let clusterer = PatternClusterer::new(32, 8, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0);
+    /// assert_eq!(clusterer.num_clusters(), 0);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_c: usize,
+        num_spc: usize,
+        match_thr: f64,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        pct_pool: f64,
+        pct_learn: f64,
+        num_t: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(num_c > 0, "num_c must be > 0");
+        assert!(num_spc > 0, "num_spc must be > 0");
+        assert!(
+            match_thr > 0.0 && match_thr <= 1.0,
+            "match_thr must be in (0.0, 1.0]"
+        );
+        assert!(
+            pct_pool > 0.0 && pct_pool <= 1.0,
+            "pct_pool must be in (0.0, 1.0]"
+        );
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_c * num_spc);
+
+        Self {
+            base: BlockBase::new(seed),
+            input: BlockInput::new(),
+            output,
+            memory: BlockMemory::new(num_c, 0, perm_thr, perm_inc, perm_dec, pct_learn),
+            num_c,
+            num_spc,
+            match_thr,
+            perm_thr,
+            perm_inc,
+            perm_dec,
+            pct_pool,
+            pct_learn,
+            num_t,
+            merge_thr: DEFAULT_MERGE_THRESHOLD,
+            max_idle: DEFAULT_MAX_IDLE,
+            maintenance_period: DEFAULT_MAINTENANCE_PERIOD,
+            active: vec![false; num_c],
+            counts: vec![0; num_c],
+            last_used: vec![0; num_c],
+            num_steps: 0,
+            cluster: None,
+            best_match: 0.0,
+            overlaps: vec![0; num_c],
+        }
+    }
+
+    /// Set the prototype overlap at which two clusters are merged.
+    ///
+    /// Overlap is the number of shared connections divided by the larger
+    /// connection count of the pair.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `merge_thr` is not in (0.0, 1.0].
+    pub fn set_merge_threshold(&mut self, merge_thr: f64) {
+        assert!(
+            merge_thr > 0.0 && merge_thr <= 1.0,
+            "merge_thr must be in (0.0, 1.0]"
+        );
+        self.merge_thr = merge_thr;
+    }
+
+    /// Builder-style variant of `set_merge_threshold()`.
+    pub fn with_merge_threshold(mut self, merge_thr: f64) -> Self {
+        self.set_merge_threshold(merge_thr);
+        self
+    }
+
+    /// Get the merge threshold.
+    pub fn merge_threshold(&self) -> f64 {
+        self.merge_thr
+    }
+
+    /// Set how many learning steps a prototype may go unused before it is pruned.
+    ///
+    /// Zero disables pruning; prototypes are then only recycled when all are in use.
+    pub fn set_max_idle(&mut self, max_idle: usize) {
+        self.max_idle = max_idle;
+    }
+
+    /// Builder-style variant of `set_max_idle()`.
+    pub fn with_max_idle(mut self, max_idle: usize) -> Self {
+        self.set_max_idle(max_idle);
+        self
+    }
+
+    /// Get the maximum idle steps before pruning.
+    pub fn max_idle(&self) -> usize {
+        self.max_idle
+    }
+
+    /// Set the number of learning steps between prune and merge passes.
+    ///
+    /// Zero disables both pruning and merging.
+    pub fn set_maintenance_period(&mut self, period: usize) {
+        self.maintenance_period = period;
+    }
+
+    /// Builder-style variant of `set_maintenance_period()`.
+    pub fn with_maintenance_period(mut self, period: usize) -> Self {
+        self.set_maintenance_period(period);
+        self
+    }
+
+    /// Get the number of learning steps between maintenance passes.
+    pub fn maintenance_period(&self) -> usize {
+        self.maintenance_period
+    }
+
+    /// Get the cluster ID of the current input.
+    ///
+    /// `None` if the input is empty or matched no cluster. During learning,
+    /// an unmatched input gets a new cluster, so this is only `None` for
+    /// non-empty input in inference.
+    pub fn get_cluster(&self) -> Option<usize> {
+        self.cluster
+    }
+
+    /// Get the best prototype match of the current input (0.0-1.0).
+    pub fn get_match(&self) -> f64 {
+        self.best_match
+    }
+
+    /// Check whether cluster `c` currently exists.
+    pub fn is_cluster(&self, c: usize) -> bool {
+        self.active.get(c).copied().unwrap_or(false)
+    }
+
+    /// Get the number of clusters currently in use.
+    pub fn num_clusters(&self) -> usize {
+        self.active.iter().filter(|&&a| a).count()
+    }
+
+    /// Get the number of inputs assigned to each cluster while learning.
+    ///
+    /// Unused cluster IDs have a count of zero.
+    pub fn cluster_counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Get the input bits connected to the prototype of cluster `c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is not an existing cluster.
+    pub fn get_prototype(&self, c: usize) -> Vec<usize> {
+        assert!(self.is_cluster(c), "cluster {} does not exist", c);
+        self.prototype(c).get_acts()
+    }
+
+    /// Get maximum number of clusters.
+    pub fn num_c(&self) -> usize {
+        self.num_c
+    }
+
+    /// Get number of statelets per cluster.
+    pub fn num_spc(&self) -> usize {
+        self.num_spc
+    }
+
+    /// Get the match threshold.
+    pub fn match_threshold(&self) -> f64 {
+        self.match_thr
+    }
+
+    /// Connection BitField of prototype `c`.
+    fn prototype(&self, c: usize) -> &crate::BitField {
+        self.memory
+            .conns(c)
+            .expect("PatternClusterer memory uses connection BitFields")
+    }
+
+    /// Set the output to the statelets of the current cluster.
+    fn write_output(&mut self) {
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        if let Some(c) = self.cluster {
+            output.state.set_range(c * self.num_spc, self.num_spc);
+        }
+    }
+
+    /// Pick the prototype for a new cluster: the first free one, otherwise
+    /// the least recently used.
+    fn allocate(&self) -> usize {
+        if let Some(c) = self.active.iter().position(|&a| !a) {
+            return c;
+        }
+        (0..self.num_c)
+            .min_by_key(|&c| self.last_used[c])
+            .unwrap_or(0)
+    }
+
+    /// Release prototype `c`.
+    fn release(&mut self, c: usize) {
+        self.active[c] = false;
+        self.counts[c] = 0;
+        self.last_used[c] = 0;
+    }
+
+    /// Prune idle prototypes and merge overlapping ones.
+    fn maintain(&mut self) {
+        if self.max_idle > 0 {
+            for c in 0..self.num_c {
+                if self.active[c] && self.num_steps - self.last_used[c] > self.max_idle as u64 {
+                    self.release(c);
+                }
+            }
+        }
+
+        for i in 0..self.num_c {
+            for j in (i + 1)..self.num_c {
+                if !self.active[i] {
+                    break;
+                }
+                if !self.active[j] {
+                    continue;
+                }
+
+                let (a, b) = (self.prototype(i), self.prototype(j));
+                let size = a.num_set().max(b.num_set());
+                if size == 0 || (a.num_similar(b) as f64) < self.merge_thr * size as f64 {
+                    continue;
+                }
+
+                // Keep the more used cluster (ties keep the lower ID)
+                let (keep, drop) = if self.counts[j] > self.counts[i] {
+                    (j, i)
+                } else {
+                    (i, j)
+                };
+                self.counts[keep] += self.counts[drop];
+                self.last_used[keep] = self.last_used[keep].max(self.last_used[drop]);
+                self.release(drop);
+
+                if self.cluster == Some(drop) {
+                    self.cluster = Some(keep);
+                }
+            }
+        }
+    }
+}
+
+impl Block for PatternClusterer {
+    fn init(&mut self) -> Result<()> {
+        // No prototype starts connected; clusters are imprinted while learning
+        self.memory
+            .init_pooled_conn(self.input.num_bits(), self.base.rng(), self.pct_pool, 0.0);
+        self.base.set_initialized(true);
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement save
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement load
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.borrow_mut().clear();
+        self.memory.clear();
+        self.cluster = None;
+        self.best_match = 0.0;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        self.input.pull();
+    }
+
+    fn compute(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "PatternClusterer must be initialized before encoding"
+        );
+
+        self.memory
+            .overlap_all(&self.input.state, &mut self.overlaps);
+
+        let expected = self.input.state.num_set() as f64 * self.pct_pool;
+        let mut best = None;
+        self.best_match = 0.0;
+        for c in 0..self.num_c {
+            if !self.active[c] {
+                continue;
+            }
+            let size = (self.prototype(c).num_set() as f64).max(expected);
+            let score = if size > 0.0 {
+                self.overlaps[c] as f64 / size
+            } else {
+                0.0
+            };
+            if score > self.best_match {
+                self.best_match = score;
+                best = Some(c);
+            }
+        }
+
+        self.cluster = best.filter(|_| self.best_match >= self.match_thr);
+        self.write_output();
+    }
+
+    fn learn(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "PatternClusterer must be initialized before learning"
+        );
+
+        if self.input.state.num_set() == 0 {
+            return;
+        }
+
+        self.num_steps += 1;
+
+        let c = match self.cluster {
+            Some(c) => {
+                self.memory
+                    .learn_conn(c, &self.input.state, self.base.rng());
+                c
+            }
+            None => {
+                let c = self.allocate();
+                self.memory.imprint_conn(c, &self.input.state);
+                self.active[c] = true;
+                self.counts[c] = 0;
+                c
+            }
+        };
+        self.counts[c] += 1;
+        self.last_used[c] = self.num_steps;
+
+        let old = self.cluster;
+        self.cluster = Some(c);
+        if self.maintenance_period > 0
+            && self
+                .num_steps
+                .is_multiple_of(self.maintenance_period as u64)
+        {
+            self.maintain();
+        }
+
+        // A new or merged cluster replaces what compute() stored
+        if self.cluster != old {
+            self.write_output();
+            self.output.borrow_mut().store();
+        }
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.active.capacity() * std::mem::size_of::<bool>()
+            + (self.counts.capacity() + self.overlaps.capacity()) * std::mem::size_of::<usize>()
+            + self.last_used.capacity() * std::mem::size_of::<u64>()
+            + self.input.memory_usage()
+            + self.output.borrow().memory_usage()
+            + self.memory.memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        self.input.get_source_blocks()
+    }
+}
+
+impl BlockBaseAccess for PatternClusterer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl InputAccess for PatternClusterer {
+    fn input(&self) -> &BlockInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut BlockInput {
+        &mut self.input
+    }
+}
+
+impl MemoryAccess for PatternClusterer {
+    fn memory(&self) -> &BlockMemory {
+        &self.memory
+    }
+
+    fn memory_mut(&mut self) -> &mut BlockMemory {
+        &mut self.memory
+    }
+}
+
+impl OutputAccess for PatternClusterer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl crate::network_config::BlockConfigurable for PatternClusterer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::PatternClusterer {
+            num_c: self.num_c,
+            num_spc: self.num_spc,
+            match_thr: self.match_thr,
+            perm_thr: self.perm_thr,
+            perm_inc: self.perm_inc,
+            perm_dec: self.perm_dec,
+            pct_pool: self.pct_pool,
+            pct_learn: self.pct_learn,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+            merge_thr: self.merge_thr,
+            max_idle: self.max_idle,
+            maintenance_period: self.maintenance_period,
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "PatternClusterer"
+    }
+}
+
+impl crate::network_config::BlockStateful for PatternClusterer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::PatternClusterer {
+            permanences: self.memory.get_all_permanences(),
+            active: self.active.clone(),
+            counts: self.counts.clone(),
+            last_used: self.last_used.clone(),
+            num_steps: self.num_steps,
+        })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::PatternClusterer {
+            permanences,
+            active,
+            counts,
+            last_used,
+            num_steps,
+        } = state
+        {
+            if active.len() != self.num_c
+                || counts.len() != self.num_c
+                || last_used.len() != self.num_c
+            {
+                return Err(crate::GnomicsError::InvalidInputSize {
+                    expected: self.num_c,
+                    actual: active.len(),
+                });
+            }
+            // maintain() measures idle time as num_steps - last_used
+            if last_used.iter().any(|&step| step > *num_steps) {
+                return Err(crate::GnomicsError::Other(
+                    "PatternClusterer last_used exceeds num_steps".into(),
+                ));
+            }
+            self.memory.set_all_permanences(permanences)?;
+            self.active.clone_from(active);
+            self.counts.clone_from(counts);
+            self.last_used.clone_from(last_used);
+            self.num_steps = *num_steps;
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for PatternClusterer".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_support::{feed, with_source};
+
+    fn clusterer_with_input(num_c: usize) -> (PatternClusterer, Rc<RefCell<BlockOutput>>) {
+        with_source(
+            PatternClusterer::new(num_c, 4, 0.5, 20, 2, 1, 1.0, 0.3, 2, 0),
+            128,
+        )
+    }
+
+    #[test]
+    fn test_novel_input_allocates_cluster() {
+        let (mut c, source) = clusterer_with_input(4);
+        feed(&source, 0..16);
+        c.execute(true).unwrap();
+
+        assert_eq!(c.get_cluster(), Some(0));
+        assert_eq!(c.get_prototype(0), (0..16).collect::<Vec<_>>());
+        assert_eq!(c.output().borrow().state.get_acts(), vec![0, 1, 2, 3]);
+        // The stored history matches the new cluster, not the empty compute result
+        assert_eq!(
+            c.output().borrow().get_bitfield(0).get_acts(),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_inference_does_not_allocate() {
+        let (mut c, source) = clusterer_with_input(4);
+        feed(&source, 0..16);
+        c.execute(false).unwrap();
+
+        assert_eq!(c.get_cluster(), None);
+        assert_eq!(c.num_clusters(), 0);
+        assert_eq!(c.output().borrow().state.num_set(), 0);
+    }
+
+    #[test]
+    fn test_full_memory_recycles_least_recently_used() {
+        let (mut c, source) = clusterer_with_input(2);
+        c.set_maintenance_period(0);
+        for range in [0..16, 32..48, 0..16, 64..80] {
+            feed(&source, range);
+            c.execute(true).unwrap();
+        }
+
+        // 32..48 was least recently used, so its ID was reused
+        assert_eq!(c.get_cluster(), Some(1));
+        assert_eq!(c.get_prototype(1), (64..80).collect::<Vec<_>>());
+        assert_eq!(c.cluster_counts(), &[2, 1]);
+    }
+
+    #[test]
+    fn test_maintenance_prunes_idle_clusters() {
+        let (mut c, source) = clusterer_with_input(4);
+        c.set_maintenance_period(1);
+        c.set_max_idle(2);
+
+        feed(&source, 0..16);
+        c.execute(true).unwrap();
+        for _ in 0..3 {
+            feed(&source, 64..80);
+            c.execute(true).unwrap();
+        }
+
+        assert!(!c.is_cluster(0));
+        assert!(c.is_cluster(1));
+        assert_eq!(c.num_clusters(), 1);
+    }
+
+    #[test]
+    fn test_from_state_rejects_future_last_used() {
+        use crate::network_config::{BlockState, BlockStateful};
+
+        let (mut c, source) = clusterer_with_input(2);
+        feed(&source, 0..16);
+        c.execute(true).unwrap();
+
+        let mut state = c.to_state().unwrap();
+        if let BlockState::PatternClusterer { last_used, .. } = &mut state {
+            last_used[0] = 5;
+        }
+        assert!(c.from_state(&state).is_err());
+    }
+}
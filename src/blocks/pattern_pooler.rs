@@ -18,6 +18,14 @@
 //! - Creating pooled representations for classification
 //! - Unsupervised learning of sparse codes
 //!
+//! # Topology
+//!
+//! By default every dendrite pools a random `pct_pool` subset of the whole input
+//! and the top `num_as` statelets win globally. For image-like or multi-sensor
+//! inputs, a [`PoolerTopology`] lays statelets out on a 1D/2D grid, restricts
+//! each receptive field to a local window of the input, and applies local
+//! inhibition so winners are spread spatially.
+//!
 //! # Examples
 //!
 //! ```
@@ -44,10 +52,131 @@
 //! ```
 
 use crate::{Block, BlockBase, BlockBaseAccess, BlockInput, InputAccess, BlockMemory, MemoryAccess, BlockOutput, OutputAccess, Result};
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 
+/// Spatial layout for a topological PatternPooler.
+///
+/// Input bits and statelets are laid out row-major on 2D grids (use a height
+/// of 1 for 1D layouts). Each statelet's center is mapped proportionally into
+/// the input grid and its receptive field is the `(2 * rf_radius + 1)` square
+/// window around that center, shifted inward at the edges so every field has
+/// the same size. During compute, a statelet only competes with statelets
+/// within `inhibition_radius` of it on the output grid.
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::blocks::{PatternPooler, PoolerTopology};
+///
+/// // 32x32 input image, 16x16 statelet grid, 5x5 receptive fields
+/// let topology = PoolerTopology::new(32, 32, 16, 16, 2, 3);
+/// let pooler = PatternPooler::new(256, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+///     .with_topology(topology);
+/// assert!(pooler.topology().is_some());
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoolerTopology {
+    /// Input grid width
+    pub input_width: usize,
+    /// Input grid height (1 for 1D inputs)
+    pub input_height: usize,
+    /// Statelet grid width
+    pub output_width: usize,
+    /// Statelet grid height (1 for 1D layouts)
+    pub output_height: usize,
+    /// Receptive field radius in input coordinates
+    pub rf_radius: usize,
+    /// Local inhibition radius in statelet coordinates
+    pub inhibition_radius: usize,
+}
+
+impl PoolerTopology {
+    /// Create a 2D topology.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any grid dimension is zero.
+    pub fn new(
+        input_width: usize,
+        input_height: usize,
+        output_width: usize,
+        output_height: usize,
+        rf_radius: usize,
+        inhibition_radius: usize,
+    ) -> Self {
+        assert!(input_width > 0 && input_height > 0, "input grid must be non-empty");
+        assert!(output_width > 0 && output_height > 0, "output grid must be non-empty");
+
+        Self {
+            input_width,
+            input_height,
+            output_width,
+            output_height,
+            rf_radius,
+            inhibition_radius,
+        }
+    }
+
+    /// Create a 1D topology over `num_i` input bits and `num_s` statelets.
+    pub fn line(num_i: usize, num_s: usize, rf_radius: usize, inhibition_radius: usize) -> Self {
+        Self::new(num_i, 1, num_s, 1, rf_radius, inhibition_radius)
+    }
+
+    /// Number of input bits covered by the input grid.
+    pub fn num_inputs(&self) -> usize {
+        self.input_width * self.input_height
+    }
+
+    /// Number of statelets covered by the output grid.
+    pub fn num_statelets(&self) -> usize {
+        self.output_width * self.output_height
+    }
+
+    /// Get the receptive field (input bit addresses) of statelet `s`.
+    pub fn receptive_field(&self, s: usize) -> Vec<usize> {
+        let sx = s % self.output_width;
+        let sy = s / self.output_width;
+
+        let (x0, x1) = Self::window(sx, self.output_width, self.input_width, self.rf_radius);
+        let (y0, y1) = Self::window(sy, self.output_height, self.input_height, self.rf_radius);
+
+        let mut field = Vec::with_capacity((x1 - x0) * (y1 - y0));
+        for y in y0..y1 {
+            for x in x0..x1 {
+                field.push(y * self.input_width + x);
+            }
+        }
+        field
+    }
+
+    /// Get the inhibition neighborhood bounds `(x0, x1, y0, y1)` of statelet `s`.
+    ///
+    /// Bounds are half-open and clipped to the statelet grid.
+    fn neighborhood(&self, s: usize) -> (usize, usize, usize, usize) {
+        let sx = s % self.output_width;
+        let sy = s / self.output_width;
+        let r = self.inhibition_radius;
+
+        (
+            sx.saturating_sub(r),
+            (sx + r + 1).min(self.output_width),
+            sy.saturating_sub(r),
+            (sy + r + 1).min(self.output_height),
+        )
+    }
+
+    /// Map output coordinate `pos` to a fixed-size input window `[beg, end)`.
+    fn window(pos: usize, out_len: usize, in_len: usize, radius: usize) -> (usize, usize) {
+        let len = (2 * radius + 1).min(in_len);
+        let center = ((2 * pos + 1) * in_len) / (2 * out_len);
+        let beg = center.saturating_sub(radius).min(in_len - len);
+        (beg, beg + len)
+    }
+}
+
 /// Learns sparse distributed representations via competitive learning.
 ///
 /// Uses winner-take-all activation where the top `num_as` dendrites with highest
@@ -84,9 +213,11 @@ pub struct PatternPooler {
     pct_learn: f64,      // Learning percentage
     num_t: usize,        // History depth
     always_update: bool, // Update even if input unchanged
+    topology: Option<PoolerTopology>, // Local receptive fields and inhibition
 
     // Working memory
     overlaps: Vec<usize>, // Overlap scores per dendrite
+    indices: Vec<usize>,  // Scratch: statelets ranked by overlap
 }
 
 impl PatternPooler {
@@ -160,10 +291,45 @@ impl PatternPooler {
             pct_learn,
             num_t,
             always_update,
+            topology: None,
             overlaps: vec![0; num_s],
+            indices: Vec::with_capacity(num_s),
         }
     }
 
+    /// Enable topological mode (local receptive fields and local inhibition).
+    ///
+    /// Must be called before `init()`. The statelet grid must cover exactly
+    /// `num_s` statelets and the input grid must match the connected input size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block is already initialized or the output grid does not
+    /// contain `num_s` statelets.
+    pub fn set_topology(&mut self, topology: PoolerTopology) {
+        assert!(
+            !self.base.is_initialized(),
+            "topology must be set before init()"
+        );
+        assert_eq!(
+            topology.num_statelets(),
+            self.num_s,
+            "output grid must contain num_s statelets"
+        );
+        self.topology = Some(topology);
+    }
+
+    /// Builder-style variant of `set_topology()`.
+    pub fn with_topology(mut self, topology: PoolerTopology) -> Self {
+        self.set_topology(topology);
+        self
+    }
+
+    /// Get the topology, if topological mode is enabled.
+    pub fn topology(&self) -> Option<&PoolerTopology> {
+        self.topology.as_ref()
+    }
+
     /// Get number of statelets.
     pub fn num_s(&self) -> usize {
         self.num_s
@@ -180,18 +346,88 @@ impl PatternPooler {
     }
 }
 
+impl PatternPooler {
+    /// Fill the `indices` scratch buffer with statelets sorted by overlap
+    /// (descending, ties by index). Reuses the buffer, so it does not allocate.
+    fn rank_by_overlap(&mut self) {
+        let overlaps = &self.overlaps;
+        self.indices.clear();
+        self.indices.extend(0..self.num_s);
+        self.indices
+            .sort_unstable_by(|&a, &b| overlaps[b].cmp(&overlaps[a]).then(a.cmp(&b)));
+    }
+
+    /// Local inhibition: activate statelets that rank in the top of their neighborhood.
+    ///
+    /// Statelets are visited in descending overlap order (ties by index) and
+    /// become active while their neighborhood holds fewer winners than its share
+    /// of the global `num_as / num_s` density. Zero-overlap statelets never fire.
+    fn inhibit_local(&mut self, topology: &PoolerTopology) {
+        let density = self.num_as as f64 / self.num_s as f64;
+
+        self.rank_by_overlap();
+
+        let mut output = self.output.borrow_mut();
+
+        for &s in &self.indices {
+            if self.overlaps[s] == 0 {
+                break;
+            }
+
+            let (x0, x1, y0, y1) = topology.neighborhood(s);
+            let num_neighbors = (x1 - x0) * (y1 - y0);
+            let num_winners = ((density * num_neighbors as f64).ceil() as usize).max(1);
+
+            // Count winners already active in this neighborhood
+            let mut num_active = 0;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    num_active += output.state.get_bit(y * topology.output_width + x) as usize;
+                }
+            }
+
+            if num_active < num_winners {
+                output.state.set_bit(s);
+            }
+        }
+    }
+}
+
 impl Block for PatternPooler {
     fn init(&mut self) -> Result<()> {
         // Output already set up in new()
 
-        // Initialize memory with pooled connectivity
         let num_input_bits = self.input.num_bits();
-        self.memory.init_pooled_conn(
-            num_input_bits,
-            self.base.rng(),
-            self.pct_pool,
-            self.pct_conn,
-        );
+
+        if let Some(topology) = self.topology {
+            if topology.num_inputs() != num_input_bits {
+                return Err(crate::GnomicsError::InvalidInputSize {
+                    expected: topology.num_inputs(),
+                    actual: num_input_bits,
+                });
+            }
+
+            // Initialize memory with local receptive fields
+            let fields: Vec<Vec<usize>> =
+                (0..self.num_s).map(|s| topology.receptive_field(s)).collect();
+            self.memory.init_local_conn(
+                num_input_bits,
+                self.base.rng(),
+                &fields,
+                self.pct_conn,
+            );
+        } else {
+            // Initialize memory with pooled connectivity
+            self.memory.init_pooled_conn(
+                num_input_bits,
+                self.base.rng(),
+                self.pct_pool,
+                self.pct_conn,
+            );
+        }
+
+        // Sparse inputs compute overlaps from active bits only
+        self.memory.init_index();
 
         self.base.set_initialized(true);
         Ok(())
@@ -236,18 +472,20 @@ impl Block for PatternPooler {
         self.output.borrow_mut().state.clear_all();
 
         // Compute overlaps for all dendrites
-        for d in 0..self.num_s {
-            self.overlaps[d] = self.memory.overlap_conn(d, &self.input.state);
+        self.memory.overlap_all(&self.input.state, &mut self.overlaps);
+
+        if let Some(topology) = self.topology {
+            self.inhibit_local(&topology);
+            return;
         }
 
         // Find top num_as dendrites (winner-take-all)
-        // Create sorted indices by overlap (descending)
-        let mut indices: Vec<usize> = (0..self.num_s).collect();
-        indices.sort_by(|&a, &b| self.overlaps[b].cmp(&self.overlaps[a]));
+        self.rank_by_overlap();
 
         // Activate top num_as winners
-        for &idx in indices.iter().take(self.num_as) {
-            self.output.borrow_mut().state.set_bit(idx);
+        let mut output = self.output.borrow_mut();
+        for &idx in self.indices.iter().take(self.num_as) {
+            output.state.set_bit(idx);
         }
     }
 
@@ -350,6 +588,7 @@ impl crate::network_config::BlockConfigurable for PatternPooler {
             always_update: self.always_update,
             num_t: self.num_t,
             seed: self.base().seed(),
+            topology: self.topology,
         }
     }
 
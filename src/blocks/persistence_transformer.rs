@@ -8,7 +8,8 @@
 //!
 //! - **Temporal Encoding**: Represents how long value has been stable
 //! - **Stability Detection**: Activates different bits based on persistence duration
-//! - **Change Sensitivity**: Resets counter when value changes significantly
+//! - **Change Sensitivity**: Resets counter when value changes by more than a
+//!   configurable fraction of the range (10% by default)
 //!
 //! # Examples
 //!
@@ -32,6 +33,9 @@ use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 
+/// Default stability threshold as a fraction of the value range.
+pub const DEFAULT_THRESHOLD: f64 = 0.1;
+
 /// Encodes temporal persistence of scalar values.
 ///
 /// Tracks how long a value has remained stable (within tolerance) and encodes
@@ -41,8 +45,8 @@ use std::rc::Rc;
 /// # Algorithm
 ///
 /// 1. Calculate percentage change: `delta = abs(current - previous) / range`
-/// 2. If `delta <= 0.1` (10% tolerance): increment counter
-/// 3. If `delta > 0.1`: reset counter to 0
+/// 2. If `delta <= threshold` (default 0.1): increment counter
+/// 3. If `delta > threshold`: reset counter to 0
 /// 4. Encode counter as position: `percent = counter / max_step`
 /// 5. Activate bits based on persistence percentage
 ///
@@ -66,6 +70,7 @@ pub struct PersistenceTransformer {
     num_as: usize,   // Number of active statelets
     dif_s: usize,    // num_s - num_as
     max_step: usize, // Maximum persistence steps to track
+    threshold: f64,  // Fraction of range treated as a change
 
     // State
     value: f64,
@@ -138,6 +143,7 @@ impl PersistenceTransformer {
             num_as,
             dif_s,
             max_step,
+            threshold: DEFAULT_THRESHOLD,
             value: min_val,
             counter: 0,
             pct_val_prev: 0.0,
@@ -171,6 +177,43 @@ impl PersistenceTransformer {
         self.value
     }
 
+    /// Set the stability threshold as a fraction of the value range.
+    ///
+    /// Changes larger than `threshold * (max_val - min_val)` reset the
+    /// persistence counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not in [0.0, 1.0]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::PersistenceTransformer;
+    ///
+    /// let mut pt = PersistenceTransformer::new(0.0, 100.0, 1024, 128, 100, 2, 0);
+    /// pt.set_threshold(0.02); // 2.0 units resets the counter
+    /// assert_eq!(pt.threshold(), 0.02);
+    /// ```
+    pub fn set_threshold(&mut self, threshold: f64) {
+        assert!(
+            (0.0..=1.0).contains(&threshold),
+            "threshold must be in [0.0, 1.0]"
+        );
+        self.threshold = threshold;
+    }
+
+    /// Builder form of [`set_threshold`](Self::set_threshold).
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.set_threshold(threshold);
+        self
+    }
+
+    /// Get the stability threshold.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
     /// Get current persistence counter.
     ///
     /// Returns the number of consecutive steps the value has been stable.
@@ -245,10 +288,10 @@ impl Block for PersistenceTransformer {
         // Calculate change from previous
         let pct_delta = (pct_val - self.pct_val_prev).abs();
 
-        // Determine if we should reset counter (change > 10% threshold)
+        // Determine if we should reset counter (change > threshold)
         let mut reset_timer_flag = false;
 
-        if pct_delta <= 0.1 {
+        if pct_delta <= self.threshold {
             // Value is stable - increment counter
             self.counter += 1;
         } else {
@@ -322,6 +365,7 @@ impl crate::network_config::BlockConfigurable for PersistenceTransformer {
             num_s: self.num_s,
             num_as: self.num_as,
             max_step: self.max_step,
+            threshold: self.threshold,
             num_t: self.output.borrow().num_t(),
             seed: self.base().seed(),
         }
@@ -434,6 +478,28 @@ mod tests {
         assert_eq!(pt.get_counter(), 3); // Counter continues
     }
 
+    #[test]
+    fn test_custom_threshold() {
+        let mut pt =
+            PersistenceTransformer::new(0.0, 1.0, 1024, 128, 100, 2, 0).with_threshold(0.02);
+
+        pt.set_value(0.5);
+        pt.compute(); // Reset to 0
+        pt.compute(); // 1
+        assert_eq!(pt.get_counter(), 1);
+
+        // 5% change now exceeds the 2% threshold
+        pt.set_value(0.55);
+        pt.compute();
+        assert_eq!(pt.get_counter(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "threshold must be in [0.0, 1.0]")]
+    fn test_invalid_threshold() {
+        PersistenceTransformer::new(0.0, 1.0, 1024, 128, 100, 2, 0).with_threshold(1.5);
+    }
+
     #[test]
     fn test_persistence_counter_caps_at_max() {
         let mut pt = PersistenceTransformer::new(0.0, 1.0, 1024, 128, 10, 2, 0);
@@ -0,0 +1,370 @@
+//! RdseTransformer - Random Distributed Scalar Encoder for unbounded values.
+//!
+//! This module provides the `RdseTransformer` block that encodes scalar values
+//! without a fixed range. Values are quantized into buckets of width
+//! `resolution`, and each bucket is hashed to a random but deterministic set of
+//! active bits. Neighboring buckets share most of their bits, so similar values
+//! still have overlapping representations.
+//!
+//! # Semantic Properties
+//!
+//! - **Unbounded**: No `min_val`/`max_val`; drifting or unknown ranges are fine
+//! - **Overlap-Preserving**: Buckets `d` apart share about `num_as - d` bits
+//! - **Deterministic**: Same seed and offset always give the same patterns
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::RdseTransformer;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! // 1.0 units per bucket, 2048 bits, 40 active
+//! let mut rdse = RdseTransformer::new(1.0, 2048, 40, 2, 0);
+//!
+//! rdse.set_value(1_000_000.0);
+//! rdse.execute(false).unwrap();
+//! assert_eq!(rdse.output().borrow().state.num_set(), 40);
+//!
+//! // A nearby value shares most active bits
+//! let mut other = RdseTransformer::new(1.0, 2048, 40, 2, 0);
+//! other.set_offset(Some(1_000_000.0));
+//! other.set_value(1_000_003.0);
+//! other.execute(false).unwrap();
+//!
+//! let overlap = rdse.output().borrow().state.num_similar(&other.output().borrow().state);
+//! assert!(overlap >= 35);
+//! ```
+
+use crate::utils::hash_u64;
+use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Encodes unbounded scalar values by hashing resolution-sized buckets.
+///
+/// # Algorithm
+///
+/// 1. On the first value, store it as `offset` (the center of bucket 0)
+/// 2. Bucket: `b = round((value - offset) / resolution)`
+/// 3. For `i` in `0..num_as`, hash `(seed, b + i)` to a bit position in
+///    `0..num_s` (probing forward past collisions)
+///
+/// Bucket `b` and bucket `b + d` hash `num_as - d` of the same keys, which is
+/// what makes the encoding overlap-preserving.
+///
+/// # Performance
+///
+/// - Encoding time: ~200ns for 40 active bits (one hash per active bit)
+/// - Memory: ~1KB for 2048 bits with history depth 2
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct RdseTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    resolution: f64, // Value width of one bucket
+    num_s: usize,    // Number of statelets
+    num_as: usize,   // Number of active statelets
+
+    // State
+    offset: Option<f64>, // Center of bucket 0 (set from first value)
+    value: f64,
+    bucket_prev: Option<i64>, // For change detection optimization
+}
+
+impl RdseTransformer {
+    /// Create a new RdseTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolution` - Value width of one bucket (values closer than this
+    ///   may share a pattern)
+    /// * `num_s` - Number of statelets (output bits)
+    /// * `num_as` - Number of active statelets (typically ~2% of num_s)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed selecting the hash family
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `resolution` is not positive and finite
+    /// - `num_as` == 0 or `num_as` > `num_s`
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::RdseTransformer;
+    ///
+    /// // Sensor with unknown range, 0.5 units per bucket
+    /// let encoder = RdseTransformer::new(0.5, 2048, 40, 2, 42);
+    /// assert_eq!(encoder.resolution(), 0.5);
+    /// ```
+    pub fn new(resolution: f64, num_s: usize, num_as: usize, num_t: usize, seed: u64) -> Self {
+        assert!(
+            resolution > 0.0 && resolution.is_finite(),
+            "resolution must be positive and finite"
+        );
+        assert!(num_as > 0, "num_as must be > 0");
+        assert!(num_as <= num_s, "num_as must be <= num_s");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            output,
+            resolution,
+            num_s,
+            num_as,
+            offset: None,
+            value: 0.0,
+            bucket_prev: None,
+        }
+    }
+
+    /// Set the current value to encode.
+    ///
+    /// Any finite value is accepted. Non-finite values (NaN, infinity) encode
+    /// as an empty pattern.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// Get the current value.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Get the bucket offset (center of bucket 0), if set.
+    pub fn offset(&self) -> Option<f64> {
+        self.offset
+    }
+
+    /// Set the bucket offset (center of bucket 0).
+    ///
+    /// `None` means the next finite value encoded becomes the offset.
+    pub fn set_offset(&mut self, offset: Option<f64>) {
+        self.offset = offset;
+        self.bucket_prev = None;
+    }
+
+    /// Get the bucket index for `value` relative to the current offset.
+    ///
+    /// Returns `None` if `value` is not finite or no offset is set yet.
+    pub fn bucket(&self, value: f64) -> Option<i64> {
+        let offset = self.offset?;
+        if !value.is_finite() {
+            return None;
+        }
+        Some(((value - offset) / self.resolution).round() as i64)
+    }
+
+    /// Get bucket resolution.
+    pub fn resolution(&self) -> f64 {
+        self.resolution
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+}
+
+impl Block for RdseTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        // Keep offset: it anchors the learned bucket layout
+        self.output.borrow_mut().clear();
+        self.value = 0.0;
+        self.bucket_prev = None;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        if self.offset.is_none() && self.value.is_finite() {
+            self.offset = Some(self.value);
+        }
+
+        let bucket = self.bucket(self.value);
+
+        // Optimization: Only encode if bucket changed
+        if bucket == self.bucket_prev && bucket.is_some() {
+            return;
+        }
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+
+        if let Some(b) = bucket {
+            let seed = self.base.seed();
+            for i in 0..self.num_as {
+                let key = b.wrapping_add(i as i64) as u64;
+                let mut s = (hash_u64(seed, key) % self.num_s as u64) as usize;
+
+                // Probe forward past collisions so exactly num_as bits are set
+                while output.state.get_bit(s) == 1 {
+                    s = (s + 1) % self.num_s;
+                }
+                output.state.set_bit(s);
+            }
+        }
+
+        self.bucket_prev = bucket;
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for RdseTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for RdseTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for RdseTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::RdseTransformer {
+            resolution: self.resolution,
+            num_s: self.num_s,
+            num_as: self.num_as,
+            num_t: self.output.borrow().num_t(),
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "RdseTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for RdseTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::RdseTransformer {
+            offset: self.offset,
+        })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::RdseTransformer { offset } = state {
+            self.set_offset(*offset);
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for RdseTransformer".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let rdse = RdseTransformer::new(0.5, 1024, 20, 2, 0);
+        assert_eq!(rdse.resolution(), 0.5);
+        assert_eq!(rdse.num_s(), 1024);
+        assert_eq!(rdse.num_as(), 20);
+        assert_eq!(rdse.offset(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "resolution must be positive and finite")]
+    fn test_invalid_resolution() {
+        RdseTransformer::new(0.0, 1024, 20, 2, 0);
+    }
+
+    #[test]
+    fn test_first_value_sets_offset() {
+        let mut rdse = RdseTransformer::new(1.0, 1024, 20, 2, 0);
+        rdse.set_value(-42.0);
+        rdse.compute();
+
+        assert_eq!(rdse.offset(), Some(-42.0));
+        assert_eq!(rdse.bucket(-42.0), Some(0));
+        assert_eq!(rdse.bucket(-39.6), Some(2));
+    }
+
+    #[test]
+    fn test_encode_num_active() {
+        let mut rdse = RdseTransformer::new(1.0, 64, 32, 2, 0);
+
+        // Dense setting forces hash collisions; probing keeps the count exact
+        for v in 0..50 {
+            rdse.set_value(v as f64);
+            rdse.compute();
+            assert_eq!(rdse.output().borrow().state.num_set(), 32);
+        }
+    }
+
+    #[test]
+    fn test_non_finite_value() {
+        let mut rdse = RdseTransformer::new(1.0, 1024, 20, 2, 0);
+        rdse.set_value(f64::NAN);
+        rdse.compute();
+
+        assert_eq!(rdse.output().borrow().state.num_set(), 0);
+        assert_eq!(rdse.offset(), None);
+    }
+}
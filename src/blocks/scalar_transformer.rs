@@ -10,6 +10,15 @@
 //! - **Continuous Gradation**: Smooth transitions between values
 //! - **Semantic Similarity**: Overlap percentage correlates with value similarity
 //!
+//! # Scales and Adaptive Ranges
+//!
+//! By default values are placed linearly between `min_val` and `max_val`. A
+//! [`ScalarScale`] changes that mapping (log, sqrt or a custom curve) for
+//! values spanning several orders of magnitude, and a [`ScalarAdapt`] mode lets
+//! the range follow the data (running min/max or running quantiles). Adaptation
+//! happens in `learn()`, so it is frozen whenever the block runs with
+//! `learn = false`, and the adapted range is saved in the block state.
+//!
 //! # Examples
 //!
 //! ```
@@ -37,10 +46,68 @@
 //! ```
 
 use crate::{Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 
+/// Mapping from a value's position in the range to its position in
+/// statelet space.
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::blocks::{ScalarScale, ScalarTransformer};
+///
+/// // Latencies from 1 ms to 60 s: each decade gets the same resolution
+/// let latency = ScalarTransformer::new(1.0, 60_000.0, 2048, 128, 2, 0)
+///     .with_scale(ScalarScale::Log);
+/// assert_eq!(latency.scale(), &ScalarScale::Log);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum ScalarScale {
+    /// Equal value steps get equal resolution
+    #[default]
+    Linear,
+    /// Equal value ratios get equal resolution (requires `min_val > 0`)
+    Log,
+    /// More resolution near `min_val`, less near `max_val`
+    Sqrt,
+    /// Piecewise-linear curve: output positions in [0, 1] sampled at evenly
+    /// spaced linear positions. Must start at 0.0, end at 1.0 and never
+    /// decrease, e.g. `[0.0, 0.6, 0.9, 1.0]`.
+    Custom(Vec<f64>),
+}
+
+/// How the encoding range follows the data.
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::blocks::{ScalarAdapt, ScalarTransformer};
+/// use gnomics::Block;
+///
+/// let mut st = ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0)
+///     .with_adapt(ScalarAdapt::MinMax);
+///
+/// st.set_value(250.0);
+/// st.execute(true).unwrap(); // learn = true lets the range adapt
+/// assert_eq!(st.max_val(), 250.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum ScalarAdapt {
+    /// Range fixed at construction; values are clamped
+    #[default]
+    Fixed,
+    /// Range grows to the running min/max of learned values
+    MinMax,
+    /// Track `num_points` running quantiles (endpoints are the running
+    /// min/max) and encode by quantile rank, so every quantile interval gets
+    /// equal resolution. Overrides the scale. `rate` is the quantile step as
+    /// a fraction of the current range.
+    Quantile { num_points: usize, rate: f64 },
+}
+
 /// Encodes continuous scalar values into overlapping binary patterns.
 ///
 /// Creates Sparse Distributed Representations (SDRs) where similar values
@@ -49,7 +116,7 @@ use std::rc::Rc;
 ///
 /// # Algorithm
 ///
-/// 1. Normalize value to [0, 1] range
+/// 1. Normalize value to [0, 1] range (via the scale or quantile curve)
 /// 2. Calculate center position: `center = normalized * (num_s - num_as)`
 /// 3. Activate contiguous window of `num_as` bits starting at center
 ///
@@ -73,8 +140,13 @@ pub struct ScalarTransformer {
     num_s: usize,  // Number of statelets
     num_as: usize, // Number of active statelets
     dif_s: usize,  // num_s - num_as
+    init_min: f64, // Constructor range (adaptation starts here)
+    init_max: f64,
+    scale: ScalarScale,
+    adapt: ScalarAdapt,
 
     // State
+    quantiles: Vec<f64>, // Running quantiles (Quantile mode only)
     value: f64,
     value_prev: f64, // For change detection optimization
 }
@@ -136,6 +208,11 @@ impl ScalarTransformer {
             num_s,
             num_as,
             dif_s,
+            init_min: min_val,
+            init_max: max_val,
+            scale: ScalarScale::Linear,
+            adapt: ScalarAdapt::Fixed,
+            quantiles: Vec::new(),
             value: min_val,
             value_prev: 0.123456789, // Unlikely sentinel value (matches C++)
         };
@@ -143,9 +220,176 @@ impl ScalarTransformer {
         st
     }
 
+    /// Set the value-to-position scale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `Log` is used with `min_val <= 0`, or a `Custom` curve has
+    /// fewer than 2 points, does not run from 0.0 to 1.0, or decreases.
+    pub fn set_scale(&mut self, scale: ScalarScale) {
+        match &scale {
+            ScalarScale::Log => {
+                assert!(self.min_val > 0.0, "Log scale requires min_val > 0")
+            }
+            ScalarScale::Custom(points) => {
+                assert!(points.len() >= 2, "custom curve needs at least 2 points");
+                assert!(
+                    points[0] == 0.0 && points[points.len() - 1] == 1.0,
+                    "custom curve must run from 0.0 to 1.0"
+                );
+                assert!(
+                    points.windows(2).all(|w| w[0] <= w[1]),
+                    "custom curve must be non-decreasing"
+                );
+            }
+            _ => {}
+        }
+        self.scale = scale;
+        self.value_prev = 0.123456789;
+    }
+
+    /// Builder form of [`set_scale`](Self::set_scale).
+    pub fn with_scale(mut self, scale: ScalarScale) -> Self {
+        self.set_scale(scale);
+        self
+    }
+
+    /// Set the range adaptation mode.
+    ///
+    /// Quantile mode starts from evenly spaced quantiles over the current
+    /// range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `Quantile` mode has `num_points < 2` or `rate` outside (0, 1].
+    pub fn set_adapt(&mut self, adapt: ScalarAdapt) {
+        self.quantiles.clear();
+        if let ScalarAdapt::Quantile { num_points, rate } = adapt {
+            assert!(num_points >= 2, "num_points must be at least 2");
+            assert!(rate > 0.0 && rate <= 1.0, "rate must be in (0, 1]");
+            let step = self.dif_val / (num_points - 1) as f64;
+            self.quantiles = (0..num_points)
+                .map(|k| self.min_val + step * k as f64)
+                .collect();
+            self.quantiles[num_points - 1] = self.max_val;
+        }
+        self.adapt = adapt;
+        self.value_prev = 0.123456789;
+    }
+
+    /// Builder form of [`set_adapt`](Self::set_adapt).
+    pub fn with_adapt(mut self, adapt: ScalarAdapt) -> Self {
+        self.set_adapt(adapt);
+        self
+    }
+
+    /// Get the value-to-position scale.
+    pub fn scale(&self) -> &ScalarScale {
+        &self.scale
+    }
+
+    /// Get the range adaptation mode.
+    pub fn adapt(&self) -> ScalarAdapt {
+        self.adapt
+    }
+
+    /// Get the running quantiles (empty unless in Quantile mode).
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+
+    /// Set the current range, e.g. to restore an adapted encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_val <= min_val`, or the range does not suit the scale.
+    pub fn set_range(&mut self, min_val: f64, max_val: f64) {
+        assert!(max_val > min_val, "max_val must be greater than min_val");
+        if self.scale == ScalarScale::Log {
+            assert!(min_val > 0.0, "Log scale requires min_val > 0");
+        }
+        self.min_val = min_val;
+        self.max_val = max_val;
+        self.dif_val = max_val - min_val;
+        self.value_prev = 0.123456789;
+    }
+
+    /// Map a value to its position in [0, 1] under the current range,
+    /// scale and quantiles.
+    pub fn position(&self, value: f64) -> f64 {
+        let v = value.clamp(self.min_val, self.max_val);
+
+        if self.quantiles.len() >= 2 {
+            let q = &self.quantiles;
+            let n = q.len();
+            let i = q.partition_point(|&x| x <= v).clamp(1, n - 1);
+            let (a, b) = (q[i - 1], q[i]);
+            let t = if b > a { (v - a) / (b - a) } else { 1.0 };
+            return (((i - 1) as f64 + t) / (n - 1) as f64).clamp(0.0, 1.0);
+        }
+
+        let linear = (v - self.min_val) / self.dif_val;
+        match &self.scale {
+            ScalarScale::Linear => linear,
+            ScalarScale::Log => {
+                (v.ln() - self.min_val.ln()) / (self.max_val.ln() - self.min_val.ln())
+            }
+            ScalarScale::Sqrt => linear.sqrt(),
+            ScalarScale::Custom(points) => {
+                let x = linear * (points.len() - 1) as f64;
+                let i = (x as usize).min(points.len() - 2);
+                let t = x - i as f64;
+                points[i] + t * (points[i + 1] - points[i])
+            }
+        }
+    }
+
+    /// Update the range (and quantiles) from the current value.
+    fn adapt_range(&mut self) {
+        let v = self.value;
+        if self.adapt == ScalarAdapt::Fixed || !v.is_finite() {
+            return;
+        }
+        // Log scale cannot extend the range to non-positive values
+        if self.scale == ScalarScale::Log && v <= 0.0 {
+            return;
+        }
+
+        if let ScalarAdapt::Quantile { rate, .. } = self.adapt {
+            let n = self.quantiles.len();
+            let step = rate * self.dif_val;
+            for k in 1..n - 1 {
+                let p = k as f64 / (n - 1) as f64;
+                if v < self.quantiles[k] {
+                    self.quantiles[k] -= step * (1.0 - p);
+                } else {
+                    self.quantiles[k] += step * p;
+                }
+            }
+        }
+
+        let (min_val, max_val) = (self.min_val.min(v), self.max_val.max(v));
+        if min_val != self.min_val || max_val != self.max_val {
+            self.set_range(min_val, max_val);
+        }
+
+        if !self.quantiles.is_empty() {
+            let n = self.quantiles.len();
+            for q in self.quantiles.iter_mut() {
+                *q = q.clamp(min_val, max_val);
+            }
+            self.quantiles.sort_unstable_by(|a, b| a.total_cmp(b));
+            self.quantiles[0] = min_val;
+            self.quantiles[n - 1] = max_val;
+            self.value_prev = 0.123456789;
+        }
+    }
+
     /// Set the current value to encode.
     ///
-    /// Value is automatically clamped to [min_val, max_val] range.
+    /// With a fixed range the value is clamped to [min_val, max_val]. In an
+    /// adaptive mode the raw value is kept (so `learn()` can extend the range)
+    /// and clamped only when encoding.
     ///
     /// # Examples
     ///
@@ -162,12 +406,16 @@ impl ScalarTransformer {
     /// assert_eq!(st.get_value(), 1.0);  // Clamped to max
     /// ```
     pub fn set_value(&mut self, value: f64) {
-        self.value = value.clamp(self.min_val, self.max_val);
+        self.value = match self.adapt {
+            ScalarAdapt::Fixed => value.clamp(self.min_val, self.max_val),
+            _ => value,
+        };
     }
 
     /// Get the current value.
     ///
-    /// Returns the last value set via `set_value()`, clamped to valid range.
+    /// Returns the last value set via `set_value()` (clamped to the valid
+    /// range when the range is fixed).
     pub fn get_value(&self) -> f64 {
         self.value
     }
@@ -191,6 +439,56 @@ impl ScalarTransformer {
     pub fn num_as(&self) -> usize {
         self.num_as
     }
+
+    /// Decode active statelets into the value range they encode.
+    ///
+    /// The low end is the value whose window starts at the first statelet
+    /// and the high end the value whose window ends at the last one, so a
+    /// single value's encoding decodes to (about) that value. Inverts the
+    /// current range, scale and quantiles. Returns `None` if `acts` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::ScalarTransformer;
+    /// use gnomics::{Block, OutputAccess};
+    ///
+    /// let mut st = ScalarTransformer::new(0.0, 100.0, 1024, 128, 2, 0);
+    /// st.set_value(40.0);
+    /// st.execute(false).unwrap();
+    ///
+    /// let acts = st.output().borrow().state.get_acts();
+    /// let (lo, hi) = st.decode_range(&acts).unwrap();
+    /// assert!((lo - 40.0).abs() < 0.2 && (hi - 40.0).abs() < 0.2);
+    /// ```
+    pub fn decode_range(&self, acts: &[usize]) -> Option<(f64, f64)> {
+        let first = *acts.iter().min()?;
+        let last = *acts.iter().max()?;
+
+        let lo = self.value_at(first.min(self.dif_s));
+        let hi = self.value_at((last + 1).saturating_sub(self.num_as).min(self.dif_s));
+        Some((lo.min(hi), lo.max(hi)))
+    }
+
+    /// Smallest value whose window starts at `beg` (inverse of `position()`).
+    fn value_at(&self, beg: usize) -> f64 {
+        if self.dif_s == 0 {
+            return self.min_val;
+        }
+
+        // position() is monotonic, so bisect on it
+        let target = beg as f64 / self.dif_s as f64;
+        let (mut lo, mut hi) = (self.min_val, self.max_val);
+        for _ in 0..64 {
+            let mid = 0.5 * (lo + hi);
+            if self.position(mid) < target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        hi
+    }
 }
 
 impl Block for ScalarTransformer {
@@ -226,11 +524,8 @@ impl Block for ScalarTransformer {
     fn compute(&mut self) {
         // Optimization: Only encode if value changed (matches C++ implementation)
         if self.value != self.value_prev {
-            // Clamp value to valid range
-            let clamped = self.value.clamp(self.min_val, self.max_val);
-
-            // Normalize to [0, 1]
-            let percent = (clamped - self.min_val) / self.dif_val;
+            // Clamp to valid range and normalize to [0, 1]
+            let percent = self.position(self.value);
 
             // Calculate starting position in statelet space
             let beg = ((self.dif_s as f64) * percent) as usize;
@@ -245,7 +540,8 @@ impl Block for ScalarTransformer {
     }
 
     fn learn(&mut self) {
-        // No learning in transformer
+        // Only adaptive ranges learn
+        self.adapt_range();
     }
 
     fn store(&mut self) {
@@ -253,7 +549,9 @@ impl Block for ScalarTransformer {
     }
 
     fn memory_usage(&self) -> usize {
-        std::mem::size_of::<Self>() + self.output.borrow().memory_usage()
+        std::mem::size_of::<Self>()
+            + self.quantiles.capacity() * std::mem::size_of::<f64>()
+            + self.output.borrow().memory_usage()
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -284,12 +582,14 @@ impl BlockBaseAccess for ScalarTransformer {
 impl crate::network_config::BlockConfigurable for ScalarTransformer {
     fn to_config(&self) -> crate::network_config::BlockConfig {
         crate::network_config::BlockConfig::ScalarTransformer {
-            min_val: self.min_val,
-            max_val: self.max_val,
+            min_val: self.init_min,
+            max_val: self.init_max,
             num_s: self.num_s,
             num_as: self.num_as,
             num_t: self.output.borrow().num_t(),
             seed: self.base().seed(),
+            scale: self.scale.clone(),
+            adapt: self.adapt,
         }
     }
 
@@ -300,12 +600,46 @@ impl crate::network_config::BlockConfigurable for ScalarTransformer {
 
 impl crate::network_config::BlockStateful for ScalarTransformer {
     fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
-        Ok(crate::network_config::BlockState::NoState)
-    }
-
-    fn from_state(&mut self, _state: &crate::network_config::BlockState) -> crate::Result<()> {
-        // Transformers have no learned state
-        Ok(())
+        if self.adapt == ScalarAdapt::Fixed {
+            return Ok(crate::network_config::BlockState::NoState);
+        }
+        Ok(crate::network_config::BlockState::ScalarTransformer {
+            min_val: self.min_val,
+            max_val: self.max_val,
+            quantiles: self.quantiles.clone(),
+        })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        match state {
+            crate::network_config::BlockState::NoState => Ok(()),
+            crate::network_config::BlockState::ScalarTransformer {
+                min_val,
+                max_val,
+                quantiles,
+            } => {
+                if !min_val.is_finite()
+                    || !max_val.is_finite()
+                    || max_val <= min_val
+                    || quantiles.len() != self.quantiles.len()
+                {
+                    return Err(crate::GnomicsError::Other(
+                        "Adapted range does not match ScalarTransformer".into(),
+                    ));
+                }
+                if self.scale == ScalarScale::Log && *min_val <= 0.0 {
+                    return Err(crate::GnomicsError::Other(
+                        "Log scale ScalarTransformer requires min_val > 0".into(),
+                    ));
+                }
+                self.set_range(*min_val, *max_val);
+                self.quantiles.clone_from(quantiles);
+                Ok(())
+            }
+            _ => Err(crate::GnomicsError::Other(
+                "Wrong state type for ScalarTransformer".into(),
+            )),
+        }
     }
 }
 
@@ -427,6 +761,59 @@ mod tests {
         assert_eq!(st.get_value(), st.min_val());
     }
 
+    #[test]
+    fn test_log_scale_positions() {
+        let st = ScalarTransformer::new(1.0, 1000.0, 1024, 64, 2, 0).with_scale(ScalarScale::Log);
+        assert!((st.position(10.0) - 1.0 / 3.0).abs() < 1e-12);
+        assert!((st.position(100.0) - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(st.position(1e6), 1.0);
+    }
+
+    #[test]
+    fn test_custom_curve_positions() {
+        let st = ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0)
+            .with_scale(ScalarScale::Custom(vec![0.0, 0.8, 1.0]));
+        assert!((st.position(0.25) - 0.4).abs() < 1e-12);
+        assert!((st.position(0.75) - 0.9).abs() < 1e-12);
+        assert_eq!(st.position(1.0), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Log scale requires min_val > 0")]
+    fn test_log_scale_invalid_range() {
+        ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0).with_scale(ScalarScale::Log);
+    }
+
+    #[test]
+    fn test_log_scale_from_state_rejects_invalid_range() {
+        use crate::network_config::{BlockState, BlockStateful};
+
+        let mut st = ScalarTransformer::new(1.0, 10.0, 1024, 64, 2, 0)
+            .with_scale(ScalarScale::Log)
+            .with_adapt(ScalarAdapt::MinMax);
+        for (min_val, max_val) in [(0.0, 10.0), (-1.0, 10.0), (5.0, 5.0), (f64::NAN, 10.0)] {
+            let state = BlockState::ScalarTransformer {
+                min_val,
+                max_val,
+                quantiles: Vec::new(),
+            };
+            assert!(st.from_state(&state).is_err());
+        }
+        assert_eq!((st.min_val(), st.max_val()), (1.0, 10.0));
+    }
+
+    #[test]
+    fn test_adapt_only_when_learning() {
+        let mut st =
+            ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0).with_adapt(ScalarAdapt::MinMax);
+        st.set_value(-5.0);
+        st.execute(false).unwrap();
+        assert_eq!(st.min_val(), 0.0);
+
+        st.execute(true).unwrap();
+        assert_eq!(st.min_val(), -5.0);
+    }
+
     #[test]
     fn test_memory_usage() {
         let st = ScalarTransformer::new(0.0, 1.0, 1024, 128, 2, 0);
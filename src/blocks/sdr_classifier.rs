@@ -0,0 +1,587 @@
+//! SdrClassifier - Learned linear readout from any BitField to labels or values.
+//!
+//! This module provides the `SdrClassifier` block. Unlike `PatternClassifier`,
+//! which learns its own features, the SDR classifier only learns a weight matrix
+//! from active input bits to labels and reads them out with a softmax. It is the
+//! standard readout on top of a `SequenceLearner` or any other block.
+//!
+//! # Algorithm
+//!
+//! Each step:
+//! 1. The active input bits are recorded in a short history (`steps + 1` entries)
+//! 2. `probs = softmax(sum of weights[i] over active bits i)`; the most probable
+//!    label's bit is set in the output
+//! 3. During learning with a label set, the input from `steps` steps ago is
+//!    trained towards that label: `weights[i][l] += alpha * (target[l] - p[l])`
+//!    for each bit `i` of that input
+//!
+//! With `steps = 0` the classifier labels the current input; with `steps = k` it
+//! predicts the label `k` steps ahead. Call [`SdrClassifier::reset`] at sequence
+//! boundaries so inputs are not paired with labels from another sequence.
+//!
+//! For scalar targets, [`SdrClassifier::with_value_range`] splits a range into
+//! `num_l` buckets; [`SdrClassifier::set_value`] selects the bucket and the
+//! classifier tracks the average actual value seen in each bucket.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{DiscreteTransformer, SdrClassifier};
+//! use gnomics::{Block, InputAccess, OutputAccess};
+//!
+//! let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+//! let mut classifier = SdrClassifier::new(4, 0, 0.1, 2, 0);
+//!
+//! classifier.input_mut().add_child(encoder.output(), 0);
+//! classifier.init().unwrap();
+//!
+//! for _ in 0..10 {
+//!     for value in 0..4 {
+//!         encoder.set_value(value);
+//!         encoder.execute(false).unwrap();
+//!         classifier.set_label(3 - value);
+//!         classifier.execute(true).unwrap();
+//!     }
+//! }
+//!
+//! encoder.set_value(1);
+//! encoder.execute(false).unwrap();
+//! classifier.execute(false).unwrap();
+//! assert_eq!(classifier.get_predicted_label(), 2);
+//! ```
+
+use crate::{Block, BlockBase, BlockBaseAccess, BlockInput, BlockOutput, Result};
+use crate::{InputAccess, OutputAccess};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Linear softmax readout from active input bits to labels or scalar buckets.
+///
+/// # Performance
+///
+/// - Compute time: O(active bits × num_l)
+/// - Learning time: O(active bits × num_l)
+/// - Memory: one `f64` weight per input bit and label
+pub struct SdrClassifier {
+    base: BlockBase,
+
+    /// Block input connection point
+    input: BlockInput,
+
+    /// Block output with history (one bit per label, the prediction is active)
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_l: usize,                    // Number of labels (or value buckets)
+    steps: usize,                    // Steps ahead the learned label is
+    alpha: f64,                      // Learning rate
+    num_t: usize,                    // History depth
+    value_range: Option<(f64, f64)>, // Bucket range when predicting values
+
+    // State
+    weights: Vec<f64>,             // Weights: [input bit][label]
+    bucket_values: Vec<f64>,       // Average actual value per bucket
+    label: Option<usize>,          // Current label for supervised learning
+    value: Option<f64>,            // Actual value behind the current label
+    history: VecDeque<Vec<usize>>, // Active input bits of recent steps, newest first
+    probs: Vec<f64>,               // Label probabilities for the current input
+
+    // Working memory
+    past_probs: Vec<f64>, // Scratch: label probabilities for the input being learned
+}
+
+impl SdrClassifier {
+    /// Create a new SdrClassifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_l` - Number of labels (or value buckets)
+    /// * `steps` - Steps ahead to predict (0 = label the current input)
+    /// * `alpha` - Learning rate (typically 0.001-0.1)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_l` == 0
+    /// - `alpha` <= 0.0
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::SdrClassifier;
+    ///
+    /// // Predict which of 10 labels comes 1 step after the current input
+    /// let classifier = SdrClassifier::new(10, 1, 0.1, 2, 0);
+    /// assert_eq!(classifier.steps(), 1);
+    /// ```
+    pub fn new(num_l: usize, steps: usize, alpha: f64, num_t: usize, seed: u64) -> Self {
+        assert!(num_l > 0, "num_l must be > 0");
+        assert!(alpha > 0.0, "alpha must be > 0");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_l);
+
+        Self {
+            base: BlockBase::new(seed),
+            input: BlockInput::new(),
+            output,
+            num_l,
+            steps,
+            alpha,
+            num_t,
+            value_range: None,
+            weights: Vec::new(),
+            bucket_values: Vec::new(),
+            label: None,
+            value: None,
+            history: VecDeque::with_capacity(steps + 1),
+            probs: vec![1.0 / num_l as f64; num_l],
+            past_probs: vec![0.0; num_l],
+        }
+    }
+
+    /// Predict scalar values by splitting `[min_val, max_val]` into `num_l` buckets.
+    ///
+    /// Use [`set_value`](Self::set_value) instead of `set_label` while training and
+    /// [`get_predicted_value`](Self::get_predicted_value) to read predictions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_val` >= `max_val`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::SdrClassifier;
+    ///
+    /// let mut classifier = SdrClassifier::new(20, 1, 0.1, 2, 0).with_value_range(0.0, 100.0);
+    /// classifier.set_value(42.0);
+    /// assert_eq!(classifier.get_label(), Some(8));
+    /// ```
+    pub fn with_value_range(mut self, min_val: f64, max_val: f64) -> Self {
+        assert!(min_val < max_val, "min_val must be < max_val");
+        let width = (max_val - min_val) / self.num_l as f64;
+        self.value_range = Some((min_val, max_val));
+        self.bucket_values = (0..self.num_l)
+            .map(|b| min_val + (b as f64 + 0.5) * width)
+            .collect();
+        self
+    }
+
+    /// Set the current label for supervised learning.
+    ///
+    /// The label is the target for the input `steps` steps ago. It applies to
+    /// every following learning step until it is changed or cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `label` >= `num_l`.
+    pub fn set_label(&mut self, label: usize) {
+        assert!(
+            label < self.num_l,
+            "label must be < num_l (got {} >= {})",
+            label,
+            self.num_l
+        );
+        self.label = Some(label);
+        self.value = None;
+    }
+
+    /// Set the current target value, selecting its bucket as the label.
+    ///
+    /// Values outside the range fall into the first or last bucket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value range was set with [`with_value_range`](Self::with_value_range).
+    pub fn set_value(&mut self, value: f64) {
+        let (min_val, max_val) = self.value_range.expect("value range not set");
+        let pct = (value - min_val) / (max_val - min_val);
+        let bucket = ((pct * self.num_l as f64).max(0.0) as usize).min(self.num_l - 1);
+        self.label = Some(bucket);
+        self.value = Some(value);
+    }
+
+    /// Clear the current label (and value); following steps do not learn.
+    pub fn clear_label(&mut self) {
+        self.label = None;
+        self.value = None;
+    }
+
+    /// Get the current label, if any.
+    pub fn get_label(&self) -> Option<usize> {
+        self.label
+    }
+
+    /// Forget recent inputs at a sequence boundary.
+    ///
+    /// Learning resumes once `steps` new inputs have been seen. Weights are kept.
+    pub fn reset(&mut self) {
+        self.history.clear();
+    }
+
+    /// Get label probabilities for the current input (sum to 1.0).
+    pub fn get_probabilities(&self) -> &[f64] {
+        &self.probs
+    }
+
+    /// Get the most probable label (ties go to the lowest index).
+    pub fn get_predicted_label(&self) -> usize {
+        self.probs
+            .iter()
+            .enumerate()
+            .fold(
+                (0, f64::MIN),
+                |best, (l, &p)| if p > best.1 { (l, p) } else { best },
+            )
+            .0
+    }
+
+    /// Get the average actual value of the most probable bucket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value range was set with [`with_value_range`](Self::with_value_range).
+    pub fn get_predicted_value(&self) -> f64 {
+        assert!(self.value_range.is_some(), "value range not set");
+        self.bucket_values[self.get_predicted_label()]
+    }
+
+    /// Get the average actual value seen in each bucket.
+    pub fn bucket_values(&self) -> &[f64] {
+        &self.bucket_values
+    }
+
+    /// Get the weights, `num_l` per input bit.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Get number of labels.
+    pub fn num_l(&self) -> usize {
+        self.num_l
+    }
+
+    /// Get number of steps ahead predicted.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Get the learning rate.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Get the value range, if predicting values.
+    pub fn value_range(&self) -> Option<(f64, f64)> {
+        self.value_range
+    }
+
+    /// Softmax over labels of the summed weights of `acts`, written to `probs`.
+    fn infer(weights: &[f64], acts: &[usize], probs: &mut [f64]) {
+        let num_l = probs.len();
+        probs.fill(0.0);
+        for &i in acts {
+            let row = &weights[i * num_l..(i + 1) * num_l];
+            for (p, &w) in probs.iter_mut().zip(row) {
+                *p += w;
+            }
+        }
+
+        let max = probs.iter().copied().fold(f64::MIN, f64::max);
+        for p in probs.iter_mut() {
+            *p = (*p - max).exp();
+        }
+        let total: f64 = probs.iter().sum();
+        for p in probs.iter_mut() {
+            *p /= total;
+        }
+    }
+}
+
+impl Block for SdrClassifier {
+    fn init(&mut self) -> Result<()> {
+        self.weights = vec![0.0; self.input.num_bits() * self.num_l];
+        self.base.set_initialized(true);
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement save
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement load
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.borrow_mut().clear();
+        self.reset();
+        self.probs.fill(1.0 / self.num_l as f64);
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        self.input.pull();
+    }
+
+    fn compute(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "SdrClassifier must be initialized before encoding"
+        );
+
+        // Every step counts towards the horizon, even if the input repeats.
+        // The oldest entry's buffer is reused once the history is full.
+        let mut acts = if self.history.len() > self.steps {
+            self.history.pop_back().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        self.input.state.get_acts_into(&mut acts);
+        Self::infer(&self.weights, &acts, &mut self.probs);
+        let empty = acts.is_empty();
+        self.history.push_front(acts);
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        if !empty {
+            output.state.set_bit(self.get_predicted_label());
+        }
+    }
+
+    fn learn(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "SdrClassifier must be initialized before learning"
+        );
+
+        let Some(label) = self.label else {
+            return;
+        };
+        let Some(past) = self.history.get(self.steps) else {
+            return;
+        };
+
+        let probs = if self.steps == 0 {
+            &self.probs
+        } else {
+            Self::infer(&self.weights, past, &mut self.past_probs);
+            &self.past_probs
+        };
+        for &i in past {
+            let row = &mut self.weights[i * self.num_l..(i + 1) * self.num_l];
+            for (l, (w, &p)) in row.iter_mut().zip(probs).enumerate() {
+                let target = if l == label { 1.0 } else { 0.0 };
+                *w += self.alpha * (target - p);
+            }
+        }
+
+        if let Some(value) = self.value {
+            let bucket = &mut self.bucket_values[label];
+            *bucket += self.alpha * (value - *bucket);
+        }
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.weights.capacity()
+                + self.bucket_values.capacity()
+                + self.probs.capacity()
+                + self.past_probs.capacity())
+                * std::mem::size_of::<f64>()
+            + self
+                .history
+                .iter()
+                .map(|acts| acts.capacity() * std::mem::size_of::<usize>())
+                .sum::<usize>()
+            + self.input.memory_usage()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        self.input.get_source_blocks()
+    }
+}
+
+impl BlockBaseAccess for SdrClassifier {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl InputAccess for SdrClassifier {
+    fn input(&self) -> &BlockInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut BlockInput {
+        &mut self.input
+    }
+}
+
+impl OutputAccess for SdrClassifier {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl crate::network_config::BlockConfigurable for SdrClassifier {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::SdrClassifier {
+            num_l: self.num_l,
+            steps: self.steps,
+            alpha: self.alpha,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+            value_range: self.value_range,
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "SdrClassifier"
+    }
+}
+
+impl crate::network_config::BlockStateful for SdrClassifier {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        Ok(crate::network_config::BlockState::SdrClassifier {
+            weights: self.weights.clone(),
+            bucket_values: self.bucket_values.clone(),
+        })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::SdrClassifier {
+            weights,
+            bucket_values,
+        } = state
+        {
+            if weights.len() != self.weights.len() {
+                return Err(crate::GnomicsError::InvalidInputSize {
+                    expected: self.weights.len(),
+                    actual: weights.len(),
+                });
+            }
+            if bucket_values.len() != self.bucket_values.len() {
+                return Err(crate::GnomicsError::InvalidInputSize {
+                    expected: self.bucket_values.len(),
+                    actual: bucket_values.len(),
+                });
+            }
+            self.weights.clone_from(weights);
+            self.bucket_values.clone_from(bucket_values);
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for SdrClassifier".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_support::{feed, with_source};
+
+    fn classifier_with_input(
+        num_i: usize,
+        steps: usize,
+    ) -> (SdrClassifier, Rc<RefCell<BlockOutput>>) {
+        with_source(SdrClassifier::new(3, steps, 0.5, 2, 0), num_i)
+    }
+
+    #[test]
+    fn test_untrained_probabilities_are_uniform() {
+        let (mut c, source) = classifier_with_input(16, 0);
+        feed(&source, [1, 2]);
+        c.execute(false).unwrap();
+        assert!(c
+            .get_probabilities()
+            .iter()
+            .all(|&p| (p - 1.0 / 3.0).abs() < 1e-12));
+        assert_eq!(c.output().borrow().state.get_acts(), vec![0]);
+    }
+
+    #[test]
+    fn test_learn_updates_only_active_rows() {
+        let (mut c, source) = classifier_with_input(16, 0);
+        feed(&source, [4]);
+        c.set_label(2);
+        c.execute(true).unwrap();
+
+        let row = &c.weights()[4 * 3..5 * 3];
+        assert!(row[2] > 0.0 && row[0] < 0.0 && row[1] < 0.0);
+        assert!(c.weights()[..4 * 3].iter().all(|&w| w == 0.0));
+    }
+
+    #[test]
+    fn test_steps_waits_for_history() {
+        let (mut c, source) = classifier_with_input(16, 2);
+        c.set_label(1);
+        for acts in [[0], [1]] {
+            feed(&source, acts);
+            c.execute(true).unwrap();
+        }
+        assert!(c.weights().iter().all(|&w| w == 0.0));
+
+        // Third step pairs the first input with the current label
+        feed(&source, [2]);
+        c.execute(true).unwrap();
+        assert!(c.weights()[1] > 0.0);
+        assert!(c.weights()[3..].iter().all(|&w| w == 0.0));
+    }
+
+    #[test]
+    fn test_from_state_rejects_short_bucket_values() {
+        use crate::network_config::{BlockState, BlockStateful};
+
+        let (mut c, _source) = with_source(
+            SdrClassifier::new(3, 0, 0.5, 2, 0).with_value_range(0.0, 1.0),
+            16,
+        );
+        let state = BlockState::SdrClassifier {
+            weights: c.weights().to_vec(),
+            bucket_values: vec![0.0; 2],
+        };
+        assert!(c.from_state(&state).is_err());
+        assert_eq!(c.bucket_values().len(), 3);
+    }
+
+    #[test]
+    fn test_empty_input_has_no_output() {
+        let (mut c, source) = classifier_with_input(16, 0);
+        feed(&source, []);
+        c.execute(false).unwrap();
+        assert_eq!(c.output().borrow().state.num_set(), 0);
+    }
+}
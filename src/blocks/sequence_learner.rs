@@ -15,6 +15,22 @@
 //! 2. **Surprise**: If unpredicted, activate statelets and learn
 //! 3. **Learning**: Dendrites learn the transition from previous → current
 //!
+//! Besides the active statelets, the learner exposes a second output,
+//! `predicted()`: every statelet with a dendrite over threshold on the
+//! current output, i.e. the statelets expected on the next step. Predicting
+//! scans every used dendrite, so it only runs once enabled with
+//! `set_predicted_enabled(true)`; Network's port-aware connect functions
+//! (e.g. `connect_predicted_to_input()`) enable it for you.
+//!
+//! # Anomaly Explanations
+//!
+//! `explain_anomaly()` reports why the last step was (or wasn't) anomalous:
+//! the surprised columns, the expected columns that did not occur, and the
+//! best-matching dendrite of each surprised column with its overlap. Through
+//! `Network::explain_anomaly()` the expected columns are also decoded into a
+//! value range when the learner's input is a single scalar or discrete
+//! transformer.
+//!
 //! # Architecture
 //!
 //! ```text
@@ -89,7 +105,7 @@ use crate::bitfield::BitField;
 use crate::utils;
 use crate::{
     Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput, ContextAccess,
-    InputAccess, MemoryAccess, OutputAccess, Result,
+    InputAccess, MemoryAccess, OutputAccess, OutputPort, Result, PREV,
 };
 use std::cell::RefCell;
 use std::path::Path;
@@ -119,6 +135,9 @@ pub struct SequenceLearner {
     /// Block output with history (also feeds back to context)
     output: Rc<RefCell<BlockOutput>>,
 
+    /// Predicted statelets for the next step (dendrites over threshold on output)
+    predicted: Rc<RefCell<BlockOutput>>,
+
     /// Block memory with synaptic learning
     memory: BlockMemory,
 
@@ -139,15 +158,55 @@ pub struct SequenceLearner {
     perm_dec: u8, // Permanence decrement
 
     // State
-    next_sd: Vec<usize>, // Next available dendrite per statelet
-    d_used: BitField,    // Dendrite usage mask (1=used, 0=available)
-    anomaly_score: f64,  // Current anomaly score (0.0-1.0)
-    always_update: bool, // Update even if inputs unchanged
+    next_sd: Vec<usize>,     // Next available dendrite per statelet
+    d_used: BitField,        // Dendrite usage mask (1=used, 0=available)
+    anomaly_score: f64,      // Current anomaly score (0.0-1.0)
+    always_update: bool,     // Update even if inputs unchanged
+    predicted_enabled: bool, // Compute the predicted output
 
     // Working memory
     input_acts: Vec<usize>, // Active column indices
     d_acts: Vec<usize>,     // Active dendrite indices
+    u_acts: Vec<usize>,     // Scratch: used dendrite indices
     surprise_flag: bool,    // Surprise detected for current column
+
+    // Explanation of the last step
+    surprised: Vec<usize>,            // Surprised column indices
+    best_matches: Vec<DendriteMatch>, // Best dendrite per surprised column
+}
+
+/// Best-matching dendrite of a surprised column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DendriteMatch {
+    /// Column the dendrite belongs to
+    pub column: usize,
+    /// Statelet the dendrite belongs to
+    pub statelet: usize,
+    /// Dendrite index
+    pub dendrite: usize,
+    /// Connected receptors on active context bits (below `d_thresh`)
+    pub overlap: usize,
+}
+
+/// Explanation of a SequenceLearner's last anomaly score.
+///
+/// Returned by [`SequenceLearner::explain_anomaly`]; `expected_range` is only
+/// filled in by [`Network::explain_anomaly`](crate::Network::explain_anomaly).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnomalyExplanation {
+    /// Anomaly score of the step (fraction of surprised columns)
+    pub anomaly_score: f64,
+    /// Active columns that no dendrite predicted
+    pub surprised_columns: Vec<usize>,
+    /// Columns predicted on the previous step that are not active
+    pub missing_columns: Vec<usize>,
+    /// Columns predicted on the previous step
+    pub expected_columns: Vec<usize>,
+    /// Best-matching used dendrite of each surprised column that has one
+    pub best_matches: Vec<DendriteMatch>,
+    /// Value range the expected columns decode to via the upstream transformer
+    /// (`lo > hi` when a cyclic range wraps)
+    pub expected_range: Option<(f64, f64)>,
 }
 
 impl SequenceLearner {
@@ -226,11 +285,16 @@ impl SequenceLearner {
         // Self-feedback: context pulls from output[PREV] (time=1)
         context.add_child(Rc::clone(&output_rc), 1);
 
+        let mut predicted = BlockOutput::new();
+        predicted.set_source_port(OutputPort::Predicted);
+        predicted.setup(num_t, num_s);
+
         Self {
             base: BlockBase::new(seed),
             input: BlockInput::new(),
             context,
             output: output_rc,
+            predicted: Rc::new(RefCell::new(predicted)),
             memory: BlockMemory::new(num_d, num_rpd, perm_thr, perm_inc, perm_dec, pct_learn),
             num_c,
             num_spc,
@@ -248,9 +312,13 @@ impl SequenceLearner {
             d_used: BitField::new(num_d),
             anomaly_score: 0.0,
             always_update,
+            predicted_enabled: false,
             input_acts: Vec::new(),
             d_acts: Vec::new(),
+            u_acts: Vec::new(),
             surprise_flag: false,
+            surprised: Vec::new(),
+            best_matches: Vec::new(),
         }
     }
 
@@ -312,6 +380,91 @@ impl SequenceLearner {
         self.d_thresh
     }
 
+    /// Explain the last step's anomaly score.
+    ///
+    /// Reports the surprised columns with their best-matching dendrites, and
+    /// compares the active columns with those predicted on the previous step
+    /// (empty unless predictions are enabled, see `set_predicted_enabled()`).
+    /// Overlaps are recorded before learning, so the explanation holds after
+    /// `execute(true)`. `expected_range` is left `None`; use
+    /// `Network::explain_anomaly()` to decode it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::{DiscreteTransformer, SequenceLearner};
+    /// use gnomics::{Block, InputAccess, OutputAccess};
+    ///
+    /// let mut encoder = DiscreteTransformer::new(4, 64, 2, 0);
+    /// let mut learner = SequenceLearner::new(64, 4, 8, 12, 6, 20, 2, 1, 2, false, 0);
+    /// learner.input_mut().add_child(encoder.output(), 0);
+    /// learner.init().unwrap();
+    ///
+    /// encoder.set_value(2);
+    /// encoder.execute(false).unwrap();
+    /// learner.execute(true).unwrap();
+    ///
+    /// // Nothing was learned yet, so every active column is a surprise
+    /// let explanation = learner.explain_anomaly();
+    /// assert_eq!(explanation.anomaly_score, 1.0);
+    /// assert_eq!(explanation.surprised_columns.len(), 16);
+    /// assert!(explanation.best_matches.is_empty());
+    /// ```
+    pub fn explain_anomaly(&self) -> AnomalyExplanation {
+        let mut expected_columns: Vec<usize> = self
+            .predicted
+            .borrow()
+            .get_acts(PREV)
+            .into_iter()
+            .map(|s| s / self.num_spc)
+            .collect();
+        expected_columns.dedup();
+
+        let missing_columns = expected_columns
+            .iter()
+            .copied()
+            .filter(|&c| self.input.state.get_bit(c) == 0)
+            .collect();
+
+        AnomalyExplanation {
+            anomaly_score: self.anomaly_score,
+            surprised_columns: self.surprised.clone(),
+            missing_columns,
+            expected_columns,
+            best_matches: self.best_matches.clone(),
+            expected_range: None,
+        }
+    }
+
+    /// Get the predicted statelets output.
+    ///
+    /// Holds every statelet with a dendrite over `d_thresh` on the current
+    /// output, which is the context of the next step: the statelets expected
+    /// to fire next. Stays empty unless predictions are enabled (see
+    /// `set_predicted_enabled()`). Connect it downstream through Network
+    /// (e.g. `Network::connect_predicted_to_input()`), which enables
+    /// predictions and keeps the connection in `to_config()`.
+    pub fn predicted(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.predicted)
+    }
+
+    /// Enable or disable computing the predicted output.
+    ///
+    /// Predictions take effect from the next `compute()`; disabling clears
+    /// the predicted output. `explain_anomaly()` needs them for
+    /// `expected_columns` and `missing_columns`.
+    pub fn set_predicted_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.predicted.borrow_mut().clear();
+        }
+        self.predicted_enabled = enabled;
+    }
+
+    /// Check whether the predicted output is computed.
+    pub fn predicted_enabled(&self) -> bool {
+        self.predicted_enabled
+    }
+
     /// Recognition phase: check if any dendrite predicts the column.
     ///
     /// For the given column, checks all its dendrites against the previous output.
@@ -320,6 +473,7 @@ impl SequenceLearner {
     fn recognition(&mut self, c: usize) {
         let d_beg = c * self.num_dpc;
         let d_end = d_beg + self.num_dpc;
+        let mut best: Option<(usize, usize)> = None;
 
         // For every dendrite on the column
         for d in d_beg..d_end {
@@ -334,6 +488,23 @@ impl SequenceLearner {
                     self.output.borrow_mut().state.set_bit(s);
                     self.surprise_flag = false;
                 }
+
+                if best.is_none_or(|(_, o)| overlap > o) {
+                    best = Some((d, overlap));
+                }
+            }
+        }
+
+        // Keep the near miss to explain the surprise
+        if self.surprise_flag {
+            self.surprised.push(c);
+            if let Some((d, overlap)) = best {
+                self.best_matches.push(DendriteMatch {
+                    column: c,
+                    statelet: d / self.num_dps,
+                    dendrite: d,
+                    overlap,
+                });
             }
         }
     }
@@ -389,6 +560,24 @@ impl SequenceLearner {
             self.next_sd[s] += 1;
         }
     }
+
+    /// Predict phase: mark every statelet with a used dendrite over threshold
+    /// on the current output (the next step's context).
+    fn predict(&mut self) {
+        let output = self.output.borrow();
+        let mut predicted = self.predicted.borrow_mut();
+        predicted.state.clear_all();
+
+        self.d_used.get_acts_into(&mut self.u_acts);
+        for &d in &self.u_acts {
+            let s = d / self.num_dps;
+            if predicted.state.get_bit(s) == 0
+                && self.memory.overlap(d, &output.state) >= self.d_thresh as usize
+            {
+                predicted.state.set_bit(s);
+            }
+        }
+    }
 }
 
 impl Block for SequenceLearner {
@@ -424,6 +613,7 @@ impl Block for SequenceLearner {
         self.input.clear();
         self.context.clear();
         self.output.borrow_mut().clear();
+        self.predicted.borrow_mut().clear();
         self.memory.clear();
         self.anomaly_score = 0.0;
         self.input_acts.clear();
@@ -432,6 +622,7 @@ impl Block for SequenceLearner {
 
     fn step(&mut self) {
         self.output.borrow_mut().step();
+        self.predicted.borrow_mut().step();
     }
 
     fn pull(&mut self) {
@@ -445,16 +636,19 @@ impl Block for SequenceLearner {
         // Check if any input changed
         if self.always_update || self.input.children_changed() || self.context.children_changed() {
             // Get active columns
-            self.input_acts = self.input.state.get_acts();
+            self.input.state.get_acts_into(&mut self.input_acts);
 
             // Clear state
             self.anomaly_score = 0.0;
             self.output.borrow_mut().state.clear_all();
             self.d_acts.clear();
+            self.surprised.clear();
+            self.best_matches.clear();
 
             // Process each active column
-            let input_acts = self.input_acts.clone();
-            for c in input_acts {
+            // Index loop: recognition/surprise need &mut self
+            for i in 0..self.input_acts.len() {
+                let c = self.input_acts[i];
                 self.surprise_flag = true;
 
                 // Try recognition
@@ -465,6 +659,10 @@ impl Block for SequenceLearner {
                     self.surprise(c);
                 }
             }
+
+            if self.predicted_enabled {
+                self.predict();
+            }
         }
     }
 
@@ -474,8 +672,7 @@ impl Block for SequenceLearner {
         // Check if any input changed
         if self.always_update || self.input.children_changed() || self.context.children_changed() {
             // Learn on all active dendrites
-            let d_acts = self.d_acts.clone();
-            for d in d_acts {
+            for &d in &self.d_acts {
                 self.memory
                     .learn_move(d, &self.context.state, self.base.rng());
                 self.d_used.set_bit(d);
@@ -485,6 +682,7 @@ impl Block for SequenceLearner {
 
     fn store(&mut self) {
         self.output.borrow_mut().store();
+        self.predicted.borrow_mut().store();
     }
 
     fn memory_usage(&self) -> usize {
@@ -492,11 +690,15 @@ impl Block for SequenceLearner {
         bytes += self.input.memory_usage();
         bytes += self.context.memory_usage();
         bytes += self.output.borrow().memory_usage();
+        bytes += self.predicted.borrow().memory_usage();
         bytes += self.memory.memory_usage();
         bytes += self.next_sd.capacity() * std::mem::size_of::<usize>();
         bytes += self.d_used.memory_usage();
         bytes += self.input_acts.capacity() * std::mem::size_of::<usize>();
         bytes += self.d_acts.capacity() * std::mem::size_of::<usize>();
+        bytes += self.u_acts.capacity() * std::mem::size_of::<usize>();
+        bytes += self.surprised.capacity() * std::mem::size_of::<usize>();
+        bytes += self.best_matches.capacity() * std::mem::size_of::<DendriteMatch>();
         bytes
     }
 
@@ -575,6 +777,7 @@ impl crate::network_config::BlockConfigurable for SequenceLearner {
             num_t: self.num_t,
             always_update: self.always_update,
             seed: self.base().seed(),
+            predicted_enabled: self.predicted_enabled,
         }
     }
 
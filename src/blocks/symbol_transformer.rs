@@ -0,0 +1,380 @@
+//! SymbolTransformer - Encodes open-vocabulary symbols as hashed random patterns.
+//!
+//! This module provides the `SymbolTransformer` block for categorical values
+//! whose vocabulary is not known up front: log message types, user IDs, words.
+//! Unlike `DiscreteTransformer`, there is no `num_v` and no bucket layout; each
+//! symbol (a `u64` key or a string) is hashed to a deterministic random set of
+//! `num_as` active bits, so any value can be encoded without panicking.
+//!
+//! # Semantic Properties
+//!
+//! - **Open Vocabulary**: Unseen symbols get a stable pattern on first use
+//! - **Near-Orthogonal**: Unrelated symbols overlap only by chance
+//!   (about `num_as^2 / num_s` bits)
+//! - **Similarity Hints**: A symbol can be tied to an anchor symbol so that it
+//!   shares a chosen number of bits with the anchor's pattern
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::SymbolTransformer;
+//! use gnomics::{Block, OutputAccess};
+//!
+//! let mut st = SymbolTransformer::new(2048, 40, 2, 0);
+//!
+//! // "cat" and "dog" both share 20 bits with "animal"
+//! let animal = SymbolTransformer::key("animal");
+//! st.set_hint(SymbolTransformer::key("cat"), animal, 20);
+//! st.set_hint(SymbolTransformer::key("dog"), animal, 20);
+//!
+//! st.set_symbol("cat");
+//! st.execute(false).unwrap();
+//! let cat = st.output().borrow().state.clone();
+//!
+//! st.set_symbol("dog");
+//! st.execute(false).unwrap();
+//! let dog = st.output().borrow().state.clone();
+//!
+//! assert_eq!(cat.num_set(), 40);
+//! assert!(cat.num_similar(&dog) >= 20);
+//! ```
+
+use crate::utils::hash_u64;
+use crate::{BitField, Block, BlockBase, BlockBaseAccess, BlockOutput, OutputAccess, Result};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Encodes arbitrary symbols into deterministic random binary patterns.
+///
+/// # Algorithm
+///
+/// 1. Strings are reduced to a `u64` key with 64-bit FNV-1a
+/// 2. Key `k` defines an endless sequence of bit positions
+///    `hash(seed, hash(k, i)) mod num_s` for `i = 0, 1, 2, ...`
+/// 3. If `k` has a hint `(anchor, n)`, the first `n` positions are taken
+///    from the anchor's sequence
+/// 4. The remaining bits are taken from `k`'s own sequence, skipping
+///    positions that are already active, until `num_as` bits are set
+///
+/// # Performance
+///
+/// - Encoding time: ~200ns for 40 active bits (one hash per active bit)
+/// - Memory: output history plus one map entry per hint
+/// - No learning overhead (encoder only)
+#[derive(Clone)]
+pub struct SymbolTransformer {
+    base: BlockBase,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    // Parameters
+    num_s: usize,  // Number of statelets
+    num_as: usize, // Number of active statelets
+
+    // State
+    hints: HashMap<u64, (u64, usize)>, // symbol -> (anchor, shared bits)
+    value: u64,
+    value_prev: Option<u64>, // For change detection optimization
+}
+
+impl SymbolTransformer {
+    /// Create a new SymbolTransformer.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_s` - Number of statelets (output bits)
+    /// * `num_as` - Number of active statelets per symbol (typically ~2% of num_s)
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed selecting the hash family
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_as` == 0 or `num_as` > `num_s`
+    /// - `num_t` < 2
+    pub fn new(num_s: usize, num_as: usize, num_t: usize, seed: u64) -> Self {
+        assert!(num_as > 0, "num_as must be > 0");
+        assert!(num_as <= num_s, "num_as must be <= num_s");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            output,
+            num_s,
+            num_as,
+            hints: HashMap::new(),
+            value: 0,
+            value_prev: None,
+        }
+    }
+
+    /// Get the `u64` key of a string symbol (64-bit FNV-1a).
+    ///
+    /// Stable across platforms and runs, so string symbols can be mixed with
+    /// precomputed keys and stored in hints.
+    pub fn key(symbol: &str) -> u64 {
+        symbol.bytes().fold(0xCBF2_9CE4_8422_2325, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        })
+    }
+
+    /// Set the symbol key to encode.
+    pub fn set_value(&mut self, key: u64) {
+        self.value = key;
+    }
+
+    /// Set a string symbol to encode.
+    pub fn set_symbol(&mut self, symbol: &str) {
+        self.set_value(Self::key(symbol));
+    }
+
+    /// Get the current symbol key.
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+
+    /// Make `symbol` share `num_shared` bits with the pattern of `anchor`.
+    ///
+    /// Symbols hinted to the same anchor also share those bits with each
+    /// other. Anchors are always encoded from their own hash (hints are not
+    /// followed transitively). Replaces any existing hint for `symbol`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_shared` > `num_as`.
+    pub fn set_hint(&mut self, symbol: u64, anchor: u64, num_shared: usize) {
+        assert!(num_shared <= self.num_as, "num_shared must be <= num_as");
+        self.hints.insert(symbol, (anchor, num_shared));
+        self.value_prev = None;
+    }
+
+    /// Remove the hint for `symbol`, if any.
+    pub fn remove_hint(&mut self, symbol: u64) {
+        self.hints.remove(&symbol);
+        self.value_prev = None;
+    }
+
+    /// Get the hint for `symbol` as `(anchor, num_shared)`, if any.
+    pub fn hint(&self, symbol: u64) -> Option<(u64, usize)> {
+        self.hints.get(&symbol).copied()
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+}
+
+/// Set `count` more bits of `state` from the position sequence of `key`.
+fn set_from_sequence(state: &mut BitField, seed: u64, key: u64, count: usize) {
+    let num_s = state.num_bits() as u64;
+    let mut set = 0;
+    let mut i = 0;
+    while set < count {
+        let s = (hash_u64(seed, hash_u64(key, i)) % num_s) as usize;
+        if state.get_bit(s) == 0 {
+            state.set_bit(s);
+            set += 1;
+        }
+        i += 1;
+    }
+}
+
+impl Block for SymbolTransformer {
+    fn init(&mut self) -> Result<()> {
+        // Already initialized in new()
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement serialization
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement deserialization
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        // Keep hints: they define the encoding, not the current input
+        self.output.borrow_mut().clear();
+        self.value = 0;
+        self.value_prev = None;
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        // No inputs - transformer is a source block
+    }
+
+    fn compute(&mut self) {
+        // Optimization: Only encode if symbol changed
+        if self.value_prev == Some(self.value) {
+            return;
+        }
+
+        let seed = self.base.seed();
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+
+        let mut shared = 0;
+        if let Some(&(anchor, num_shared)) = self.hints.get(&self.value) {
+            set_from_sequence(&mut output.state, seed, anchor, num_shared);
+            shared = num_shared;
+        }
+        set_from_sequence(&mut output.state, seed, self.value, self.num_as - shared);
+
+        self.value_prev = Some(self.value);
+    }
+
+    fn learn(&mut self) {
+        // No learning in transformer
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.hints.capacity() * std::mem::size_of::<(u64, (u64, usize))>()
+            + self.output.borrow().memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl OutputAccess for SymbolTransformer {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl BlockBaseAccess for SymbolTransformer {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl crate::network_config::BlockConfigurable for SymbolTransformer {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::SymbolTransformer {
+            num_s: self.num_s,
+            num_as: self.num_as,
+            num_t: self.output.borrow().num_t(),
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "SymbolTransformer"
+    }
+}
+
+impl crate::network_config::BlockStateful for SymbolTransformer {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        let mut hints: Vec<(u64, u64, usize)> = self
+            .hints
+            .iter()
+            .map(|(&symbol, &(anchor, shared))| (symbol, anchor, shared))
+            .collect();
+        hints.sort_unstable();
+        Ok(crate::network_config::BlockState::SymbolTransformer { hints })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::SymbolTransformer { hints } = state {
+            if hints.iter().any(|&(_, _, shared)| shared > self.num_as) {
+                return Err(crate::GnomicsError::Other(
+                    "Hint shares more bits than num_as".into(),
+                ));
+            }
+            self.hints = hints
+                .iter()
+                .map(|&(symbol, anchor, shared)| (symbol, (anchor, shared)))
+                .collect();
+            self.value_prev = None;
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for SymbolTransformer".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let st = SymbolTransformer::new(1024, 20, 2, 0);
+        assert_eq!(st.num_s(), 1024);
+        assert_eq!(st.num_as(), 20);
+        assert_eq!(st.hint(1), None);
+    }
+
+    #[test]
+    fn test_key_is_fnv1a() {
+        assert_eq!(SymbolTransformer::key(""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(SymbolTransformer::key("a"), 0xAF63_DC4C_8601_EC8C);
+        assert_ne!(SymbolTransformer::key("ab"), SymbolTransformer::key("ba"));
+    }
+
+    #[test]
+    fn test_encode_num_active() {
+        // Dense setting forces hash collisions; skipping keeps the count exact
+        let mut st = SymbolTransformer::new(64, 48, 2, 0);
+        for key in 0..100 {
+            st.set_value(key);
+            st.compute();
+            assert_eq!(st.output().borrow().state.num_set(), 48);
+        }
+    }
+
+    #[test]
+    fn test_full_hint_copies_anchor() {
+        let mut st = SymbolTransformer::new(1024, 20, 2, 0);
+        st.set_value(7);
+        st.compute();
+        let anchor = st.output().borrow().state.clone();
+
+        st.set_hint(8, 7, 20);
+        st.set_value(8);
+        st.compute();
+        assert_eq!(st.output().borrow().state, anchor);
+    }
+
+    #[test]
+    #[should_panic(expected = "num_shared must be <= num_as")]
+    fn test_hint_too_large() {
+        let mut st = SymbolTransformer::new(1024, 20, 2, 0);
+        st.set_hint(1, 2, 21);
+    }
+}
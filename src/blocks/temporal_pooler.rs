@@ -0,0 +1,583 @@
+//! TemporalPooler - Learns stable representations of whole sequences.
+//!
+//! This module provides the `TemporalPooler` block (a union pooler). Where a
+//! `SequenceLearner` output changes at every step, the temporal pooler keeps a
+//! decaying union of recent predicted-active inputs and learns a sparse output
+//! that stays stable for the whole sequence, so a downstream `PatternClassifier`
+//! can label the sequence rather than the current position within it.
+//!
+//! # Algorithm
+//!
+//! Each step:
+//! 1. `drive = input AND context` if a context is connected (e.g. the predicted
+//!    cells of a learner), otherwise `drive = input`
+//! 2. Statelet pooling activation decays and accumulates overlap with the drive:
+//!    `pooling[s] = decay * pooling[s] + overlap(s, drive)`. Statelets active on
+//!    the previous step skip the decay while they are still driven, so a pooled
+//!    representation persists until its input stops supporting it. Activation
+//!    is capped at `num_rpd / (1 - decay)`, the level a statelet with every
+//!    receptor driven settles at, so held statelets cannot grow without bound
+//! 3. Top `num_as` statelets by pooling activation become active
+//! 4. Input bits driven within the last few steps form the input union (each bit's
+//!    trace decays by `decay` per step and leaves the union below 0.1)
+//! 5. During learning, active statelets strengthen connections to the whole
+//!    union, so every element of a sequence ends up driving the same statelets
+//!
+//! Call [`TemporalPooler::reset`] at sequence boundaries to forget accumulated
+//! activity without touching learned memory.
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::blocks::{DiscreteTransformer, TemporalPooler};
+//! use gnomics::{Block, InputAccess, OutputAccess};
+//!
+//! let mut encoder = DiscreteTransformer::new(8, 512, 2, 0);
+//! let mut pooler = TemporalPooler::new(1024, 40, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0);
+//!
+//! pooler.input_mut().add_child(encoder.output(), 0);
+//! pooler.init().unwrap();
+//!
+//! for value in [0, 1, 2, 3] {
+//!     encoder.set_value(value);
+//!     encoder.execute(false).unwrap();
+//!     pooler.execute(true).unwrap();
+//! }
+//!
+//! assert_eq!(pooler.output().borrow().state.num_set(), 40);
+//! // The union still holds earlier elements of the sequence
+//! assert!(pooler.union().num_set() > 64);
+//! ```
+
+use crate::bitfield::BitField;
+use crate::{
+    Block, BlockBase, BlockBaseAccess, BlockInput, BlockMemory, BlockOutput, ContextAccess,
+    InputAccess, MemoryAccess, OutputAccess, Result,
+};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Input bits whose trace falls below this leave the union.
+const UNION_MIN: f64 = 0.1;
+
+/// Receptors per dendrite (matches PatternPooler).
+const NUM_RPD: usize = 128;
+
+/// Learns stable sparse representations of sequences from a decaying union of inputs.
+///
+/// # Performance
+///
+/// - Compute time: PatternPooler overlap plus one pass over input bits and statelets
+/// - Memory: PatternPooler memory plus one `f64` per input bit and per statelet
+pub struct TemporalPooler {
+    base: BlockBase,
+
+    /// Block input connection point (typically a learner's active statelets)
+    input: BlockInput,
+
+    /// Optional mask restricting pooling to predicted input bits
+    context: BlockInput,
+
+    /// Block output with history
+    output: Rc<RefCell<BlockOutput>>,
+
+    /// Block memory with synaptic learning
+    memory: BlockMemory,
+
+    // Parameters
+    num_s: usize,   // Number of statelets (dendrites)
+    num_as: usize,  // Active statelets
+    perm_thr: u8,   // Permanence threshold
+    perm_inc: u8,   // Permanence increment
+    perm_dec: u8,   // Permanence decrement
+    pct_pool: f64,  // Pooling percentage
+    pct_conn: f64,  // Initial connectivity
+    pct_learn: f64, // Learning percentage
+    decay: f64,     // Per-step decay of pooling activation and input traces
+    num_t: usize,   // History depth
+
+    // State
+    pooling: Vec<f64>, // Pooling activation per statelet
+    trace: Vec<f64>,   // Recency trace per input bit
+    union: BitField,   // Input bits with trace >= UNION_MIN
+    drive: BitField,   // Predicted-active input this step
+
+    // Working memory
+    overlaps: Vec<usize>, // Overlap scores per dendrite
+    indices: Vec<usize>,  // Scratch: statelets ranked by pooling activation
+}
+
+impl TemporalPooler {
+    /// Create a new TemporalPooler.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_s` - Number of statelets (dendrites)
+    /// * `num_as` - Number of active statelets in output
+    /// * `perm_thr` - Permanence threshold (typically 20/99)
+    /// * `perm_inc` - Permanence increment (typically 2)
+    /// * `perm_dec` - Permanence decrement (typically 1)
+    /// * `pct_pool` - Pooling percentage (typically 0.8)
+    /// * `pct_conn` - Initial connectivity (typically 0.5)
+    /// * `pct_learn` - Learning percentage (typically 0.3)
+    /// * `decay` - Per-step decay in [0.0, 1.0); higher values pool over longer sequences
+    /// * `num_t` - History depth (must be >= 2)
+    /// * `seed` - RNG seed for reproducibility
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `num_as` > `num_s`
+    /// - `decay` is not in [0.0, 1.0)
+    /// - `num_t` < 2
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::blocks::TemporalPooler;
+    ///
+    /// // Pools roughly the last 10 steps
+    /// let pooler = TemporalPooler::new(2048, 40, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_s: usize,
+        num_as: usize,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        pct_pool: f64,
+        pct_conn: f64,
+        pct_learn: f64,
+        decay: f64,
+        num_t: usize,
+        seed: u64,
+    ) -> Self {
+        assert!(num_as <= num_s, "num_as must be <= num_s");
+        assert!((0.0..1.0).contains(&decay), "decay must be in [0.0, 1.0)");
+        assert!(num_t >= 2, "num_t must be at least 2");
+
+        let output = Rc::new(RefCell::new(BlockOutput::new()));
+        output.borrow_mut().setup(num_t, num_s);
+
+        Self {
+            base: BlockBase::new(seed),
+            input: BlockInput::new(),
+            context: BlockInput::new(),
+            output,
+            memory: BlockMemory::new(num_s, NUM_RPD, perm_thr, perm_inc, perm_dec, pct_learn),
+            num_s,
+            num_as,
+            perm_thr,
+            perm_inc,
+            perm_dec,
+            pct_pool,
+            pct_conn,
+            pct_learn,
+            decay,
+            num_t,
+            pooling: vec![0.0; num_s],
+            trace: Vec::new(),
+            union: BitField::new(0),
+            drive: BitField::new(0),
+            overlaps: vec![0; num_s],
+            indices: Vec::with_capacity(num_s),
+        }
+    }
+
+    /// Forget accumulated pooling activity and the input union.
+    ///
+    /// Call between sequences so the next one starts from a clean slate.
+    /// Learned memory and output history are kept.
+    pub fn reset(&mut self) {
+        self.pooling.fill(0.0);
+        self.trace.fill(0.0);
+        self.union.clear_all();
+    }
+
+    /// Get the current input union (recently driven input bits).
+    pub fn union(&self) -> &BitField {
+        &self.union
+    }
+
+    /// Get pooling activation per statelet.
+    pub fn pooling(&self) -> &[f64] {
+        &self.pooling
+    }
+
+    /// Get the per-step decay.
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Get number of statelets.
+    pub fn num_s(&self) -> usize {
+        self.num_s
+    }
+
+    /// Get number of active statelets.
+    pub fn num_as(&self) -> usize {
+        self.num_as
+    }
+
+    /// Get permanence threshold.
+    pub fn perm_thr(&self) -> u8 {
+        self.perm_thr
+    }
+}
+
+impl Block for TemporalPooler {
+    fn init(&mut self) -> Result<()> {
+        let num_input_bits = self.input.num_bits();
+
+        if self.context.num_children() > 0 && self.context.num_bits() != num_input_bits {
+            return Err(crate::GnomicsError::InvalidInputSize {
+                expected: num_input_bits,
+                actual: self.context.num_bits(),
+            });
+        }
+
+        self.memory.init_pooled_conn(
+            num_input_bits,
+            self.base.rng(),
+            self.pct_pool,
+            self.pct_conn,
+        );
+        self.memory.init_index();
+
+        self.trace = vec![0.0; num_input_bits];
+        self.union = BitField::new(num_input_bits);
+        self.drive = BitField::new(num_input_bits);
+
+        self.base.set_initialized(true);
+        Ok(())
+    }
+
+    fn save(&self, _path: &Path) -> Result<()> {
+        // TODO: Implement save
+        Ok(())
+    }
+
+    fn load(&mut self, _path: &Path) -> Result<()> {
+        // TODO: Implement load
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.input.clear();
+        self.context.clear();
+        self.output.borrow_mut().clear();
+        self.memory.clear();
+        self.reset();
+        self.drive.clear_all();
+    }
+
+    fn step(&mut self) {
+        self.output.borrow_mut().step();
+    }
+
+    fn pull(&mut self) {
+        self.input.pull();
+        self.context.pull();
+    }
+
+    fn compute(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "TemporalPooler must be initialized before encoding"
+        );
+
+        // Predicted-active input: mask by context when one is connected
+        {
+            let drive = self.drive.words_mut();
+            drive.copy_from_slice(self.input.state.words());
+            if self.context.num_children() > 0 {
+                for (d, &c) in drive.iter_mut().zip(self.context.state.words()) {
+                    *d &= c;
+                }
+            }
+        }
+
+        // Decay input traces and refresh driven bits
+        self.trace.iter_mut().for_each(|t| *t *= self.decay);
+        for i in self.drive.get_acts() {
+            self.trace[i] = 1.0;
+        }
+        self.union.clear_all();
+        for (i, &t) in self.trace.iter().enumerate() {
+            if t >= UNION_MIN {
+                self.union.set_bit(i);
+            }
+        }
+
+        // Accumulate pooling activation. Output still holds last step's winners,
+        // which keep their activation undecayed while they are still driven.
+        // The cap bounds held winners at the steady state of full overlap.
+        let max_pooling = self.memory.num_rpd() as f64 / (1.0 - self.decay);
+        self.memory.overlap_all(&self.drive, &mut self.overlaps);
+        {
+            let prev = &self.output.borrow().state;
+            for (s, (p, &o)) in self.pooling.iter_mut().zip(&self.overlaps).enumerate() {
+                let held = o > 0 && prev.get_bit(s) > 0;
+                let decay = if held { 1.0 } else { self.decay };
+                *p = (decay * *p + o as f64).min(max_pooling);
+            }
+        }
+
+        // Activate top num_as statelets (descending activation, ties by index)
+        let pooling = &self.pooling;
+        self.indices.clear();
+        self.indices.extend(0..self.num_s);
+        self.indices.sort_unstable_by(|&a, &b| {
+            pooling[b].partial_cmp(&pooling[a]).unwrap().then(a.cmp(&b))
+        });
+
+        let mut output = self.output.borrow_mut();
+        output.state.clear_all();
+        for &s in self.indices.iter().take(self.num_as) {
+            if self.pooling[s] <= 0.0 {
+                break;
+            }
+            output.state.set_bit(s);
+        }
+    }
+
+    fn learn(&mut self) {
+        assert!(
+            self.base.is_initialized(),
+            "TemporalPooler must be initialized before learning"
+        );
+
+        // Winners learn the whole recent union, not just this step's input
+        let winners = self.output.borrow().state.get_acts();
+        for d in winners {
+            self.memory.learn_conn(d, &self.union, self.base.rng());
+        }
+    }
+
+    fn store(&mut self) {
+        self.output.borrow_mut().store();
+    }
+
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + (self.pooling.capacity() + self.trace.capacity()) * std::mem::size_of::<f64>()
+            + self.overlaps.capacity() * std::mem::size_of::<usize>()
+            + self.union.memory_usage()
+            + self.drive.memory_usage()
+            + self.input.memory_usage()
+            + self.context.memory_usage()
+            + self.output.borrow().memory_usage()
+            + self.memory.memory_usage()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn get_dependencies(&self) -> Vec<crate::network::BlockId> {
+        let mut deps = self.input.get_source_blocks();
+        deps.extend(self.context.get_source_blocks());
+        deps
+    }
+}
+
+impl BlockBaseAccess for TemporalPooler {
+    fn base(&self) -> &BlockBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BlockBase {
+        &mut self.base
+    }
+}
+
+impl InputAccess for TemporalPooler {
+    fn input(&self) -> &BlockInput {
+        &self.input
+    }
+
+    fn input_mut(&mut self) -> &mut BlockInput {
+        &mut self.input
+    }
+}
+
+impl ContextAccess for TemporalPooler {
+    fn context(&self) -> &BlockInput {
+        &self.context
+    }
+
+    fn context_mut(&mut self) -> &mut BlockInput {
+        &mut self.context
+    }
+}
+
+impl MemoryAccess for TemporalPooler {
+    fn memory(&self) -> &BlockMemory {
+        &self.memory
+    }
+
+    fn memory_mut(&mut self) -> &mut BlockMemory {
+        &mut self.memory
+    }
+}
+
+impl OutputAccess for TemporalPooler {
+    fn output(&self) -> Rc<RefCell<BlockOutput>> {
+        Rc::clone(&self.output)
+    }
+}
+
+impl crate::network_config::BlockConfigurable for TemporalPooler {
+    fn to_config(&self) -> crate::network_config::BlockConfig {
+        crate::network_config::BlockConfig::TemporalPooler {
+            num_s: self.num_s,
+            num_as: self.num_as,
+            perm_thr: self.perm_thr,
+            perm_inc: self.perm_inc,
+            perm_dec: self.perm_dec,
+            pct_pool: self.pct_pool,
+            pct_conn: self.pct_conn,
+            pct_learn: self.pct_learn,
+            decay: self.decay,
+            num_t: self.num_t,
+            seed: self.base().seed(),
+        }
+    }
+
+    fn block_type_name(&self) -> &'static str {
+        "TemporalPooler"
+    }
+}
+
+impl crate::network_config::BlockStateful for TemporalPooler {
+    fn to_state(&self) -> crate::Result<crate::network_config::BlockState> {
+        let permanences = self.memory.get_all_permanences();
+        Ok(crate::network_config::BlockState::TemporalPooler { permanences })
+    }
+
+    fn from_state(&mut self, state: &crate::network_config::BlockState) -> crate::Result<()> {
+        if let crate::network_config::BlockState::TemporalPooler { permanences } = state {
+            self.memory.set_all_permanences(permanences)?;
+            Ok(())
+        } else {
+            Err(crate::GnomicsError::Other(
+                "Wrong state type for TemporalPooler".into(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::test_support::{self, with_source};
+
+    fn pooler_with_input(num_i: usize, decay: f64) -> (TemporalPooler, Rc<RefCell<BlockOutput>>) {
+        with_source(
+            TemporalPooler::new(128, 8, 20, 2, 1, 0.8, 0.5, 0.3, decay, 2, 0),
+            num_i,
+        )
+    }
+
+    fn feed(tp: &mut TemporalPooler, source: &Rc<RefCell<BlockOutput>>, acts: &[usize]) {
+        test_support::feed(source, acts.iter().copied());
+        tp.pull();
+        tp.compute();
+    }
+
+    #[test]
+    fn test_new() {
+        let tp = TemporalPooler::new(256, 16, 20, 2, 1, 0.8, 0.5, 0.3, 0.5, 2, 0);
+        assert_eq!(tp.num_s(), 256);
+        assert_eq!(tp.num_as(), 16);
+        assert_eq!(tp.decay(), 0.5);
+        assert_eq!(tp.perm_thr(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "decay must be in [0.0, 1.0)")]
+    fn test_invalid_decay() {
+        TemporalPooler::new(256, 16, 20, 2, 1, 0.8, 0.5, 0.3, 1.0, 2, 0);
+    }
+
+    #[test]
+    fn test_union_decays() {
+        let (mut tp, source) = pooler_with_input(64, 0.5);
+        feed(&mut tp, &source, &[1, 2]);
+        feed(&mut tp, &source, &[10]);
+        assert_eq!(tp.union().get_acts(), vec![1, 2, 10]);
+
+        // 0.5^4 = 0.0625 drops the first bits out of the union
+        for _ in 0..3 {
+            feed(&mut tp, &source, &[10]);
+        }
+        assert_eq!(tp.union().get_acts(), vec![10]);
+    }
+
+    #[test]
+    fn test_zero_decay_is_memoryless() {
+        let (mut tp, source) = pooler_with_input(64, 0.0);
+        feed(&mut tp, &source, &[1, 2, 3]);
+        feed(&mut tp, &source, &[40]);
+        assert_eq!(tp.union().get_acts(), vec![40]);
+    }
+
+    #[test]
+    fn test_pooling_stays_bounded() {
+        let (mut tp, source) = pooler_with_input(64, 0.9);
+        let acts: Vec<usize> = (0..32).collect();
+        for _ in 0..400 {
+            feed(&mut tp, &source, &acts);
+        }
+
+        // Held winners saturate instead of accumulating every step
+        let max_pooling = tp.memory.num_rpd() as f64 / (1.0 - 0.9);
+        assert!(tp.pooling().iter().all(|&p| p <= max_pooling));
+        assert!(tp.pooling().contains(&max_pooling));
+    }
+
+    #[test]
+    fn test_wide_input_winners_follow_overlap() {
+        // num_rpd = 2048 * 0.8, far above the block's initial receptor count
+        let (mut tp, source) = pooler_with_input(2048, 0.9);
+        let acts: Vec<usize> = (0..2048).step_by(2).collect();
+        for _ in 0..30 {
+            feed(&mut tp, &source, &acts);
+        }
+
+        let mut overlaps = vec![0; tp.num_s()];
+        tp.memory.overlap_all(&tp.drive, &mut overlaps);
+        let winners = tp.output().borrow().state.clone();
+        let min_winner = (0..tp.num_s())
+            .filter(|&s| winners.get_bit(s) > 0)
+            .map(|s| overlaps[s])
+            .min()
+            .unwrap();
+        let max_loser = (0..tp.num_s())
+            .filter(|&s| winners.get_bit(s) == 0)
+            .map(|s| overlaps[s])
+            .max()
+            .unwrap();
+
+        assert_eq!(winners.num_set(), tp.num_as());
+        assert!(min_winner >= max_loser);
+        assert!(tp.pooling().iter().any(|&p| p != tp.pooling()[0]));
+    }
+
+    #[test]
+    fn test_reset_clears_activity() {
+        let (mut tp, source) = pooler_with_input(64, 0.9);
+        feed(&mut tp, &source, &(0..32).collect::<Vec<_>>());
+        assert!(tp.pooling().iter().any(|&p| p > 0.0));
+
+        tp.reset();
+        assert!(tp.pooling().iter().all(|&p| p == 0.0));
+        assert_eq!(tp.union().num_set(), 0);
+    }
+}
@@ -0,0 +1,33 @@
+//! Shared fixtures for the blocks' unit tests.
+//!
+//! Blocks under test read from a bare `BlockOutput` standing in for an
+//! upstream block, so tests control the input bits directly.
+
+use crate::{Block, BlockOutput, InputAccess};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Connect a `num_i`-bit source to `block`'s input and initialize the block.
+pub fn with_source<B: Block + InputAccess>(
+    mut block: B,
+    num_i: usize,
+) -> (B, Rc<RefCell<BlockOutput>>) {
+    let source = Rc::new(RefCell::new(BlockOutput::new()));
+    source.borrow_mut().setup(2, num_i);
+    block.input_mut().add_child(Rc::clone(&source), 0);
+    block.init().unwrap();
+    (block, source)
+}
+
+/// Advance `source` one step with exactly the `acts` bits active.
+///
+/// Accepts arrays as well as ranges of bit indices.
+pub fn feed(source: &Rc<RefCell<BlockOutput>>, acts: impl IntoIterator<Item = usize>) {
+    let mut s = source.borrow_mut();
+    s.step();
+    s.state.clear_all();
+    for i in acts {
+        s.state.set_bit(i);
+    }
+    s.store();
+}
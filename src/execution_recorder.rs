@@ -50,6 +50,7 @@ pub struct BlockConnection {
 pub enum ConnectionType {
     Input,
     Context,
+    Apical,
 }
 
 /// Single timestep in the execution trace
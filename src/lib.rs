@@ -18,6 +18,7 @@
 //! The framework is built around several core components:
 //!
 //! - **BitField**: High-performance bit manipulation using 32-bit words
+//! - **SparseBitField**: Sorted index-list representation for very sparse patterns
 //! - **Block System**: Computational units with lifecycle management
 //! - **Learning Blocks**: Pattern pooling, classification, and temporal learning
 //! - **Transformers**: Encoding continuous/discrete values into binary patterns
@@ -64,7 +65,7 @@
 //!
 //! Gnomics is designed for high performance:
 //!
-//! - BitField operations use hardware popcount instructions
+//! - BitField operations use SIMD kernels (SSE2/AVX2/AVX-512) with runtime detection
 //! - Word-level copying for efficient data movement
 //! - Inline-optimized hot paths
 //! - Zero-cost abstractions with Rust's type system
@@ -96,7 +97,9 @@
 
 // Module declarations
 pub mod bitfield;
+pub mod sparse_bitfield;
 pub mod error;
+pub mod simd;
 pub mod utils;
 
 // Phase 2: Block Infrastructure
@@ -122,26 +125,31 @@ pub mod wasm_interface;
 
 // Re-exports for convenient access
 pub use bitfield::{bitfield_copy_words, BitField, Word, BITS_PER_WORD};
+pub use sparse_bitfield::{bitfield_copy_sparse, SparseBitField};
 pub use error::{GnomicsError, Result};
 
 // Phase 2 re-exports
 pub use block::Block;
 pub use block_base::{BlockBase, BlockBaseAccess};
-pub use block_input::{BlockInput, ChildInfo, ContextAccess, InputAccess};
+pub use block_input::{ApicalAccess, BlockInput, ChildInfo, ContextAccess, InputAccess};
 pub use block_output::{BlockOutput, OutputAccess, CURR, PREV};
 pub use block_memory::{BlockMemory, MemoryAccess, PERM_MAX, PERM_MIN};
 
 // Phase 3+4+5 re-exports
 pub use blocks::{
-    ContextLearner, DiscreteTransformer, PatternClassifier, PatternPooler,
-    PersistenceTransformer, ScalarTransformer, SequenceLearner,
+    ActionLearner, AnomalyExplanation, BitFieldSource, ContextLearner, CyclicTransformer,
+    DateTimeFields, DateTimeTransformer, DelayLine, DeltaTransformer, DendriteMatch,
+    DiscreteTransformer, HyperGridTransformer, ImageTransformer, MultiLabelMetrics,
+    PatternClassifier, PatternClusterer, PatternPooler, PersistenceTransformer, PoolerTopology,
+    RdseTransformer, ScalarTransformer, SdrClassifier, SequenceLearner, SymbolTransformer,
+    TemporalPooler,
 };
 
 // Network re-exports
 pub use network::{ConnectionBuilder, Network};
 pub use network_config::{
     BlockConfig, BlockConfigurable, BlockInfo, BlockState, BlockStateful, ConnectionConfig,
-    InputType, NetworkConfig,
+    InputType, NetworkConfig, OutputPort,
 };
 
 // Execution recording re-exports
@@ -177,6 +185,6 @@ mod tests {
         // Verify re-exports are accessible
         let _ba = BitField::new(32);
         let _result: Result<()> = Ok(());
-        assert_eq!(BITS_PER_WORD, 32);
+        assert_eq!(BITS_PER_WORD, 8 * std::mem::size_of::<Word>());
     }
 }
@@ -41,13 +41,19 @@
 //! # }
 //! ```
 
-use crate::{Block, ContextAccess, GnomicsError, InputAccess, OutputAccess, Result};
+use crate::network_config::OutputPort;
+use crate::{
+    ApicalAccess, Block, BlockOutput, ContextAccess, GnomicsError, InputAccess, OutputAccess,
+    Result,
+};
 use crate::execution_recorder::{
     BlockConnection, BlockMetadata, ConnectionType, ExecutionRecorder, ExecutionTrace,
     BitFieldSnapshot,
 };
 use std::any::Any;
+use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
 
 /// Unique identifier for a block in a Network.
 ///
@@ -413,6 +419,65 @@ impl Network {
         self.blocks.keys().copied()
     }
 
+    /// Switch a block's output history between dense and sparse storage.
+    ///
+    /// See `BlockOutput::set_sparse()`. The setting is kept in `to_config()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the block is not found.
+    pub fn set_sparse_output(&mut self, id: BlockId, sparse: bool) -> Result<()> {
+        self.source_output(id)?.borrow_mut().set_sparse(sparse);
+        Ok(())
+    }
+
+    /// Explain a SequenceLearner's last anomaly score.
+    ///
+    /// Same as `SequenceLearner::explain_anomaly()`, plus `expected_range`
+    /// when the learner's only input is a ScalarTransformer,
+    /// CyclicTransformer or DiscreteTransformer, whose statelets map
+    /// one-to-one to its columns. A cyclic range that crosses the period
+    /// boundary has `lo > hi`.
+    /// Expected columns need the learner's predictions enabled
+    /// (`SequenceLearner::set_predicted_enabled()`).
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// net.execute(true)?;
+    /// if net.get::<SequenceLearner>(learner)?.get_anomaly_score() > 0.5 {
+    ///     let explanation = net.explain_anomaly(learner)?;
+    ///     println!("surprised: {:?}", explanation.surprised_columns);
+    ///     println!("expected values: {:?}", explanation.expected_range);
+    /// }
+    /// ```
+    pub fn explain_anomaly(&self, learner: BlockId) -> Result<crate::blocks::AnomalyExplanation> {
+        let learner = self.get::<crate::blocks::SequenceLearner>(learner)?;
+        let mut explanation = learner.explain_anomaly();
+
+        if learner.input().num_children() != 1 {
+            return Ok(explanation);
+        }
+
+        // Decode the expected columns through the upstream transformer
+        let expected = &explanation.expected_columns;
+        if let [(source, OutputPort::Active)] = learner.input().get_source_ports()[..] {
+            if let Ok(t) = self.get::<crate::blocks::ScalarTransformer>(source) {
+                explanation.expected_range = t.decode_range(expected);
+            } else if let Ok(t) = self.get::<crate::blocks::CyclicTransformer>(source) {
+                explanation.expected_range = t.decode_range(expected);
+            } else if let Ok(t) = self.get::<crate::blocks::DiscreteTransformer>(source) {
+                let values = t.decode_values(expected);
+                explanation.expected_range = values
+                    .first()
+                    .zip(values.last())
+                    .map(|(&lo, &hi)| (lo as f64, hi as f64));
+            }
+        }
+
+        Ok(explanation)
+    }
+
     /// Connect source block's output to target block's input.
     ///
     /// This is a simplified API that replaces the verbose pattern of getting
@@ -449,7 +514,7 @@ impl Network {
 
     /// Connect source block's output to target block's context input.
     ///
-    /// Only ContextLearner and SequenceLearner blocks have context inputs.
+    /// Only ContextLearner, SequenceLearner and TemporalPooler blocks have context inputs.
     ///
     /// # Arguments
     ///
@@ -470,6 +535,53 @@ impl Network {
         self.connect_to_context_with_offset(source, target, 0)
     }
 
+    /// Connect source block's output to target block's apical (top-down feedback) input.
+    ///
+    /// Only ContextLearner blocks have apical inputs. Apical sources are not
+    /// execution dependencies, so the source may be a higher-level block that
+    /// runs after the target; the target then sees its previous-step output.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block (must have output)
+    /// * `target` - BlockId of the target block (must have apical input)
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let learner = net.add(ContextLearner::new(512, 4, 8, 32, 20, 20, 2, 1, 2, false, 0));
+    /// let pooler = net.add(TemporalPooler::new(1024, 40, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0));
+    ///
+    /// net.connect_to_input(learner, pooler)?;
+    /// net.connect_to_apical(pooler, learner)?;
+    /// ```
+    pub fn connect_to_apical(&mut self, source: BlockId, target: BlockId) -> Result<()> {
+        self.connect_to_apical_with_offset(source, target, 0)
+    }
+
+    /// Connect source block's predicted output to target block's input.
+    ///
+    /// Only ContextLearner and SequenceLearner blocks have predicted outputs
+    /// (statelets whose dendrites cross the dendrite threshold).
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block (must have predicted output)
+    /// * `target` - BlockId of the target block (must have input)
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let learner = net.add(SequenceLearner::new(512, 4, 8, 32, 20, 20, 2, 1, 2, false, 0));
+    /// let classifier = net.add(PatternClassifier::new(4, 2048, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0));
+    ///
+    /// // Classify the statelets expected on the next step
+    /// net.connect_predicted_to_input(learner, classifier)?;
+    /// ```
+    pub fn connect_predicted_to_input(&mut self, source: BlockId, target: BlockId) -> Result<()> {
+        self.connect_to_input_with_port(source, target, OutputPort::Predicted, 0)
+    }
+
     /// Connect source block's output to target block's input with explicit offset.
     ///
     /// The offset parameter is used for advanced scenarios where you need to control
@@ -487,41 +599,141 @@ impl Network {
         target: BlockId,
         offset: usize,
     ) -> Result<()> {
-        // Step 1: Get source output
+        let source_output = self.source_output(source)?;
+        self.add_input_child(target, source_output, offset)
+    }
+
+    /// Connect one of the source block's outputs to target block's input.
+    ///
+    /// `OutputPort::Active` is the same as `connect_to_input_with_offset()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block
+    /// * `target` - BlockId of the target block (must have input)
+    /// * `port` - Which output of the source to read
+    /// * `offset` - Bit offset for add_child (typically 0)
+    pub fn connect_to_input_with_port(
+        &mut self,
+        source: BlockId,
+        target: BlockId,
+        port: OutputPort,
+        offset: usize,
+    ) -> Result<()> {
+        let source_output = self.port_output(source, port)?;
+        self.add_input_child(target, source_output, offset)
+    }
+
+    /// Get the main output of a source block.
+    fn source_output(&self, source: BlockId) -> Result<Rc<RefCell<BlockOutput>>> {
         let source_wrapper = self.blocks.get(&source).ok_or_else(|| {
             GnomicsError::Other(format!("Source block {} not found", source.as_usize()))
         })?;
 
-        let source_output = {
-            let block_any = source_wrapper.as_any();
-
-            // Try each block type that has OutputAccess
-            if let Some(b) = block_any.downcast_ref::<crate::blocks::ScalarTransformer>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DiscreteTransformer>()
-            {
-                b.output()
-            } else if let Some(b) =
-                block_any.downcast_ref::<crate::blocks::PersistenceTransformer>()
-            {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
-                b.output()
-            } else {
-                return Err(GnomicsError::Other(format!(
-                    "Source block {} does not have output",
-                    source.as_usize()
-                )));
-            }
+        let block_any = source_wrapper.as_any();
+
+        // Try each block type that has OutputAccess
+        let output = if let Some(b) = block_any.downcast_ref::<crate::blocks::ScalarTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DiscreteTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PersistenceTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DeltaTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::RdseTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::CyclicTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DateTimeTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::HyperGridTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SymbolTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ImageTransformer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::BitFieldSource>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
+            b.output()
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+            b.output()
+        } else {
+            return Err(GnomicsError::Other(format!(
+                "Source block {} does not have output",
+                source.as_usize()
+            )));
         };
 
-        // Step 2: Get target and add connection
+        Ok(output)
+    }
+
+    /// Get the given output port of a source block.
+    fn port_output(
+        &mut self,
+        source: BlockId,
+        port: OutputPort,
+    ) -> Result<Rc<RefCell<BlockOutput>>> {
+        match port {
+            OutputPort::Active => self.source_output(source),
+            OutputPort::Predicted => self.predicted_output(source),
+        }
+    }
+
+    /// Get the predicted output of a source block.
+    ///
+    /// Enables the source's predictions, which are only computed once they
+    /// have a consumer, and tags the output with the source's BlockId so
+    /// connections to it are discovered like those to the main output.
+    fn predicted_output(&mut self, source: BlockId) -> Result<Rc<RefCell<BlockOutput>>> {
+        let source_wrapper = self.blocks.get_mut(&source).ok_or_else(|| {
+            GnomicsError::Other(format!("Source block {} not found", source.as_usize()))
+        })?;
+
+        let block_any_mut = source_wrapper.as_any_mut();
+
+        // Only ContextLearner and SequenceLearner predict statelets
+        let output = if let Some(b) =
+            block_any_mut.downcast_mut::<crate::blocks::ContextLearner>()
+        {
+            b.set_predicted_enabled(true);
+            b.predicted()
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::SequenceLearner>() {
+            b.set_predicted_enabled(true);
+            b.predicted()
+        } else {
+            return Err(GnomicsError::Other(format!(
+                "Source block {} does not have predicted output",
+                source.as_usize()
+            )));
+        };
+
+        output.borrow_mut().set_source_block_id(source);
+        Ok(output)
+    }
+
+    /// Add an output to the target block's input (any block with InputAccess).
+    fn add_input_child(
+        &mut self,
+        target: BlockId,
+        output: Rc<RefCell<BlockOutput>>,
+        offset: usize,
+    ) -> Result<()> {
         let target_wrapper = self.blocks.get_mut(&target).ok_or_else(|| {
             GnomicsError::Other(format!("Target block {} not found", target.as_usize()))
         })?;
@@ -530,13 +742,23 @@ impl Network {
 
         // Try each block type that has InputAccess
         if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::PatternPooler>() {
-            b.input_mut().add_child(source_output, offset);
+            b.input_mut().add_child(output, offset);
         } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::PatternClassifier>() {
-            b.input_mut().add_child(source_output, offset);
+            b.input_mut().add_child(output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::PatternClusterer>() {
+            b.input_mut().add_child(output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::SdrClassifier>() {
+            b.input_mut().add_child(output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::DelayLine>() {
+            b.input_mut().add_child(output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::ActionLearner>() {
+            b.input_mut().add_child(output, offset);
         } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::ContextLearner>() {
-            b.input_mut().add_child(source_output, offset);
+            b.input_mut().add_child(output, offset);
         } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::SequenceLearner>() {
-            b.input_mut().add_child(source_output, offset);
+            b.input_mut().add_child(output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::TemporalPooler>() {
+            b.input_mut().add_child(output, offset);
         } else {
             return Err(GnomicsError::Other(format!(
                 "Target block {} does not have input",
@@ -549,7 +771,7 @@ impl Network {
 
     /// Connect source block's output to target block's context input with explicit offset.
     ///
-    /// Only ContextLearner and SequenceLearner blocks have context inputs.
+    /// Only ContextLearner, SequenceLearner and TemporalPooler blocks have context inputs.
     ///
     /// # Arguments
     ///
@@ -562,52 +784,42 @@ impl Network {
         target: BlockId,
         offset: usize,
     ) -> Result<()> {
-        // Step 1: Get source output
-        let source_wrapper = self.blocks.get(&source).ok_or_else(|| {
-            GnomicsError::Other(format!("Source block {} not found", source.as_usize()))
-        })?;
+        self.connect_to_context_with_port(source, target, OutputPort::Active, offset)
+    }
 
-        let source_output = {
-            let block_any = source_wrapper.as_any();
-
-            // Try each block type that has OutputAccess
-            if let Some(b) = block_any.downcast_ref::<crate::blocks::ScalarTransformer>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DiscreteTransformer>()
-            {
-                b.output()
-            } else if let Some(b) =
-                block_any.downcast_ref::<crate::blocks::PersistenceTransformer>()
-            {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
-                b.output()
-            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
-                b.output()
-            } else {
-                return Err(GnomicsError::Other(format!(
-                    "Source block {} does not have output",
-                    source.as_usize()
-                )));
-            }
-        };
+    /// Connect one of the source block's outputs to target block's context input.
+    ///
+    /// `OutputPort::Active` is the same as `connect_to_context_with_offset()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block
+    /// * `target` - BlockId of the target block (must have context input)
+    /// * `port` - Which output of the source to read
+    /// * `offset` - Bit offset for add_child (typically 0)
+    pub fn connect_to_context_with_port(
+        &mut self,
+        source: BlockId,
+        target: BlockId,
+        port: OutputPort,
+        offset: usize,
+    ) -> Result<()> {
+        let source_output = self.port_output(source, port)?;
 
-        // Step 2: Get target and add to CONTEXT (only ContextLearner and SequenceLearner)
+        // Add to CONTEXT (only ContextLearner, SequenceLearner and TemporalPooler)
         let target_wrapper = self.blocks.get_mut(&target).ok_or_else(|| {
             GnomicsError::Other(format!("Target block {} not found", target.as_usize()))
         })?;
 
         let block_any_mut = target_wrapper.as_any_mut();
 
-        // Only ContextLearner and SequenceLearner have context
+        // Only ContextLearner, SequenceLearner and TemporalPooler have context
         if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::ContextLearner>() {
             b.context_mut().add_child(source_output, offset);
         } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::SequenceLearner>() {
             b.context_mut().add_child(source_output, offset);
+        } else if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::TemporalPooler>() {
+            b.context_mut().add_child(source_output, offset);
         } else {
             return Err(GnomicsError::Other(format!(
                 "Target block {} does not have context input",
@@ -618,6 +830,62 @@ impl Network {
         Ok(())
     }
 
+    /// Connect source block's output to target block's apical input with explicit offset.
+    ///
+    /// Only ContextLearner blocks have apical inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block (must have output)
+    /// * `target` - BlockId of the target block (must have apical input)
+    /// * `offset` - Bit offset for add_child (typically 0)
+    pub fn connect_to_apical_with_offset(
+        &mut self,
+        source: BlockId,
+        target: BlockId,
+        offset: usize,
+    ) -> Result<()> {
+        self.connect_to_apical_with_port(source, target, OutputPort::Active, offset)
+    }
+
+    /// Connect one of the source block's outputs to target block's apical input.
+    ///
+    /// `OutputPort::Active` is the same as `connect_to_apical_with_offset()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - BlockId of the source block
+    /// * `target` - BlockId of the target block (must have apical input)
+    /// * `port` - Which output of the source to read
+    /// * `offset` - Bit offset for add_child (typically 0)
+    pub fn connect_to_apical_with_port(
+        &mut self,
+        source: BlockId,
+        target: BlockId,
+        port: OutputPort,
+        offset: usize,
+    ) -> Result<()> {
+        let source_output = self.port_output(source, port)?;
+
+        // Add to APICAL (only ContextLearner)
+        let target_wrapper = self.blocks.get_mut(&target).ok_or_else(|| {
+            GnomicsError::Other(format!("Target block {} not found", target.as_usize()))
+        })?;
+
+        let block_any_mut = target_wrapper.as_any_mut();
+
+        if let Some(b) = block_any_mut.downcast_mut::<crate::blocks::ContextLearner>() {
+            b.apical_mut().add_child(source_output, offset);
+        } else {
+            return Err(GnomicsError::Other(format!(
+                "Target block {} does not have apical input",
+                target.as_usize()
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Connect multiple sources to a single target's input.
     ///
     /// Convenience method for connecting multiple encoder outputs to a single
@@ -1056,6 +1324,50 @@ impl Network {
                         });
                     }
                 }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+                for child in b.input().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Input,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+                for child in b.input().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Input,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+                for child in b.input().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Input,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+                for child in b.input().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Input,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
                 // Input connections
                 for child in b.input().get_children() {
@@ -1079,6 +1391,17 @@ impl Network {
                         });
                     }
                 }
+                // Apical connections
+                for child in b.apical().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Apical,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
                 // Input connections
                 for child in b.input().get_children() {
@@ -1102,6 +1425,29 @@ impl Network {
                         });
                     }
                 }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+                // Input connections
+                for child in b.input().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Input,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
+                // Context connections
+                for child in b.context().get_children() {
+                    if let Some(source_id) = child.output.borrow().source_block_id() {
+                        connections.push(BlockConnection {
+                            source_id,
+                            target_id,
+                            connection_type: ConnectionType::Context,
+                            time_offset: child.time_offset,
+                        });
+                    }
+                }
             }
         }
 
@@ -1134,6 +1480,54 @@ impl Network {
                 b.output().borrow().state.num_bits(),
                 b.output().borrow().state.num_set(),
             )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DeltaTransformer>() {
+            (
+                "DeltaTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::RdseTransformer>() {
+            (
+                "RdseTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::CyclicTransformer>() {
+            (
+                "CyclicTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DateTimeTransformer>() {
+            (
+                "DateTimeTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::HyperGridTransformer>() {
+            (
+                "HyperGridTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SymbolTransformer>() {
+            (
+                "SymbolTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ImageTransformer>() {
+            (
+                "ImageTransformer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::BitFieldSource>() {
+            (
+                "BitFieldSource",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
         } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
             (
                 "PatternPooler",
@@ -1146,6 +1540,30 @@ impl Network {
                 b.output().borrow().state.num_bits(),
                 b.output().borrow().state.num_set(),
             )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+            (
+                "PatternClusterer",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+            (
+                "SdrClassifier",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+            (
+                "DelayLine",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+            (
+                "ActionLearner",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
         } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
             (
                 "ContextLearner",
@@ -1158,6 +1576,12 @@ impl Network {
                 b.output().borrow().state.num_bits(),
                 b.output().borrow().state.num_set(),
             )
+        } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+            (
+                "TemporalPooler",
+                b.output().borrow().state.num_bits(),
+                b.output().borrow().state.num_set(),
+            )
         } else {
             return None;
         };
@@ -1207,6 +1631,40 @@ impl Network {
                     Some(BitFieldSnapshot::from_bitfield(
                         &b.output().borrow().state,
                     ))
+                } else if let Some(b) =
+                    block_any.downcast_ref::<crate::blocks::DeltaTransformer>()
+                {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::RdseTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::CyclicTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DateTimeTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::HyperGridTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SymbolTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ImageTransformer>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::BitFieldSource>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
                 } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>()
                 {
                     Some(BitFieldSnapshot::from_bitfield(
@@ -1218,6 +1676,24 @@ impl Network {
                     Some(BitFieldSnapshot::from_bitfield(
                         &b.output().borrow().state,
                     ))
+                } else if let Some(b) =
+                    block_any.downcast_ref::<crate::blocks::PatternClusterer>()
+                {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
                 } else if let Some(b) =
                     block_any.downcast_ref::<crate::blocks::ContextLearner>()
                 {
@@ -1230,6 +1706,10 @@ impl Network {
                     Some(BitFieldSnapshot::from_bitfield(
                         &b.output().borrow().state,
                     ))
+                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+                    Some(BitFieldSnapshot::from_bitfield(
+                        &b.output().borrow().state,
+                    ))
                 } else {
                     None
                 };
@@ -1273,7 +1753,7 @@ impl Network {
     /// ```
     pub fn to_config(&self) -> Result<crate::network_config::NetworkConfig> {
         use crate::network_config::{BlockConfigurable, ConnectionConfig, InputType, NetworkConfig};
-        use crate::{InputAccess, ContextAccess};
+        use crate::{ApicalAccess, InputAccess, ContextAccess};
 
         // Create ordered list of block IDs for consistent indexing
         let mut block_ids: Vec<BlockId> = self.blocks.keys().copied().collect();
@@ -1288,6 +1768,7 @@ impl Network {
 
         // Extract block configurations
         let mut block_configs = Vec::new();
+        let mut sparse_outputs = Vec::new();
         for &block_id in &block_ids {
             let wrapper = self.blocks.get(&block_id).unwrap();
             let block = wrapper.block();
@@ -1302,19 +1783,46 @@ impl Network {
                 b.to_config()
             } else if let Some(b) = config_any.downcast_ref::<crate::blocks::PersistenceTransformer>() {
                 b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::DeltaTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::RdseTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::CyclicTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::DateTimeTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::HyperGridTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::SymbolTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::ImageTransformer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::BitFieldSource>() {
+                b.to_config()
             } else if let Some(b) = config_any.downcast_ref::<crate::blocks::PatternPooler>() {
                 b.to_config()
             } else if let Some(b) = config_any.downcast_ref::<crate::blocks::PatternClassifier>() {
                 b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::DelayLine>() {
+                b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::ActionLearner>() {
+                b.to_config()
             } else if let Some(b) = config_any.downcast_ref::<crate::blocks::ContextLearner>() {
                 b.to_config()
             } else if let Some(b) = config_any.downcast_ref::<crate::blocks::SequenceLearner>() {
                 b.to_config()
+            } else if let Some(b) = config_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+                b.to_config()
             } else {
                 return Err(GnomicsError::Other("Unknown block type for serialization".into()));
             };
 
             block_configs.push(config);
+            sparse_outputs.push(self.source_output(block_id)?.borrow().is_sparse());
         }
 
         // Extract connections by examining block inputs
@@ -1325,72 +1833,173 @@ impl Network {
 
             // Check if block has InputAccess trait
             if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
-                let sources = b.input().get_source_blocks();
-                for source_id in sources {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
                     if let Some(&source_idx) = id_to_index.get(&source_id) {
                         connections.push(ConnectionConfig {
                             source_block: source_idx,
                             target_block: target_idx,
                             input_type: InputType::Input,
                             offset: 0,
+                            source_port,
                         });
                     }
                 }
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
-                let sources = b.input().get_source_blocks();
-                for source_id in sources {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Input,
+                            offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Input,
+                            offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Input,
+                            offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
                     if let Some(&source_idx) = id_to_index.get(&source_id) {
                         connections.push(ConnectionConfig {
                             source_block: source_idx,
                             target_block: target_idx,
                             input_type: InputType::Input,
                             offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Input,
+                            offset: 0,
+                            source_port,
                         });
                     }
                 }
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
                 // Input connections
-                let sources = b.input().get_source_blocks();
-                for source_id in sources {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
                     if let Some(&source_idx) = id_to_index.get(&source_id) {
                         connections.push(ConnectionConfig {
                             source_block: source_idx,
                             target_block: target_idx,
                             input_type: InputType::Input,
                             offset: 0,
+                            source_port,
                         });
                     }
                 }
                 // Context connections
-                let ctx_sources = b.context().get_source_blocks();
-                for source_id in ctx_sources {
+                let ctx_sources = b.context().get_source_ports();
+                for (source_id, source_port) in ctx_sources {
                     if let Some(&source_idx) = id_to_index.get(&source_id) {
                         connections.push(ConnectionConfig {
                             source_block: source_idx,
                             target_block: target_idx,
                             input_type: InputType::Context,
                             offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+                // Apical connections
+                let apical_sources = b.apical().get_source_ports();
+                for (source_id, source_port) in apical_sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Apical,
+                            offset: 0,
+                            source_port,
                         });
                     }
                 }
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
                 // Input connections only (context is self-feedback, handled separately)
-                let sources = b.input().get_source_blocks();
-                for source_id in sources {
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
                     if let Some(&source_idx) = id_to_index.get(&source_id) {
                         connections.push(ConnectionConfig {
                             source_block: source_idx,
                             target_block: target_idx,
                             input_type: InputType::Input,
                             offset: 0,
+                            source_port,
                         });
                     }
                 }
                 // Note: SequenceLearner's context self-feedback is handled in from_config()
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+                // Input connections
+                let sources = b.input().get_source_ports();
+                for (source_id, source_port) in sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Input,
+                            offset: 0,
+                            source_port,
+                        });
+                    }
+                }
+                // Context connections (optional mask)
+                let ctx_sources = b.context().get_source_ports();
+                for (source_id, source_port) in ctx_sources {
+                    if let Some(&source_idx) = id_to_index.get(&source_id) {
+                        connections.push(ConnectionConfig {
+                            source_block: source_idx,
+                            target_block: target_idx,
+                            input_type: InputType::Context,
+                            offset: 0,
+                            source_port,
+                        });
+                    }
+                }
             }
         }
 
-        Ok(NetworkConfig::new(block_configs, connections))
+        let mut config = NetworkConfig::new(block_configs, connections);
+        for (info, sparse) in config.block_info.iter_mut().zip(sparse_outputs) {
+            info.sparse_output = sparse;
+        }
+        Ok(config)
     }
 
     /// Import network configuration to create a new network.
@@ -1417,7 +2026,7 @@ impl Network {
     /// ```
     pub fn from_config(config: &crate::network_config::NetworkConfig) -> Result<Self> {
         use crate::network_config::{BlockConfig, InputType};
-        use crate::{InputAccess, ContextAccess};
+        use crate::{ApicalAccess, InputAccess, ContextAccess};
 
         let mut net = Network::new();
         let mut block_ids = Vec::new();
@@ -1432,60 +2041,177 @@ impl Network {
         // Create all blocks
         for block_config in &block_configs {
             let block_id = match block_config {
-                BlockConfig::ScalarTransformer { min_val, max_val, num_s, num_as, num_t, seed } => {
-                    net.add(crate::blocks::ScalarTransformer::new(*min_val, *max_val, *num_s, *num_as, *num_t, *seed))
+                BlockConfig::ScalarTransformer { min_val, max_val, num_s, num_as, num_t, seed, scale, adapt } => {
+                    net.add(
+                        crate::blocks::ScalarTransformer::new(*min_val, *max_val, *num_s, *num_as, *num_t, *seed)
+                            .with_scale(scale.clone())
+                            .with_adapt(*adapt),
+                    )
                 }
                 BlockConfig::DiscreteTransformer { num_v, num_s, num_t, seed } => {
                     net.add(crate::blocks::DiscreteTransformer::new(*num_v, *num_s, *num_t, *seed))
                 }
-                BlockConfig::PersistenceTransformer { min_val, max_val, num_s, num_as, max_step, num_t, seed } => {
-                    net.add(crate::blocks::PersistenceTransformer::new(*min_val, *max_val, *num_s, *num_as, *max_step, *num_t, *seed))
+                BlockConfig::PersistenceTransformer { min_val, max_val, num_s, num_as, max_step, num_t, seed, threshold } => {
+                    net.add(crate::blocks::PersistenceTransformer::new(*min_val, *max_val, *num_s, *num_as, *max_step, *num_t, *seed).with_threshold(*threshold))
+                }
+                BlockConfig::DeltaTransformer { min_delta, max_delta, num_s, num_as, smoothing, num_t, seed } => net.add(
+                    crate::blocks::DeltaTransformer::new(*min_delta, *max_delta, *num_s, *num_as, *num_t, *seed)
+                        .with_smoothing(*smoothing),
+                ),
+                BlockConfig::RdseTransformer { resolution, num_s, num_as, num_t, seed } => {
+                    net.add(crate::blocks::RdseTransformer::new(*resolution, *num_s, *num_as, *num_t, *seed))
                 }
-                BlockConfig::PatternPooler { num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, always_update, num_t, seed } => {
-                    net.add(crate::blocks::PatternPooler::new(*num_s, *num_as, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_conn, *pct_learn, *always_update, *num_t, *seed))
+                BlockConfig::CyclicTransformer { period, num_s, num_as, num_t, seed } => {
+                    net.add(crate::blocks::CyclicTransformer::new(*period, *num_s, *num_as, *num_t, *seed))
                 }
-                BlockConfig::PatternClassifier { num_l, num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, num_t, seed } => {
-                    net.add(crate::blocks::PatternClassifier::new(*num_l, *num_s, *num_as, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_conn, *pct_learn, *num_t, *seed))
+                BlockConfig::DateTimeTransformer { fields, num_t, seed } => {
+                    net.add(crate::blocks::DateTimeTransformer::new(fields.clone(), *num_t, *seed))
                 }
-                BlockConfig::ContextLearner { num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t, always_update, seed } => {
-                    net.add(crate::blocks::ContextLearner::new(*num_c, *num_spc, *num_dps, *num_rpd, *d_thresh, *perm_thr, *perm_inc, *perm_dec, *num_t, *always_update, *seed))
+                BlockConfig::HyperGridTransformer {
+                    num_dims,
+                    num_grids,
+                    num_bins,
+                    grid_dims,
+                    min_period,
+                    max_period,
+                    num_t,
+                    seed,
+                } => net.add(crate::blocks::HyperGridTransformer::new(
+                    *num_dims,
+                    *num_grids,
+                    *num_bins,
+                    *grid_dims,
+                    *min_period,
+                    *max_period,
+                    *num_t,
+                    *seed,
+                )),
+                BlockConfig::SymbolTransformer { num_s, num_as, num_t, seed } => {
+                    net.add(crate::blocks::SymbolTransformer::new(*num_s, *num_as, *num_t, *seed))
+                }
+                BlockConfig::ImageTransformer { width, height, downsample, binarize, num_t, seed } => net.add(
+                    crate::blocks::ImageTransformer::new(*width, *height, *num_t, *seed)
+                        .with_downsample(*downsample)
+                        .with_binarize(*binarize),
+                ),
+                BlockConfig::BitFieldSource { num_s, num_t, seed } => {
+                    net.add(crate::blocks::BitFieldSource::new(*num_s, *num_t, *seed))
+                }
+                BlockConfig::PatternPooler { num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, always_update, num_t, seed, topology } => {
+                    let mut pooler = crate::blocks::PatternPooler::new(*num_s, *num_as, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_conn, *pct_learn, *always_update, *num_t, *seed);
+                    if let Some(topology) = topology {
+                        // Deserialized topologies skip PoolerTopology::new()'s checks
+                        if topology.num_inputs() == 0 || topology.num_statelets() == 0 {
+                            return Err(GnomicsError::InvalidParameter(
+                                "PatternPooler topology grids must be non-empty".into(),
+                            ));
+                        }
+                        if topology.num_statelets() != *num_s {
+                            return Err(GnomicsError::InvalidParameter(format!(
+                                "PatternPooler topology has {} statelets, expected {}",
+                                topology.num_statelets(),
+                                num_s
+                            )));
+                        }
+                        pooler.set_topology(*topology);
+                    }
+                    net.add(pooler)
+                }
+                BlockConfig::PatternClassifier { num_l, num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, num_t, seed, label_names, temperature, unknown_threshold, label_thresholds } => {
+                    // Check what the builders below would otherwise assert on
+                    if temperature.is_nan() || *temperature <= 0.0 {
+                        return Err(GnomicsError::InvalidParameter(format!(
+                            "PatternClassifier temperature must be > 0, got {}",
+                            temperature
+                        )));
+                    }
+                    if !(0.0..=1.0).contains(unknown_threshold)
+                        || !label_thresholds.iter().all(|t| (0.0..=1.0).contains(t))
+                    {
+                        return Err(GnomicsError::InvalidParameter(
+                            "PatternClassifier thresholds must be in [0, 1]".into(),
+                        ));
+                    }
+                    for (i, name) in label_names.iter().enumerate() {
+                        if label_names[..i].contains(name) {
+                            return Err(GnomicsError::InvalidParameter(format!(
+                                "PatternClassifier label name {:?} is duplicated",
+                                name
+                            )));
+                        }
+                    }
+                    let mut classifier = crate::blocks::PatternClassifier::new(*num_l, *num_s, *num_as, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_conn, *pct_learn, *num_t, *seed)
+                        .with_temperature(*temperature)
+                        .with_unknown_threshold(*unknown_threshold);
+                    if label_names.len() >= *num_l {
+                        let (initial, added) = label_names.split_at(*num_l);
+                        classifier.set_label_names(&initial.iter().map(String::as_str).collect::<Vec<_>>());
+                        for name in added {
+                            classifier.add_label(name)?;
+                        }
+                    }
+                    if label_thresholds.len() == classifier.num_l() {
+                        for (label, &threshold) in label_thresholds.iter().enumerate() {
+                            classifier.set_label_threshold(label, threshold);
+                        }
+                    }
+                    net.add(classifier)
+                }
+                BlockConfig::SdrClassifier { num_l, steps, alpha, num_t, seed, value_range } => {
+                    let mut classifier = crate::blocks::SdrClassifier::new(*num_l, *steps, *alpha, *num_t, *seed);
+                    if let Some((min_val, max_val)) = value_range {
+                        classifier = classifier.with_value_range(*min_val, *max_val);
+                    }
+                    net.add(classifier)
+                }
+                BlockConfig::ActionLearner { num_a, num_s, alpha, gamma, lambda, epsilon, num_t, seed } => {
+                    net.add(crate::blocks::ActionLearner::new(*num_a, *num_s, *alpha, *gamma, *lambda, *epsilon, *num_t, *seed))
+                }
+                BlockConfig::PatternClusterer { num_c, num_spc, match_thr, perm_thr, perm_inc, perm_dec, pct_pool, pct_learn, num_t, seed, merge_thr, max_idle, maintenance_period } => {
+                    net.add(
+                        crate::blocks::PatternClusterer::new(*num_c, *num_spc, *match_thr, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_learn, *num_t, *seed)
+                            .with_merge_threshold(*merge_thr)
+                            .with_max_idle(*max_idle)
+                            .with_maintenance_period(*maintenance_period),
+                    )
+                }
+                BlockConfig::ContextLearner { num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t, always_update, seed, apical_thresh, predicted_enabled } => {
+                    let mut learner = crate::blocks::ContextLearner::new(*num_c, *num_spc, *num_dps, *num_rpd, *d_thresh, *perm_thr, *perm_inc, *perm_dec, *num_t, *always_update, *seed);
+                    if let Some(a_thresh) = apical_thresh {
+                        learner.set_apical_threshold(*a_thresh);
+                    }
+                    learner.set_predicted_enabled(*predicted_enabled);
+                    net.add(learner)
                 }
-                BlockConfig::SequenceLearner { num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t, always_update, seed } => {
-                    net.add(crate::blocks::SequenceLearner::new(*num_c, *num_spc, *num_dps, *num_rpd, *d_thresh, *perm_thr, *perm_inc, *perm_dec, *num_t, *always_update, *seed))
+                BlockConfig::SequenceLearner { num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t, always_update, seed, predicted_enabled } => {
+                    let mut learner = crate::blocks::SequenceLearner::new(*num_c, *num_spc, *num_dps, *num_rpd, *d_thresh, *perm_thr, *perm_inc, *perm_dec, *num_t, *always_update, *seed);
+                    learner.set_predicted_enabled(*predicted_enabled);
+                    net.add(learner)
+                }
+                BlockConfig::TemporalPooler { num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, decay, num_t, seed } => {
+                    net.add(crate::blocks::TemporalPooler::new(*num_s, *num_as, *perm_thr, *perm_inc, *perm_dec, *pct_pool, *pct_conn, *pct_learn, *decay, *num_t, *seed))
+                }
+                BlockConfig::DelayLine { num_i, lags, num_t, seed } => {
+                    net.add(crate::blocks::DelayLine::new(*num_i, 1, *num_t, *seed).with_lags(lags))
                 }
             };
             block_ids.push(block_id);
         }
 
+        // Restore sparse output history
+        for (info, &block_id) in config.block_info.iter().zip(&block_ids) {
+            if info.sparse_output {
+                net.set_sparse_output(block_id, true)?;
+            }
+        }
+
         // Restore connections
         for conn in &config.connections {
             let source_id = block_ids[conn.source_block];
             let target_id = block_ids[conn.target_block];
 
             // Get output from source block (needs OutputAccess trait)
-            let output = {
-                let wrapper = net.blocks.get(&source_id).unwrap();
-                let block_any = wrapper.as_any();
-
-                // Try to get output from any block type that has OutputAccess
-                if let Some(b) = block_any.downcast_ref::<crate::blocks::ScalarTransformer>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DiscreteTransformer>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PersistenceTransformer>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
-                    b.output()
-                } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
-                    b.output()
-                } else {
-                    return Err(GnomicsError::Other("Unknown block type for output access".into()));
-                }
-            };
+            let output = net.port_output(source_id, conn.source_port)?;
 
             // Add child to target block's input
             let wrapper = net.blocks.get_mut(&target_id).unwrap();
@@ -1497,10 +2223,20 @@ impl Network {
                         b.input_mut().add_child(output, conn.offset);
                     } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClassifier>() {
                         b.input_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClusterer>() {
+                        b.input_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SdrClassifier>() {
+                        b.input_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::DelayLine>() {
+                        b.input_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ActionLearner>() {
+                        b.input_mut().add_child(output, conn.offset);
                     } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ContextLearner>() {
                         b.input_mut().add_child(output, conn.offset);
                     } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SequenceLearner>() {
                         b.input_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::TemporalPooler>() {
+                        b.input_mut().add_child(output, conn.offset);
                     }
                 }
                 InputType::Context => {
@@ -1508,6 +2244,13 @@ impl Network {
                         b.context_mut().add_child(output, conn.offset);
                     } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SequenceLearner>() {
                         b.context_mut().add_child(output, conn.offset);
+                    } else if let Some(b) = block_any.downcast_mut::<crate::blocks::TemporalPooler>() {
+                        b.context_mut().add_child(output, conn.offset);
+                    }
+                }
+                InputType::Apical => {
+                    if let Some(b) = block_any.downcast_mut::<crate::blocks::ContextLearner>() {
+                        b.apical_mut().add_child(output, conn.offset);
                     }
                 }
             }
@@ -1586,14 +2329,40 @@ impl Network {
                 b.to_state()?
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PersistenceTransformer>() {
                 b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DeltaTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::RdseTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::CyclicTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DateTimeTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::HyperGridTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SymbolTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ImageTransformer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::BitFieldSource>() {
+                b.to_state()?
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternPooler>() {
                 b.to_state()?
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClassifier>() {
                 b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::PatternClusterer>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SdrClassifier>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::DelayLine>() {
+                b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ActionLearner>() {
+                b.to_state()?
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::ContextLearner>() {
                 b.to_state()?
             } else if let Some(b) = block_any.downcast_ref::<crate::blocks::SequenceLearner>() {
                 b.to_state()?
+            } else if let Some(b) = block_any.downcast_ref::<crate::blocks::TemporalPooler>() {
+                b.to_state()?
             } else {
                 return Err(GnomicsError::Other("Unknown block type for state export".into()));
             };
@@ -1662,10 +2431,20 @@ impl Network {
                 b.init()?;
             } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClassifier>() {
                 b.init()?;
+            } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClusterer>() {
+                b.init()?;
+            } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SdrClassifier>() {
+                b.init()?;
+            } else if let Some(b) = block_any.downcast_mut::<crate::blocks::DelayLine>() {
+                b.init()?;
+            } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ActionLearner>() {
+                b.init()?;
             } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ContextLearner>() {
                 b.init()?;
             } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SequenceLearner>() {
                 b.init()?;
+            } else if let Some(b) = block_any.downcast_mut::<crate::blocks::TemporalPooler>() {
+                b.init()?;
             }
         }
 
@@ -1696,14 +2475,40 @@ impl Network {
                     b.from_state(state)?;
                 } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PersistenceTransformer>() {
                     b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::DeltaTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::RdseTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::CyclicTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::DateTimeTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::HyperGridTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SymbolTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ImageTransformer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::BitFieldSource>() {
+                    b.from_state(state)?;
                 } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternPooler>() {
                     b.from_state(state)?;
                 } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClassifier>() {
                     b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::PatternClusterer>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SdrClassifier>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::DelayLine>() {
+                    b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ActionLearner>() {
+                    b.from_state(state)?;
                 } else if let Some(b) = block_any.downcast_mut::<crate::blocks::ContextLearner>() {
                     b.from_state(state)?;
                 } else if let Some(b) = block_any.downcast_mut::<crate::blocks::SequenceLearner>() {
                     b.from_state(state)?;
+                } else if let Some(b) = block_any.downcast_mut::<crate::blocks::TemporalPooler>() {
+                    b.from_state(state)?;
                 } else {
                     return Err(GnomicsError::Other("Unknown block type for state import".into()));
                 }
@@ -1759,6 +2564,14 @@ impl<'a> ConnectionBuilder<'a> {
         Ok(self)
     }
 
+    /// Connect to target's apical (top-down feedback) input.
+    ///
+    /// Chainable - returns self to allow additional connections.
+    pub fn to_apical(self, target: BlockId) -> Result<Self> {
+        self.network.connect_to_apical(self.source, target)?;
+        Ok(self)
+    }
+
     /// Connect to target's input with explicit offset.
     ///
     /// Chainable - returns self to allow additional connections.
@@ -1776,6 +2589,15 @@ impl<'a> ConnectionBuilder<'a> {
             .connect_to_context_with_offset(self.source, target, offset)?;
         Ok(self)
     }
+
+    /// Connect to target's apical input with explicit offset.
+    ///
+    /// Chainable - returns self to allow additional connections.
+    pub fn to_apical_with_offset(self, target: BlockId, offset: usize) -> Result<Self> {
+        self.network
+            .connect_to_apical_with_offset(self.source, target, offset)?;
+        Ok(self)
+    }
 }
 
 #[cfg(test)]
@@ -44,6 +44,31 @@ use crate::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Threshold for configs saved before it was configurable.
+fn default_persistence_threshold() -> f64 {
+    crate::blocks::persistence_transformer::DEFAULT_THRESHOLD
+}
+
+/// Classifier temperature for configs saved before calibration existed.
+fn default_classifier_temperature() -> f64 {
+    crate::blocks::pattern_classifier::DEFAULT_TEMPERATURE
+}
+
+/// Clusterer merge threshold for configs saved before it was configurable.
+fn default_merge_threshold() -> f64 {
+    crate::blocks::pattern_clusterer::DEFAULT_MERGE_THRESHOLD
+}
+
+/// Clusterer idle limit for configs saved before it was configurable.
+fn default_max_idle() -> usize {
+    crate::blocks::pattern_clusterer::DEFAULT_MAX_IDLE
+}
+
+/// Clusterer maintenance period for configs saved before it was configurable.
+fn default_maintenance_period() -> usize {
+    crate::blocks::pattern_clusterer::DEFAULT_MAINTENANCE_PERIOD
+}
+
 /// Configuration for a specific block type.
 ///
 /// This enum captures all the constructor parameters needed to recreate
@@ -58,6 +83,10 @@ pub enum BlockConfig {
         num_as: usize,
         num_t: usize,
         seed: u64,
+        #[serde(default)]
+        scale: crate::blocks::ScalarScale,
+        #[serde(default)]
+        adapt: crate::blocks::ScalarAdapt,
     },
 
     /// DiscreteTransformer configuration
@@ -77,6 +106,81 @@ pub enum BlockConfig {
         max_step: usize,
         num_t: usize,
         seed: u64,
+        #[serde(default = "default_persistence_threshold")]
+        threshold: f64,
+    },
+
+    /// DeltaTransformer configuration
+    DeltaTransformer {
+        min_delta: f64,
+        max_delta: f64,
+        num_s: usize,
+        num_as: usize,
+        smoothing: f64,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// RdseTransformer configuration
+    RdseTransformer {
+        resolution: f64,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// CyclicTransformer configuration
+    CyclicTransformer {
+        period: f64,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// DateTimeTransformer configuration
+    DateTimeTransformer {
+        fields: crate::blocks::DateTimeFields,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// HyperGridTransformer configuration
+    HyperGridTransformer {
+        num_dims: usize,
+        num_grids: usize,
+        num_bins: usize,
+        grid_dims: usize,
+        min_period: f64,
+        max_period: f64,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// SymbolTransformer configuration
+    SymbolTransformer {
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// ImageTransformer configuration
+    ImageTransformer {
+        width: usize,
+        height: usize,
+        downsample: usize,
+        binarize: crate::blocks::ImageBinarize,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// BitFieldSource configuration
+    BitFieldSource {
+        num_s: usize,
+        num_t: usize,
+        seed: u64,
     },
 
     /// PatternPooler configuration
@@ -92,6 +196,8 @@ pub enum BlockConfig {
         always_update: bool,
         num_t: usize,
         seed: u64,
+        #[serde(default)]
+        topology: Option<crate::blocks::PoolerTopology>,
     },
 
     /// PatternClassifier configuration
@@ -107,6 +213,63 @@ pub enum BlockConfig {
         pct_learn: f64,
         num_t: usize,
         seed: u64,
+        /// Names of all labels; entries past `num_l` were added with `add_label()`
+        #[serde(default)]
+        label_names: Vec<String>,
+        #[serde(default = "default_classifier_temperature")]
+        temperature: f64,
+        #[serde(default)]
+        unknown_threshold: f64,
+        /// Per-label thresholds for multi-label predictions, one per label name
+        #[serde(default)]
+        label_thresholds: Vec<f64>,
+    },
+
+    /// SdrClassifier configuration
+    SdrClassifier {
+        num_l: usize,
+        steps: usize,
+        alpha: f64,
+        num_t: usize,
+        seed: u64,
+        /// Bucket range `(min, max)` when predicting scalar values
+        #[serde(default)]
+        value_range: Option<(f64, f64)>,
+    },
+
+    /// ActionLearner configuration
+    ActionLearner {
+        num_a: usize,
+        num_s: usize,
+        alpha: f64,
+        gamma: f64,
+        lambda: f64,
+        epsilon: f64,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// PatternClusterer configuration
+    PatternClusterer {
+        num_c: usize,
+        num_spc: usize,
+        match_thr: f64,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        pct_pool: f64,
+        pct_learn: f64,
+        num_t: usize,
+        seed: u64,
+        /// Prototype overlap at which clusters merge
+        #[serde(default = "default_merge_threshold")]
+        merge_thr: f64,
+        /// Idle learning steps before a cluster is pruned (0 = never)
+        #[serde(default = "default_max_idle")]
+        max_idle: usize,
+        /// Learning steps between prune and merge passes (0 = never)
+        #[serde(default = "default_maintenance_period")]
+        maintenance_period: usize,
     },
 
     /// ContextLearner configuration
@@ -122,6 +285,12 @@ pub enum BlockConfig {
         num_t: usize,
         always_update: bool,
         seed: u64,
+        /// Apical dendrite threshold (defaults to `d_thresh` when absent)
+        #[serde(default)]
+        apical_thresh: Option<u32>,
+        /// Whether the predicted output is computed
+        #[serde(default)]
+        predicted_enabled: bool,
     },
 
     /// SequenceLearner configuration
@@ -137,6 +306,33 @@ pub enum BlockConfig {
         num_t: usize,
         always_update: bool,
         seed: u64,
+        /// Whether the predicted output is computed
+        #[serde(default)]
+        predicted_enabled: bool,
+    },
+
+    /// TemporalPooler configuration
+    TemporalPooler {
+        num_s: usize,
+        num_as: usize,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        pct_pool: f64,
+        pct_conn: f64,
+        pct_learn: f64,
+        decay: f64,
+        num_t: usize,
+        seed: u64,
+    },
+
+    /// DelayLine configuration
+    DelayLine {
+        num_i: usize,
+        /// Lags output, in frame order
+        lags: Vec<usize>,
+        num_t: usize,
+        seed: u64,
     },
 }
 
@@ -145,8 +341,20 @@ pub enum BlockConfig {
 pub enum InputType {
     /// Main input (BlockInput)
     Input,
-    /// Context input (for ContextLearner, SequenceLearner)
+    /// Context input (for ContextLearner, SequenceLearner, TemporalPooler)
     Context,
+    /// Apical (top-down feedback) input (for ContextLearner)
+    Apical,
+}
+
+/// Which output of the source block a connection reads from.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OutputPort {
+    /// Active statelets (the block's main output)
+    #[default]
+    Active,
+    /// Predicted statelets (for ContextLearner, SequenceLearner)
+    Predicted,
 }
 
 /// Configuration for a connection between blocks.
@@ -162,6 +370,9 @@ pub struct ConnectionConfig {
     pub input_type: InputType,
     /// Offset parameter for add_child (typically 0)
     pub offset: usize,
+    /// Output of the source block to read (active statelets if absent)
+    #[serde(default)]
+    pub source_port: OutputPort,
 }
 
 /// Information about a block in the network.
@@ -173,6 +384,9 @@ pub struct BlockInfo {
     pub name: String,
     /// Block configuration
     pub config: BlockConfig,
+    /// Keep the block output's history sparse (see `BlockOutput::set_sparse()`)
+    #[serde(default)]
+    pub sparse_output: bool,
 }
 
 /// Learned state for blocks with synaptic memory.
@@ -184,6 +398,28 @@ pub enum BlockState {
     /// Transformer blocks have no learned state
     NoState,
 
+    /// ScalarTransformer state (adapted range, adaptive modes only)
+    ScalarTransformer {
+        /// Current minimum value
+        min_val: f64,
+        /// Current maximum value
+        max_val: f64,
+        /// Running quantiles (Quantile mode only)
+        quantiles: Vec<f64>,
+    },
+
+    /// RdseTransformer state (bucket offset fixed by the first value seen)
+    RdseTransformer {
+        /// Center of bucket 0, if set
+        offset: Option<f64>,
+    },
+
+    /// SymbolTransformer state (similarity hints)
+    SymbolTransformer {
+        /// Hints as (symbol, anchor, shared bits), sorted by symbol
+        hints: Vec<(u64, u64, usize)>,
+    },
+
     /// PatternPooler learned state (synaptic permanences)
     PatternPooler {
         /// Permanence values: [dendrite][receptor] -> 0-99
@@ -194,12 +430,64 @@ pub enum BlockState {
     PatternClassifier {
         /// Permanence values: [dendrite][receptor] -> 0-99
         permanences: Vec<Vec<u8>>,
+        /// Best match fraction seen while learning each label
+        #[serde(default)]
+        learned_max: Vec<f64>,
+    },
+
+    /// SdrClassifier learned state
+    SdrClassifier {
+        /// Weights: [input bit][label]
+        weights: Vec<f64>,
+        /// Average actual value per bucket (empty unless predicting values)
+        bucket_values: Vec<f64>,
+    },
+
+    /// ActionLearner learned state
+    ActionLearner {
+        /// Weights: [input bit][action]
+        weights: Vec<f64>,
+    },
+
+    /// PatternClusterer learned state
+    PatternClusterer {
+        /// Permanence values: [dendrite][receptor] -> 0-99
+        permanences: Vec<Vec<u8>>,
+        /// Prototype in use per dendrite
+        active: Vec<bool>,
+        /// Inputs assigned per prototype
+        counts: Vec<usize>,
+        /// Learning step each prototype was last assigned
+        last_used: Vec<u64>,
+        /// Learning steps taken
+        num_steps: u64,
     },
 
     /// ContextLearner learned state
     ContextLearner {
         /// Permanence values: [dendrite][receptor] -> 0-99
         permanences: Vec<Vec<u8>>,
+        /// Apical dendrite permanences (empty without apical input)
+        #[serde(default)]
+        apical_permanences: Vec<Vec<u8>>,
+        /// Receptor addresses: [dendrite][receptor] -> context bit
+        #[serde(default)]
+        addresses: Vec<Vec<usize>>,
+        /// Apical receptor addresses (empty without apical input)
+        #[serde(default)]
+        apical_addresses: Vec<Vec<usize>>,
+        /// Next available distal dendrite per statelet
+        #[serde(default)]
+        next_sd: Vec<usize>,
+        /// Indices of used distal dendrites
+        #[serde(default)]
+        d_used: Vec<usize>,
+        /// Next available apical dendrite per statelet (empty without apical input)
+        #[serde(default)]
+        next_ad: Vec<usize>,
+        /// Indices of used apical dendrites (empty without apical input)
+        #[serde(default)]
+        a_used: Vec<usize>,
     },
 
     /// SequenceLearner learned state
@@ -207,6 +495,12 @@ pub enum BlockState {
         /// Permanence values: [dendrite][receptor] -> 0-99
         permanences: Vec<Vec<u8>>,
     },
+
+    /// TemporalPooler learned state
+    TemporalPooler {
+        /// Permanence values: [dendrite][receptor] -> 0-99
+        permanences: Vec<Vec<u8>>,
+    },
 }
 
 /// Complete network configuration.
@@ -252,6 +546,7 @@ impl NetworkConfig {
                 .map(|(i, config)| BlockInfo {
                     name: format!("block_{}", i),
                     config: config.clone(),
+                    sparse_output: false,
                 })
                 .collect(),
             connections,
@@ -360,6 +655,11 @@ mod tests {
             num_as: 256,
             num_t: 2,
             seed: 0,
+            scale: crate::blocks::ScalarScale::Log,
+            adapt: crate::blocks::ScalarAdapt::Quantile {
+                num_points: 5,
+                rate: 0.01,
+            },
         };
 
         // Test JSON round-trip
@@ -384,6 +684,8 @@ mod tests {
                     num_as: 256,
                     num_t: 2,
                     seed: 0,
+                    scale: Default::default(),
+                    adapt: Default::default(),
                 },
                 BlockConfig::PatternPooler {
                     num_s: 1024,
@@ -397,6 +699,7 @@ mod tests {
                     always_update: false,
                     num_t: 2,
                     seed: 0,
+                    topology: None,
                 },
             ],
             vec![ConnectionConfig {
@@ -404,6 +707,7 @@ mod tests {
                 target_block: 1,
                 input_type: InputType::Input,
                 offset: 0,
+                source_port: OutputPort::Active,
             }],
         );
 
@@ -0,0 +1,536 @@
+//! SIMD kernels for BitField word operations.
+//!
+//! This module provides explicitly vectorized kernels for the word-level hot
+//! paths of [`BitField`](crate::BitField): population count, AND-popcount
+//! (used by `num_similar()` and therefore `BlockMemory::overlap_conn()`) and the
+//! bitwise operators. Bulk word copies (used by `BlockInput::pull()`) go
+//! through [`copy`], which stays on `memcpy`.
+//!
+//! Compare the levels on your machine with
+//! `cargo bench --bench bitfield_bench -- simd/`.
+//!
+//! # Dispatch
+//!
+//! The best available instruction set is detected once at runtime:
+//!
+//! - **AVX-512** - `avx512f` + `avx512bw` + `avx512vpopcntdq` (native 512-bit popcount)
+//! - **AVX2** - 256-bit nibble-lookup popcount (`vpshufb` + `vpsadbw`)
+//! - **SSE2** - 128-bit SWAR popcount (`psadbw` accumulation)
+//! - **Scalar** - portable `count_ones()` fallback (all other targets, including WASM)
+//!
+//! The active level can be lowered with [`set_simd_level`] for benchmarking and
+//! testing; it can never be raised above what the CPU supports.
+//!
+//! All kernels operate on `&[Word]`, so they work unchanged with the `word64`
+//! feature (64-bit words).
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::simd;
+//!
+//! let a = [0b1011u32 as gnomics::Word; 16];
+//! let b = [0b0110u32 as gnomics::Word; 16];
+//!
+//! assert_eq!(simd::popcount(&a), 3 * 16);
+//! assert_eq!(simd::and_popcount(&a, &b), 16);
+//! ```
+
+use crate::bitfield::Word;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Instruction set used by the SIMD kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdLevel {
+    /// Portable word-by-word implementation
+    Scalar = 0,
+    /// 128-bit SSE2 kernels
+    Sse2 = 1,
+    /// 256-bit AVX2 kernels
+    Avx2 = 2,
+    /// 512-bit AVX-512 kernels (with VPOPCNTDQ)
+    Avx512 = 3,
+}
+
+impl SimdLevel {
+    /// Detect the best level supported by the running CPU.
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f")
+                && is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512vpopcntdq")
+            {
+                return SimdLevel::Avx512;
+            }
+            if is_x86_feature_detected!("avx2") {
+                return SimdLevel::Avx2;
+            }
+            if is_x86_feature_detected!("sse2") {
+                return SimdLevel::Sse2;
+            }
+        }
+
+        SimdLevel::Scalar
+    }
+
+    /// Get a short human-readable name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimdLevel::Scalar => "scalar",
+            SimdLevel::Sse2 => "sse2",
+            SimdLevel::Avx2 => "avx2",
+            SimdLevel::Avx512 => "avx512",
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => SimdLevel::Sse2,
+            2 => SimdLevel::Avx2,
+            3 => SimdLevel::Avx512,
+            _ => SimdLevel::Scalar,
+        }
+    }
+}
+
+/// Sentinel for "not yet detected"
+const LEVEL_UNSET: u8 = u8::MAX;
+
+/// Active level (lazily detected)
+static LEVEL: AtomicU8 = AtomicU8::new(LEVEL_UNSET);
+
+/// Get the SIMD level currently used by the kernels.
+#[inline]
+pub fn simd_level() -> SimdLevel {
+    let v = LEVEL.load(Ordering::Relaxed);
+    if v != LEVEL_UNSET {
+        return SimdLevel::from_u8(v);
+    }
+
+    let level = SimdLevel::detect();
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    level
+}
+
+/// Set the SIMD level used by the kernels (process-wide).
+///
+/// The level is clamped to what the CPU supports. Returns the level actually
+/// in effect. Intended for benchmarks and tests comparing kernels.
+pub fn set_simd_level(level: SimdLevel) -> SimdLevel {
+    let level = level.min(SimdLevel::detect());
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    level
+}
+
+/// Count set bits in `words`.
+#[inline]
+pub fn popcount(words: &[Word]) -> usize {
+    match simd_level() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { x86::popcount_avx512(words) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { x86::popcount_avx2(words) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::popcount_sse2(words) },
+        _ => scalar::popcount(words),
+    }
+}
+
+/// Count bits set in both `a` and `b` (AND + popcount) without allocating.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn and_popcount(a: &[Word], b: &[Word]) -> usize {
+    assert_eq!(a.len(), b.len(), "word slices must have same length");
+
+    match simd_level() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { x86::and_popcount_avx512(a, b) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => unsafe { x86::and_popcount_avx2(a, b) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::and_popcount_sse2(a, b) },
+        _ => scalar::and_popcount(a, b),
+    }
+}
+
+/// Copy `src` into `dst`.
+///
+/// Every level uses the standard library `memcpy`, which is already
+/// vectorized and beats explicit unaligned load/store loops. Median of three
+/// runs of `cargo bench --bench bitfield_bench -- 'simd/.*/copy'` (criterion
+/// point estimates) on an Intel Xeon with AVX-512:
+///
+/// | bits  | memcpy | AVX2 loop | AVX-512 loop |
+/// |-------|--------|-----------|--------------|
+/// | 1024  | 4.0ns  | 5.3ns     | 5.9ns        |
+/// | 16384 | 19.6ns | 23.2ns    | 24.8ns       |
+///
+/// Run-to-run spread was up to ~30%, but memcpy was fastest in every run.
+/// The bench keeps the loops, so this choice can be rechecked per machine.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[inline]
+pub fn copy(dst: &mut [Word], src: &[Word]) {
+    assert_eq!(dst.len(), src.len(), "word slices must have same length");
+    dst.copy_from_slice(src);
+}
+
+/// Generate a dispatching in-place bitwise kernel (`dst = dst OP src`).
+macro_rules! bitwise_kernel {
+    ($(#[$doc:meta])* $name:ident, $op:tt, $avx512:ident, $avx2:ident, $sse2:ident) => {
+        $(#[$doc])*
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices have different lengths.
+        #[inline]
+        pub fn $name(dst: &mut [Word], src: &[Word]) {
+            assert_eq!(dst.len(), src.len(), "word slices must have same length");
+
+            match simd_level() {
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Avx512 => unsafe { x86::$avx512(dst, src) },
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Avx2 => unsafe { x86::$avx2(dst, src) },
+                #[cfg(target_arch = "x86_64")]
+                SimdLevel::Sse2 => unsafe { x86::$sse2(dst, src) },
+                _ => {
+                    for (d, s) in dst.iter_mut().zip(src) {
+                        *d $op *s;
+                    }
+                }
+            }
+        }
+    };
+}
+
+bitwise_kernel!(
+    /// In-place bitwise AND (`dst &= src`).
+    and_assign, &=, and_avx512, and_avx2, and_sse2
+);
+bitwise_kernel!(
+    /// In-place bitwise OR (`dst |= src`).
+    or_assign, |=, or_avx512, or_avx2, or_sse2
+);
+bitwise_kernel!(
+    /// In-place bitwise XOR (`dst ^= src`).
+    xor_assign, ^=, xor_avx512, xor_avx2, xor_sse2
+);
+
+/// Portable fallback kernels.
+mod scalar {
+    use crate::bitfield::Word;
+
+    #[inline]
+    pub fn popcount(words: &[Word]) -> usize {
+        words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn and_popcount(a: &[Word], b: &[Word]) -> usize {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x & y).count_ones() as usize)
+            .sum()
+    }
+}
+
+/// x86_64 kernels.
+///
+/// Every function requires its target feature to be present at runtime, which
+/// the dispatchers above guarantee via [`simd_level()`]. Loads and stores are
+/// unaligned; the tail that does not fill a whole vector is handled by the
+/// scalar code.
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::scalar;
+    use crate::bitfield::Word;
+    use std::arch::x86_64::*;
+
+    /// Words per vector of `bytes` bytes
+    const fn lanes(bytes: usize) -> usize {
+        bytes / std::mem::size_of::<Word>()
+    }
+
+    // -------------------------------------------------------------------------
+    // SSE2
+    // -------------------------------------------------------------------------
+
+    /// Per-byte popcount of a 128-bit vector (SWAR, SSE2 has no byte shuffle).
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn popcnt_bytes_sse2(v: __m128i) -> __m128i {
+        let m1 = _mm_set1_epi8(0x55);
+        let m2 = _mm_set1_epi8(0x33);
+        let m4 = _mm_set1_epi8(0x0f);
+
+        let v = _mm_sub_epi8(v, _mm_and_si128(_mm_srli_epi16(v, 1), m1));
+        let v = _mm_add_epi8(
+            _mm_and_si128(v, m2),
+            _mm_and_si128(_mm_srli_epi16(v, 2), m2),
+        );
+        _mm_and_si128(_mm_add_epi8(v, _mm_srli_epi16(v, 4)), m4)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn hsum_sse2(acc: __m128i) -> usize {
+        let hi = _mm_unpackhi_epi64(acc, acc);
+        _mm_cvtsi128_si64(_mm_add_epi64(acc, hi)) as usize
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn popcount_sse2(words: &[Word]) -> usize {
+        let n = lanes(16);
+        let chunks = words.len() / n;
+        let ptr = words.as_ptr() as *const __m128i;
+        let zero = _mm_setzero_si128();
+        let mut acc = zero;
+
+        for i in 0..chunks {
+            let v = _mm_loadu_si128(ptr.add(i));
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(popcnt_bytes_sse2(v), zero));
+        }
+
+        hsum_sse2(acc) + scalar::popcount(&words[chunks * n..])
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn and_popcount_sse2(a: &[Word], b: &[Word]) -> usize {
+        let n = lanes(16);
+        let chunks = a.len() / n;
+        let pa = a.as_ptr() as *const __m128i;
+        let pb = b.as_ptr() as *const __m128i;
+        let zero = _mm_setzero_si128();
+        let mut acc = zero;
+
+        for i in 0..chunks {
+            let v = _mm_and_si128(_mm_loadu_si128(pa.add(i)), _mm_loadu_si128(pb.add(i)));
+            acc = _mm_add_epi64(acc, _mm_sad_epu8(popcnt_bytes_sse2(v), zero));
+        }
+
+        hsum_sse2(acc) + scalar::and_popcount(&a[chunks * n..], &b[chunks * n..])
+    }
+
+    // -------------------------------------------------------------------------
+    // AVX2
+    // -------------------------------------------------------------------------
+
+    /// Per-byte popcount of a 256-bit vector via nibble lookup.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn popcnt_bytes_avx2(v: __m256i) -> __m256i {
+        let lut = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2,
+            3, 3, 4,
+        );
+        let low = _mm256_set1_epi8(0x0f);
+
+        let lo = _mm256_and_si256(v, low);
+        let hi = _mm256_and_si256(_mm256_srli_epi16(v, 4), low);
+        _mm256_add_epi8(_mm256_shuffle_epi8(lut, lo), _mm256_shuffle_epi8(lut, hi))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn hsum_avx2(acc: __m256i) -> usize {
+        let sum = _mm_add_epi64(
+            _mm256_castsi256_si128(acc),
+            _mm256_extracti128_si256(acc, 1),
+        );
+        let hi = _mm_unpackhi_epi64(sum, sum);
+        _mm_cvtsi128_si64(_mm_add_epi64(sum, hi)) as usize
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn popcount_avx2(words: &[Word]) -> usize {
+        let n = lanes(32);
+        let chunks = words.len() / n;
+        let ptr = words.as_ptr() as *const __m256i;
+        let zero = _mm256_setzero_si256();
+        let mut acc = zero;
+
+        for i in 0..chunks {
+            let v = _mm256_loadu_si256(ptr.add(i));
+            acc = _mm256_add_epi64(acc, _mm256_sad_epu8(popcnt_bytes_avx2(v), zero));
+        }
+
+        hsum_avx2(acc) + scalar::popcount(&words[chunks * n..])
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn and_popcount_avx2(a: &[Word], b: &[Word]) -> usize {
+        let n = lanes(32);
+        let chunks = a.len() / n;
+        let pa = a.as_ptr() as *const __m256i;
+        let pb = b.as_ptr() as *const __m256i;
+        let zero = _mm256_setzero_si256();
+        let mut acc = zero;
+
+        for i in 0..chunks {
+            let v = _mm256_and_si256(_mm256_loadu_si256(pa.add(i)), _mm256_loadu_si256(pb.add(i)));
+            acc = _mm256_add_epi64(acc, _mm256_sad_epu8(popcnt_bytes_avx2(v), zero));
+        }
+
+        hsum_avx2(acc) + scalar::and_popcount(&a[chunks * n..], &b[chunks * n..])
+    }
+
+    // -------------------------------------------------------------------------
+    // AVX-512
+    // -------------------------------------------------------------------------
+
+    #[target_feature(enable = "avx512f,avx512vpopcntdq")]
+    pub unsafe fn popcount_avx512(words: &[Word]) -> usize {
+        let n = lanes(64);
+        let chunks = words.len() / n;
+        let ptr = words.as_ptr() as *const __m512i;
+        let mut acc = _mm512_setzero_si512();
+
+        for i in 0..chunks {
+            let v = _mm512_loadu_si512(ptr.add(i));
+            acc = _mm512_add_epi64(acc, _mm512_popcnt_epi64(v));
+        }
+
+        _mm512_reduce_add_epi64(acc) as usize + scalar::popcount(&words[chunks * n..])
+    }
+
+    #[target_feature(enable = "avx512f,avx512vpopcntdq")]
+    pub unsafe fn and_popcount_avx512(a: &[Word], b: &[Word]) -> usize {
+        let n = lanes(64);
+        let chunks = a.len() / n;
+        let pa = a.as_ptr() as *const __m512i;
+        let pb = b.as_ptr() as *const __m512i;
+        let mut acc = _mm512_setzero_si512();
+
+        for i in 0..chunks {
+            let v = _mm512_and_si512(_mm512_loadu_si512(pa.add(i)), _mm512_loadu_si512(pb.add(i)));
+            acc = _mm512_add_epi64(acc, _mm512_popcnt_epi64(v));
+        }
+
+        _mm512_reduce_add_epi64(acc) as usize
+            + scalar::and_popcount(&a[chunks * n..], &b[chunks * n..])
+    }
+
+    // -------------------------------------------------------------------------
+    // Bitwise kernels
+    // -------------------------------------------------------------------------
+
+    /// Generate an in-place bitwise kernel for one vector width.
+    macro_rules! bitwise {
+        ($name:ident, $feature:literal, $vec:ty, $bytes:expr, $load:ident, $store:ident, $op:ident, $sop:tt) => {
+            #[target_feature(enable = $feature)]
+            pub unsafe fn $name(dst: &mut [Word], src: &[Word]) {
+                let n = lanes($bytes);
+                let chunks = src.len() / n;
+                let pd = dst.as_mut_ptr() as *mut $vec;
+                let ps = src.as_ptr() as *const $vec;
+
+                for i in 0..chunks {
+                    let v = $op($load(pd.add(i) as *const $vec), $load(ps.add(i)));
+                    $store(pd.add(i), v);
+                }
+
+                for (d, s) in dst[chunks * n..].iter_mut().zip(&src[chunks * n..]) {
+                    *d $sop *s;
+                }
+            }
+        };
+    }
+
+    bitwise!(and_sse2, "sse2", __m128i, 16, _mm_loadu_si128, _mm_storeu_si128, _mm_and_si128, &=);
+    bitwise!(or_sse2, "sse2", __m128i, 16, _mm_loadu_si128, _mm_storeu_si128, _mm_or_si128, |=);
+    bitwise!(xor_sse2, "sse2", __m128i, 16, _mm_loadu_si128, _mm_storeu_si128, _mm_xor_si128, ^=);
+
+    bitwise!(and_avx2, "avx2", __m256i, 32, _mm256_loadu_si256, _mm256_storeu_si256, _mm256_and_si256, &=);
+    bitwise!(or_avx2, "avx2", __m256i, 32, _mm256_loadu_si256, _mm256_storeu_si256, _mm256_or_si256, |=);
+    bitwise!(xor_avx2, "avx2", __m256i, 32, _mm256_loadu_si256, _mm256_storeu_si256, _mm256_xor_si256, ^=);
+
+    bitwise!(and_avx512, "avx512f", __m512i, 64, _mm512_loadu_si512, _mm512_storeu_si512, _mm512_and_si512, &=);
+    bitwise!(or_avx512, "avx512f", __m512i, 64, _mm512_loadu_si512, _mm512_storeu_si512, _mm512_or_si512, |=);
+    bitwise!(xor_avx512, "avx512f", __m512i, 64, _mm512_loadu_si512, _mm512_storeu_si512, _mm512_xor_si512, ^=);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Levels supported on this machine, lowest first
+    fn levels() -> Vec<SimdLevel> {
+        [
+            SimdLevel::Scalar,
+            SimdLevel::Sse2,
+            SimdLevel::Avx2,
+            SimdLevel::Avx512,
+        ]
+        .into_iter()
+        .filter(|&l| l <= SimdLevel::detect())
+        .collect()
+    }
+
+    fn random_words(rng: &mut StdRng, n: usize) -> Vec<Word> {
+        (0..n).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_kernels_match_scalar() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // Lengths around every vector width to exercise the scalar tails
+        for len in [0, 1, 3, 4, 7, 8, 15, 16, 17, 31, 32, 33, 64, 100] {
+            let a = random_words(&mut rng, len);
+            let b = random_words(&mut rng, len);
+
+            let pop = scalar::popcount(&a);
+            let and_pop = scalar::and_popcount(&a, &b);
+            let and: Vec<Word> = a.iter().zip(&b).map(|(x, y)| x & y).collect();
+            let or: Vec<Word> = a.iter().zip(&b).map(|(x, y)| x | y).collect();
+            let xor: Vec<Word> = a.iter().zip(&b).map(|(x, y)| x ^ y).collect();
+
+            for level in levels() {
+                set_simd_level(level);
+
+                assert_eq!(popcount(&a), pop, "popcount {:?} len {}", level, len);
+                assert_eq!(
+                    and_popcount(&a, &b),
+                    and_pop,
+                    "and_popcount {:?} len {}",
+                    level,
+                    len
+                );
+
+                let mut dst = vec![0; len];
+                copy(&mut dst, &a);
+                assert_eq!(dst, a, "copy {:?} len {}", level, len);
+
+                let mut dst = a.clone();
+                and_assign(&mut dst, &b);
+                assert_eq!(dst, and, "and {:?} len {}", level, len);
+
+                let mut dst = a.clone();
+                or_assign(&mut dst, &b);
+                assert_eq!(dst, or, "or {:?} len {}", level, len);
+
+                let mut dst = a.clone();
+                xor_assign(&mut dst, &b);
+                assert_eq!(dst, xor, "xor {:?} len {}", level, len);
+            }
+        }
+
+        set_simd_level(SimdLevel::detect());
+    }
+
+    #[test]
+    fn test_set_simd_level_clamps() {
+        let detected = SimdLevel::detect();
+        assert_eq!(set_simd_level(SimdLevel::Avx512), detected);
+        assert_eq!(SimdLevel::Scalar.name(), "scalar");
+    }
+}
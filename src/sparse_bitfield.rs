@@ -0,0 +1,548 @@
+//! SparseBitField - Sorted index-list bit array for very sparse patterns.
+//!
+//! Stores only the indices of set bits (as sorted `u32`s) instead of one bit
+//! per position. For SDRs at ~2% density or lower this uses far less memory
+//! than a dense [`BitField`], and set operations cost O(active bits) instead
+//! of O(num_bits).
+//!
+//! # Design
+//!
+//! - Indices are kept sorted and unique at all times
+//! - Same core API as `BitField` (`set_bit`, `get_bit`, `&`, `|`, `^`,
+//!   `num_similar`, `get_acts`)
+//! - Lossless conversion to and from `BitField` via `From`
+//! - `BlockOutput` can keep its history sparse (see
+//!   [`BlockOutput::set_sparse`](crate::BlockOutput::set_sparse)) and
+//!   `BlockInput::pull()` accepts either representation; output state,
+//!   input state and block working memory stay dense BitFields
+//!
+//! # Examples
+//!
+//! ```
+//! use gnomics::{BitField, SparseBitField};
+//!
+//! let mut sb = SparseBitField::new(1_000_000);
+//! sb.set_bit(10);
+//! sb.set_bit(5);
+//! assert_eq!(sb.get_acts(), vec![5, 10]);
+//!
+//! let dense = BitField::from(&sb);
+//! assert_eq!(SparseBitField::from(&dense), sb);
+//! ```
+
+use crate::bitfield::{BitField, BITS_PER_WORD};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::{BitAnd, BitOr, BitXor};
+
+/// Bit array stored as a sorted list of active bit indices.
+///
+/// All bit indices are 0-based and must be less than `num_bits`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SparseBitField {
+    /// Total number of bits
+    num_bits: usize,
+
+    /// Sorted, unique indices of set bits
+    acts: Vec<u32>,
+}
+
+impl SparseBitField {
+    /// Create a new SparseBitField with `n` bits, all initialized to 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds `u32::MAX + 1` (indices are stored as `u32`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gnomics::SparseBitField;
+    ///
+    /// let sb = SparseBitField::new(1024);
+    /// assert_eq!(sb.num_bits(), 1024);
+    /// assert_eq!(sb.num_set(), 0);
+    /// ```
+    #[inline]
+    pub fn new(n: usize) -> Self {
+        assert!(
+            n <= u32::MAX as usize + 1,
+            "SparseBitField supports at most 2^32 bits"
+        );
+        Self {
+            num_bits: n,
+            acts: Vec::new(),
+        }
+    }
+
+    /// Resize to `n` bits and clear all bits.
+    pub fn resize(&mut self, n: usize) {
+        assert!(
+            n <= u32::MAX as usize + 1,
+            "SparseBitField supports at most 2^32 bits"
+        );
+        self.num_bits = n;
+        self.acts.clear();
+    }
+
+    /// Get total number of bits.
+    #[inline(always)]
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    // =========================================================================
+    // Single Bit Operations
+    // =========================================================================
+
+    /// Set bit at position `b` to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if `b >= num_bits`.
+    #[inline]
+    pub fn set_bit(&mut self, b: usize) {
+        debug_assert!(
+            b < self.num_bits,
+            "bit index {} out of bounds (length: {})",
+            b,
+            self.num_bits
+        );
+        if let Err(pos) = self.acts.binary_search(&(b as u32)) {
+            self.acts.insert(pos, b as u32);
+        }
+    }
+
+    /// Get bit at position `b` (returns 0 or 1 as u8).
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if `b >= num_bits`.
+    #[inline]
+    pub fn get_bit(&self, b: usize) -> u8 {
+        debug_assert!(
+            b < self.num_bits,
+            "bit index {} out of bounds (length: {})",
+            b,
+            self.num_bits
+        );
+        self.acts.binary_search(&(b as u32)).is_ok() as u8
+    }
+
+    /// Clear bit at position `b` (set to 0).
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if `b >= num_bits`.
+    #[inline]
+    pub fn clear_bit(&mut self, b: usize) {
+        debug_assert!(
+            b < self.num_bits,
+            "bit index {} out of bounds (length: {})",
+            b,
+            self.num_bits
+        );
+        if let Ok(pos) = self.acts.binary_search(&(b as u32)) {
+            self.acts.remove(pos);
+        }
+    }
+
+    /// Toggle bit at position `b` (0 -> 1, 1 -> 0).
+    #[inline]
+    pub fn toggle_bit(&mut self, b: usize) {
+        debug_assert!(
+            b < self.num_bits,
+            "bit index {} out of bounds (length: {})",
+            b,
+            self.num_bits
+        );
+        match self.acts.binary_search(&(b as u32)) {
+            Ok(pos) => {
+                self.acts.remove(pos);
+            }
+            Err(pos) => self.acts.insert(pos, b as u32),
+        }
+    }
+
+    /// Assign bit at position `b` to given value (0 or 1).
+    ///
+    /// Any non-zero value is treated as 1.
+    #[inline]
+    pub fn assign_bit(&mut self, b: usize, val: u8) {
+        if val > 0 {
+            self.set_bit(b);
+        } else {
+            self.clear_bit(b);
+        }
+    }
+
+    /// Clear all bits to 0 (keeps allocated capacity).
+    #[inline]
+    pub fn clear_all(&mut self) {
+        self.acts.clear();
+    }
+
+    // =========================================================================
+    // Vector Operations
+    // =========================================================================
+
+    /// Set bits from vector of indices.
+    ///
+    /// Clears all bits first, then sets bits at indices in `idxs`. Indices may
+    /// be unsorted or repeated. Indices >= num_bits are silently ignored.
+    pub fn set_acts(&mut self, idxs: &[usize]) {
+        self.acts.clear();
+        self.acts.extend(
+            idxs.iter()
+                .filter(|&&idx| idx < self.num_bits)
+                .map(|&idx| idx as u32),
+        );
+        self.acts.sort_unstable();
+        self.acts.dedup();
+    }
+
+    /// Get indices of all set bits, in ascending order.
+    pub fn get_acts(&self) -> Vec<usize> {
+        self.acts.iter().map(|&a| a as usize).collect()
+    }
+
+    /// Get indices of all set bits into an existing vector.
+    ///
+    /// Clears `acts` first. Does not allocate when `acts` already has enough
+    /// capacity.
+    pub fn get_acts_into(&self, acts: &mut Vec<usize>) {
+        acts.clear();
+        acts.extend(self.acts.iter().map(|&a| a as usize));
+    }
+
+    /// Get the sorted active indices as a slice (no copy).
+    #[inline(always)]
+    pub fn acts(&self) -> &[u32] {
+        &self.acts
+    }
+
+    // =========================================================================
+    // Counting Operations
+    // =========================================================================
+
+    /// Count number of set bits.
+    #[inline(always)]
+    pub fn num_set(&self) -> usize {
+        self.acts.len()
+    }
+
+    /// Count number of cleared bits.
+    #[inline]
+    pub fn num_cleared(&self) -> usize {
+        self.num_bits - self.acts.len()
+    }
+
+    /// Count number of set bits shared with another SparseBitField.
+    ///
+    /// Merge-intersects the two index lists in O(a + b).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fields have different sizes.
+    pub fn num_similar(&self, other: &SparseBitField) -> usize {
+        assert_eq!(
+            self.num_bits, other.num_bits,
+            "SparseBitFields must have same size"
+        );
+
+        let (mut i, mut j, mut count) = (0, 0, 0);
+        while i < self.acts.len() && j < other.acts.len() {
+            match self.acts[i].cmp(&other.acts[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    count += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Count number of set bits shared with a dense BitField.
+    ///
+    /// Probes the dense field once per active index, so cost is O(num_set).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fields have different sizes.
+    pub fn num_similar_dense(&self, other: &BitField) -> usize {
+        assert_eq!(
+            self.num_bits,
+            other.num_bits(),
+            "fields must have same size"
+        );
+
+        let words = other.words();
+        self.acts
+            .iter()
+            .filter(|&&a| {
+                let a = a as usize;
+                (words[a / BITS_PER_WORD] >> (a % BITS_PER_WORD)) & 1 == 1
+            })
+            .count()
+    }
+
+    // =========================================================================
+    // Conversions
+    // =========================================================================
+
+    /// Overwrite this field with the contents of a dense BitField.
+    ///
+    /// Resizes to match `src` and reuses the existing index storage, so it
+    /// does not allocate once the capacity covers `src.num_set()`.
+    pub fn copy_from_dense(&mut self, src: &BitField) {
+        self.num_bits = src.num_bits();
+        self.acts.clear();
+
+        for (word_idx, &word) in src.words().iter().enumerate() {
+            let mut w = word;
+            while w != 0 {
+                let bit_idx = w.trailing_zeros() as usize;
+                self.acts.push((word_idx * BITS_PER_WORD + bit_idx) as u32);
+                w &= w - 1;
+            }
+        }
+    }
+
+    /// Write this field into a dense BitField of the same size.
+    ///
+    /// Clears `dst` first. Does not allocate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` has a different size.
+    pub fn copy_to_dense(&self, dst: &mut BitField) {
+        assert_eq!(self.num_bits, dst.num_bits(), "fields must have same size");
+        bitfield_copy_sparse(dst, self, 0, dst.num_words());
+    }
+
+    /// Convert to a dense BitField.
+    pub fn to_dense(&self) -> BitField {
+        BitField::from(self)
+    }
+
+    // =========================================================================
+    // Information
+    // =========================================================================
+
+    /// Estimate memory usage in bytes.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.acts.capacity() * std::mem::size_of::<u32>()
+    }
+
+    /// Merge two index lists, keeping indices selected by `keep(in_a, in_b)`.
+    fn merge(&self, other: &SparseBitField, keep: impl Fn(bool, bool) -> bool) -> SparseBitField {
+        assert_eq!(
+            self.num_bits, other.num_bits,
+            "SparseBitFields must have same size"
+        );
+
+        let mut acts = Vec::with_capacity(self.acts.len() + other.acts.len());
+        let (mut i, mut j) = (0, 0);
+        while i < self.acts.len() || j < other.acts.len() {
+            let a = self.acts.get(i).copied().unwrap_or(u32::MAX);
+            let b = other.acts.get(j).copied().unwrap_or(u32::MAX);
+            let (idx, in_a, in_b) = match (i < self.acts.len(), j < other.acts.len()) {
+                (true, true) if a == b => (a, true, true),
+                (true, true) if a < b => (a, true, false),
+                (true, false) => (a, true, false),
+                _ => (b, false, true),
+            };
+            if keep(in_a, in_b) {
+                acts.push(idx);
+            }
+            i += in_a as usize;
+            j += in_b as usize;
+        }
+
+        SparseBitField {
+            num_bits: self.num_bits,
+            acts,
+        }
+    }
+}
+
+// =============================================================================
+// Conversions
+// =============================================================================
+
+impl From<&BitField> for SparseBitField {
+    fn from(bf: &BitField) -> Self {
+        let mut sb = SparseBitField::new(bf.num_bits());
+        sb.acts.reserve_exact(bf.num_set());
+        sb.copy_from_dense(bf);
+        sb
+    }
+}
+
+impl From<&SparseBitField> for BitField {
+    fn from(sb: &SparseBitField) -> Self {
+        let mut bf = BitField::new(sb.num_bits);
+        sb.copy_to_dense(&mut bf);
+        bf
+    }
+}
+
+// =============================================================================
+// Bitwise Operators
+// =============================================================================
+
+impl BitAnd for &SparseBitField {
+    type Output = SparseBitField;
+
+    /// Bitwise AND (sorted-list intersection).
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.merge(rhs, |a, b| a && b)
+    }
+}
+
+impl BitAnd for SparseBitField {
+    type Output = SparseBitField;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        &self & &rhs
+    }
+}
+
+impl BitOr for &SparseBitField {
+    type Output = SparseBitField;
+
+    /// Bitwise OR (sorted-list union).
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.merge(rhs, |a, b| a || b)
+    }
+}
+
+impl BitOr for SparseBitField {
+    type Output = SparseBitField;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        &self | &rhs
+    }
+}
+
+impl BitXor for &SparseBitField {
+    type Output = SparseBitField;
+
+    /// Bitwise XOR (sorted-list symmetric difference).
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.merge(rhs, |a, b| a != b)
+    }
+}
+
+impl BitXor for SparseBitField {
+    type Output = SparseBitField;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        &self ^ &rhs
+    }
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Write a sparse field into a word range of a dense BitField.
+///
+/// Sparse counterpart of [`bitfield_copy_words`](crate::bitfield_copy_words):
+/// clears `num_words` words of `dst` starting at `dst_word_offset`, then sets
+/// the active bits of `src` relative to that offset. Used by
+/// `BlockInput::pull()` for children with sparse history.
+///
+/// # Panics
+///
+/// Panics in debug mode if `src` does not fit in the destination range.
+pub fn bitfield_copy_sparse(
+    dst: &mut BitField,
+    src: &SparseBitField,
+    dst_word_offset: usize,
+    num_words: usize,
+) {
+    let dst_end = dst_word_offset + num_words;
+    debug_assert!(dst_end <= dst.num_words(), "dst word overflow");
+    debug_assert!(
+        src.num_bits <= num_words * BITS_PER_WORD,
+        "src bit overflow"
+    );
+
+    let words = &mut dst.words_mut()[dst_word_offset..dst_end];
+    words.fill(0);
+    for &a in &src.acts {
+        let a = a as usize;
+        words[a / BITS_PER_WORD] |= 1 << (a % BITS_PER_WORD);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get_clear() {
+        let mut sb = SparseBitField::new(100);
+        sb.set_bit(42);
+        sb.set_bit(7);
+        sb.set_bit(42);
+        assert_eq!(sb.acts(), &[7, 42]);
+        assert_eq!(sb.get_bit(42), 1);
+        assert_eq!(sb.get_bit(8), 0);
+
+        sb.clear_bit(7);
+        sb.toggle_bit(99);
+        assert_eq!(sb.get_acts(), vec![42, 99]);
+        assert_eq!(sb.num_cleared(), 98);
+    }
+
+    #[test]
+    fn test_set_acts_sorts_and_dedups() {
+        let mut sb = SparseBitField::new(50);
+        sb.set_acts(&[30, 2, 30, 10, 70]);
+        assert_eq!(sb.get_acts(), vec![2, 10, 30]);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let mut bf = BitField::new(200);
+        bf.set_acts(&[0, 31, 32, 63, 64, 150, 199]);
+
+        let sb = SparseBitField::from(&bf);
+        assert_eq!(sb.get_acts(), bf.get_acts());
+        assert_eq!(BitField::from(&sb), bf);
+    }
+
+    #[test]
+    fn test_bitwise_ops() {
+        let mut a = SparseBitField::new(64);
+        let mut b = SparseBitField::new(64);
+        a.set_acts(&[1, 5, 9, 20]);
+        b.set_acts(&[5, 9, 33]);
+
+        assert_eq!((&a & &b).get_acts(), vec![5, 9]);
+        assert_eq!((&a | &b).get_acts(), vec![1, 5, 9, 20, 33]);
+        assert_eq!((&a ^ &b).get_acts(), vec![1, 20, 33]);
+        assert_eq!(a.num_similar(&b), 2);
+        assert_eq!(a.num_similar_dense(&b.to_dense()), 2);
+    }
+
+    #[test]
+    fn test_copy_sparse_at_offset() {
+        let mut dst = BitField::new(128);
+        dst.set_range(0, 128);
+
+        let mut src = SparseBitField::new(64);
+        src.set_acts(&[0, 63]);
+        bitfield_copy_sparse(&mut dst, &src, 64 / BITS_PER_WORD, 64 / BITS_PER_WORD);
+
+        assert_eq!(dst.num_set(), 66);
+        assert_eq!(dst.get_bit(64), 1);
+        assert_eq!(dst.get_bit(65), 0);
+        assert_eq!(dst.get_bit(127), 1);
+    }
+}
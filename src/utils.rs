@@ -113,6 +113,30 @@ pub fn shuffle_indices<R: Rng>(arr: &mut [usize], rng: &mut R) {
     }
 }
 
+/// Hash a `key` under a `seed` into a well-mixed 64-bit value.
+///
+/// Deterministic across platforms and runs (SplitMix64 finalizer), so encoders
+/// can derive stable random bit positions from values without storing them.
+///
+/// # Examples
+///
+/// ```
+/// use gnomics::utils::hash_u64;
+///
+/// assert_eq!(hash_u64(7, 42), hash_u64(7, 42));
+/// assert_ne!(hash_u64(7, 42), hash_u64(8, 42));
+/// ```
+#[inline]
+pub fn hash_u64(seed: u64, key: u64) -> u64 {
+    let mut z = seed
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(key)
+        .wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
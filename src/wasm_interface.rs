@@ -149,6 +149,320 @@ impl WasmNetwork {
         handle
     }
 
+    /// Add a DeltaTransformer block for encoding the change between consecutive values.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `min_delta` - Most negative delta encoded
+    /// * `max_delta` - Most positive delta encoded
+    /// * `num_s` - Number of statelets
+    /// * `num_as` - Number of active statelets
+    /// * `smoothing` - EMA factor in (0, 1] (1 = raw delta)
+    /// * `num_t` - History depth
+    /// * `seed` - Random seed
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const trend = net.add_delta_transformer("Trend", -2.0, 2.0, 2048, 256, 0.5, 2, 42);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_delta_transformer(
+        &mut self,
+        name: &str,
+        min_delta: f64,
+        max_delta: f64,
+        num_s: usize,
+        num_as: usize,
+        smoothing: f64,
+        num_t: usize,
+        seed: u32,
+    ) -> Result<usize, JsValue> {
+        if !(smoothing > 0.0 && smoothing <= 1.0) {
+            return Err(JsValue::from_str("smoothing must be in (0.0, 1.0]"));
+        }
+
+        let block = DeltaTransformer::new(min_delta, max_delta, num_s, num_as, num_t, seed.into())
+            .with_smoothing(smoothing);
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add an RdseTransformer block for encoding unbounded continuous values.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `resolution` - Value width of one bucket
+    /// * `num_s` - Number of statelets
+    /// * `num_as` - Number of active statelets
+    /// * `num_t` - History depth
+    /// * `seed` - Random seed
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const encoder = net.add_rdse_transformer(
+    ///     "Sensor", 0.5, 2048, 40, 2, 42
+    /// );
+    /// ```
+    pub fn add_rdse_transformer(
+        &mut self,
+        name: &str,
+        resolution: f64,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u32,
+    ) -> usize {
+        let block = RdseTransformer::new(resolution, num_s, num_as, num_t, seed.into());
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Add a CyclicTransformer block for encoding periodic values.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `period` - Length of one cycle (e.g. 360 for degrees, 24 for hours)
+    /// * `num_s` - Number of statelets
+    /// * `num_as` - Number of active statelets
+    /// * `num_t` - History depth
+    /// * `seed` - Random seed
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const encoder = net.add_cyclic_transformer(
+    ///     "Heading", 360.0, 1024, 64, 2, 42
+    /// );
+    /// ```
+    pub fn add_cyclic_transformer(
+        &mut self,
+        name: &str,
+        period: f64,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u32,
+    ) -> usize {
+        let block = CyclicTransformer::new(period, num_s, num_as, num_t, seed.into());
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Add a DateTimeTransformer block for encoding Unix timestamps.
+    ///
+    /// Each field is given as a (statelets, active statelets) pair; pass
+    /// `num_s = 0` to disable a field.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `tod_s`, `tod_as` - Time-of-day field
+    /// * `dow_s`, `dow_as` - Day-of-week field
+    /// * `weekend_s`, `weekend_as` - Weekend flag field
+    /// * `holiday_s`, `holiday_as` - Holiday flag field
+    /// * `holiday_days` - Holiday dates as days since 1970-01-01
+    /// * `season_s`, `season_as` - Season field
+    /// * `utc_offset_minutes` - Offset from UTC in minutes
+    /// * `num_t` - History depth
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const dt = net.add_date_time_transformer(
+    ///     "Clock", 512, 32, 256, 32, 64, 32, 0, 0, [], 0, 0, -300, 2
+    /// );
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_date_time_transformer(
+        &mut self,
+        name: &str,
+        tod_s: usize,
+        tod_as: usize,
+        dow_s: usize,
+        dow_as: usize,
+        weekend_s: usize,
+        weekend_as: usize,
+        holiday_s: usize,
+        holiday_as: usize,
+        holiday_days: Vec<i32>,
+        season_s: usize,
+        season_as: usize,
+        utc_offset_minutes: i32,
+        num_t: usize,
+    ) -> usize {
+        let field = |num_s, num_as| (num_s > 0).then_some(DateTimeField { num_s, num_as });
+        let mut holiday_days: Vec<i64> = holiday_days.into_iter().map(i64::from).collect();
+        holiday_days.sort_unstable();
+        holiday_days.dedup();
+
+        let fields = DateTimeFields {
+            time_of_day: field(tod_s, tod_as),
+            day_of_week: field(dow_s, dow_as),
+            weekend: field(weekend_s, weekend_as),
+            holiday: field(holiday_s, holiday_as),
+            season: field(season_s, season_as),
+            holiday_days,
+            utc_offset: i64::from(utc_offset_minutes) * 60,
+        };
+
+        let block = DateTimeTransformer::new(fields, num_t, 0);
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Add a HyperGridTransformer block for encoding N-dimensional vectors.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `num_dims` - Input vector dimension
+    /// * `num_grids` - Number of grids (active statelets)
+    /// * `num_bins` - Bins per grid axis
+    /// * `grid_dims` - Dimension of each grid subspace (1-3)
+    /// * `min_period` - Period of the finest grid
+    /// * `max_period` - Period of the coarsest grid
+    /// * `num_t` - History depth
+    /// * `seed` - Random seed
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const pos = net.add_hypergrid_transformer(
+    ///     "Position", 3, 64, 8, 2, 1.0, 20.0, 2, 42
+    /// );
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_hypergrid_transformer(
+        &mut self,
+        name: &str,
+        num_dims: usize,
+        num_grids: usize,
+        num_bins: usize,
+        grid_dims: usize,
+        min_period: f64,
+        max_period: f64,
+        num_t: usize,
+        seed: u32,
+    ) -> usize {
+        let block = HyperGridTransformer::new(
+            num_dims,
+            num_grids,
+            num_bins,
+            grid_dims,
+            min_period,
+            max_period,
+            num_t,
+            seed.into(),
+        );
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Add a SymbolTransformer block for encoding open-vocabulary symbols.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `num_s` - Number of statelets
+    /// * `num_as` - Number of active statelets per symbol
+    /// * `num_t` - History depth
+    /// * `seed` - Random seed
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const words = net.add_symbol_transformer("Words", 2048, 40, 2, 42);
+    /// ```
+    pub fn add_symbol_transformer(
+        &mut self,
+        name: &str,
+        num_s: usize,
+        num_as: usize,
+        num_t: usize,
+        seed: u32,
+    ) -> usize {
+        let block = SymbolTransformer::new(num_s, num_as, num_t, seed.into());
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Add an ImageTransformer block for encoding 2D images.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `width` - Image width in pixels
+    /// * `height` - Image height in pixels
+    /// * `downsample` - Pooling factor (1 = none)
+    /// * `binarize` - `"threshold"`, `"mean"` or `"edge"`
+    /// * `threshold` - Intensity (threshold) or gradient (edge) threshold
+    /// * `num_t` - History depth
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const image = net.add_image_transformer("Camera", 28, 28, 2, "threshold", 0.5, 2);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_image_transformer(
+        &mut self,
+        name: &str,
+        width: usize,
+        height: usize,
+        downsample: usize,
+        binarize: &str,
+        threshold: f64,
+        num_t: usize,
+    ) -> Result<usize, JsValue> {
+        let binarize = match binarize {
+            "threshold" => ImageBinarize::Threshold(threshold),
+            "mean" => ImageBinarize::Mean,
+            "edge" => ImageBinarize::Edge(threshold),
+            _ => return Err(JsValue::from_str("Unknown binarize mode (expected threshold, mean or edge)")),
+        };
+        if downsample == 0 {
+            return Err(JsValue::from_str("downsample factor must be > 0"));
+        }
+
+        let block = ImageTransformer::new(width, height, num_t, 0)
+            .with_downsample(downsample)
+            .with_binarize(binarize);
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add a BitFieldSource block for injecting externally computed SDRs.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `num_s` - Number of statelets
+    /// * `num_t` - History depth
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const source = net.add_bitfield_source("External", 1024, 2);
+    /// ```
+    pub fn add_bitfield_source(&mut self, name: &str, num_s: usize, num_t: usize) -> usize {
+        let block = BitFieldSource::new(num_s, num_t, 0);
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
     /// Add a PatternPooler block for unsupervised feature learning.
     ///
     /// # Example (JavaScript)
@@ -254,255 +568,1208 @@ impl WasmNetwork {
         handle
     }
 
-    /// Add a ContextLearner block for contextual pattern recognition.
+    /// Add an SdrClassifier block (linear readout to labels).
+    ///
+    /// Pass `min_val < max_val` to predict scalar values over `num_l` buckets,
+    /// or equal values (e.g. 0, 0) to predict labels.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// const learner = net.add_context_learner(
-    ///     "Context Learner", 512, 4, 8, 32, 20, 20, 2, 1, 2, false, 42
+    /// const readout = net.add_sdr_classifier("Readout", 10, 1, 0.1, 0, 0, 2, 0);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_sdr_classifier(
+        &mut self,
+        name: &str,
+        num_l: usize,
+        steps: usize,
+        alpha: f64,
+        min_val: f64,
+        max_val: f64,
+        num_t: usize,
+        seed: u32,
+    ) -> Result<usize, JsValue> {
+        if num_l == 0 {
+            return Err(JsValue::from_str("num_l must be > 0"));
+        }
+        if alpha <= 0.0 {
+            return Err(JsValue::from_str("alpha must be > 0"));
+        }
+        if min_val > max_val {
+            return Err(JsValue::from_str("min_val must be < max_val"));
+        }
+
+        let mut block = SdrClassifier::new(num_l, steps, alpha, num_t, seed.into());
+        if min_val < max_val {
+            block = block.with_value_range(min_val, max_val);
+        }
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add a PatternClusterer block for unsupervised clustering.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const clusterer = net.add_pattern_clusterer(
+    ///     "Clusters", 32, 8, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0
     /// );
     /// ```
     #[allow(clippy::too_many_arguments)]
-    pub fn add_context_learner(
+    pub fn add_pattern_clusterer(
         &mut self,
         name: &str,
         num_c: usize,
         num_spc: usize,
-        num_dps: usize,
-        num_rpd: usize,
-        d_thresh: u32,
+        match_thr: f64,
         perm_thr: u8,
         perm_inc: u8,
         perm_dec: u8,
+        pct_pool: f64,
+        pct_learn: f64,
         num_t: usize,
-        always_update: bool,
         seed: u32,
-    ) -> usize {
-        let block = ContextLearner::new(
-            num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t,
-            always_update, seed.into(),
+    ) -> Result<usize, JsValue> {
+        if num_c == 0 || num_spc == 0 {
+            return Err(JsValue::from_str("num_c and num_spc must be > 0"));
+        }
+        if !(match_thr > 0.0 && match_thr <= 1.0) {
+            return Err(JsValue::from_str("match_thr must be in (0.0, 1.0]"));
+        }
+        if !(pct_pool > 0.0 && pct_pool <= 1.0) {
+            return Err(JsValue::from_str("pct_pool must be in (0.0, 1.0]"));
+        }
+
+        let block = PatternClusterer::new(
+            num_c, num_spc, match_thr, perm_thr, perm_inc, perm_dec, pct_pool, pct_learn, num_t,
+            seed.into(),
         );
         let id = self.net.add(block);
         self.net.set_block_name(id, name);
         let handle = self.block_handles.len();
         self.block_handles.push((name.to_string(), id));
-        handle
+        Ok(handle)
+    }
+
+    /// Add an ActionLearner block (reward-driven action selection).
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const agent = net.add_action_learner("Agent", 4, 64, 0.1, 0.9, 0.8, 0.1, 2, 0);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_action_learner(
+        &mut self,
+        name: &str,
+        num_a: usize,
+        num_s: usize,
+        alpha: f64,
+        gamma: f64,
+        lambda: f64,
+        epsilon: f64,
+        num_t: usize,
+        seed: u32,
+    ) -> Result<usize, JsValue> {
+        if num_a == 0 || num_s < num_a {
+            return Err(JsValue::from_str("num_a must be > 0 and num_s >= num_a"));
+        }
+        if alpha <= 0.0 {
+            return Err(JsValue::from_str("alpha must be > 0"));
+        }
+        for (value, label) in [(gamma, "gamma"), (lambda, "lambda"), (epsilon, "epsilon")] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(JsValue::from_str(&format!(
+                    "{} must be in [0.0, 1.0]",
+                    label
+                )));
+            }
+        }
+
+        let block = ActionLearner::new(
+            num_a, num_s, alpha, gamma, lambda, epsilon, num_t, seed.into(),
+        );
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add a TemporalPooler block for stable sequence representations.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const tp = net.add_temporal_pooler(
+    ///     "Sequence Pooler", 1024, 40, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0
+    /// );
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_temporal_pooler(
+        &mut self,
+        name: &str,
+        num_s: usize,
+        num_as: usize,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        pct_pool: f64,
+        pct_conn: f64,
+        pct_learn: f64,
+        decay: f64,
+        num_t: usize,
+        seed: u32,
+    ) -> Result<usize, JsValue> {
+        if !(0.0..1.0).contains(&decay) {
+            return Err(JsValue::from_str("decay must be in [0.0, 1.0)"));
+        }
+
+        let block = TemporalPooler::new(
+            num_s, num_as, perm_thr, perm_inc, perm_dec, pct_pool, pct_conn, pct_learn, decay,
+            num_t, seed.into(),
+        );
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add a DelayLine block that outputs the input states at a set of lags.
+    ///
+    /// # Arguments
+    /// * `name` - Human-readable name
+    /// * `num_i` - Number of input bits per frame
+    /// * `lags` - Lags to output, in frame order (0 = current input)
+    /// * `num_t` - History depth
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const window = net.add_delay_line("Window", 1024, [0, 1, 2, 3], 2);
+    /// ```
+    pub fn add_delay_line(
+        &mut self,
+        name: &str,
+        num_i: usize,
+        lags: Vec<usize>,
+        num_t: usize,
+    ) -> Result<usize, JsValue> {
+        if num_i == 0 {
+            return Err(JsValue::from_str("num_i must be > 0"));
+        }
+        if lags.is_empty() {
+            return Err(JsValue::from_str("lags must not be empty"));
+        }
+
+        let block = DelayLine::new(num_i, 1, num_t, 0).with_lags(&lags);
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        Ok(handle)
+    }
+
+    /// Add a ContextLearner block for contextual pattern recognition.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const learner = net.add_context_learner(
+    ///     "Context Learner", 512, 4, 8, 32, 20, 20, 2, 1, 2, false, 42
+    /// );
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_context_learner(
+        &mut self,
+        name: &str,
+        num_c: usize,
+        num_spc: usize,
+        num_dps: usize,
+        num_rpd: usize,
+        d_thresh: u32,
+        perm_thr: u8,
+        perm_inc: u8,
+        perm_dec: u8,
+        num_t: usize,
+        always_update: bool,
+        seed: u32,
+    ) -> usize {
+        let block = ContextLearner::new(
+            num_c, num_spc, num_dps, num_rpd, d_thresh, perm_thr, perm_inc, perm_dec, num_t,
+            always_update, seed.into(),
+        );
+        let id = self.net.add(block);
+        self.net.set_block_name(id, name);
+        let handle = self.block_handles.len();
+        self.block_handles.push((name.to_string(), id));
+        handle
+    }
+
+    /// Connect source block output to target block input.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.connect_to_input(encoder, pooler);
+    /// ```
+    pub fn connect_to_input(
+        &mut self,
+        source_handle: usize,
+        target_handle: usize,
+    ) -> Result<(), JsValue> {
+        if source_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid source handle"));
+        }
+        if target_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid target handle"));
+        }
+
+        let source_id = self.block_handles[source_handle].1;
+        let target_id = self.block_handles[target_handle].1;
+
+        self.net
+            .connect_to_input(source_id, target_id)
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Connect source block output to target block context input.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.connect_to_context(context_encoder, learner);
+    /// ```
+    pub fn connect_to_context(
+        &mut self,
+        source_handle: usize,
+        target_handle: usize,
+    ) -> Result<(), JsValue> {
+        if source_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid source handle"));
+        }
+        if target_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid target handle"));
+        }
+
+        let source_id = self.block_handles[source_handle].1;
+        let target_id = self.block_handles[target_handle].1;
+
+        self.net
+            .connect_to_context(source_id, target_id)
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Connect source block predicted output to target block input.
+    ///
+    /// The source must be a ContextLearner or SequenceLearner.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.connect_predicted_to_input(learner, classifier);
+    /// ```
+    pub fn connect_predicted_to_input(
+        &mut self,
+        source_handle: usize,
+        target_handle: usize,
+    ) -> Result<(), JsValue> {
+        if source_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid source handle"));
+        }
+        if target_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid target handle"));
+        }
+
+        let source_id = self.block_handles[source_handle].1;
+        let target_id = self.block_handles[target_handle].1;
+
+        self.net
+            .connect_predicted_to_input(source_id, target_id)
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Connect source block output to target block apical (top-down feedback) input.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.connect_to_apical(pooler, learner);
+    /// ```
+    pub fn connect_to_apical(
+        &mut self,
+        source_handle: usize,
+        target_handle: usize,
+    ) -> Result<(), JsValue> {
+        if source_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid source handle"));
+        }
+        if target_handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid target handle"));
+        }
+
+        let source_id = self.block_handles[source_handle].1;
+        let target_id = self.block_handles[target_handle].1;
+
+        self.net
+            .connect_to_apical(source_id, target_id)
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Build the network (compute execution order).
+    ///
+    /// Must be called after adding all blocks and connections, before execute().
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.build();
+    /// ```
+    pub fn build(&mut self) -> Result<(), JsValue> {
+        self.net
+            .build()
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Initialize a learning block (allocates memory structures).
+    ///
+    /// Must be called for PatternPooler, PatternClassifier, SdrClassifier,
+    /// PatternClusterer, ActionLearner, SequenceLearner, ContextLearner and
+    /// TemporalPooler blocks before execution.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.init_block(pooler);
+    /// net.init_block(learner);
+    /// ```
+    pub fn init_block(&mut self, handle: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+
+        // Try each block type that needs initialization
+        if let Ok(block) = self.net.get_mut::<PatternPooler>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<SdrClassifier>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<PatternClusterer>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<ActionLearner>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<SequenceLearner>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<ContextLearner>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+        if let Ok(block) = self.net.get_mut::<TemporalPooler>(block_id) {
+            return block
+                .init()
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+        }
+
+        // Transformers don't need initialization
+        Ok(())
+    }
+
+    /// Start recording execution for visualization.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.start_recording();
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.net.start_recording();
+    }
+
+    /// Execute the network one step.
+    ///
+    /// # Arguments
+    /// * `learn` - Whether to enable learning
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.execute(true);  // with learning
+    /// net.execute(false); // inference only
+    /// ```
+    pub fn execute(&mut self, learn: bool) -> Result<(), JsValue> {
+        self.net
+            .execute(learn)
+            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    }
+
+    /// Get current execution trace as JSON string.
+    ///
+    /// This stops and restarts recording to get a snapshot.
+    /// Returns null if recording was not started.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const traceJson = net.get_trace_json();
+    /// if (traceJson) {
+    ///     const trace = JSON.parse(traceJson);
+    ///     // Update visualization
+    /// }
+    /// ```
+    pub fn get_trace_json(&mut self) -> Option<String> {
+        if let Some(trace) = self.net.stop_recording() {
+            let json = trace.to_json().ok()?;
+            // Restart recording for next batch
+            self.net.start_recording();
+            Some(json)
+        } else {
+            None
+        }
+    }
+
+    /// Set value for a ScalarTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_scalar_value(encoder, 42.5);
+    /// ```
+    pub fn set_scalar_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ScalarTransformer>(block_id) {
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a ScalarTransformer"))
+        }
+    }
+
+    /// Set the value-to-position scale of a ScalarTransformer block.
+    ///
+    /// `scale` is one of `"linear"`, `"log"` or `"sqrt"`.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_scalar_scale(latency, "log");
+    /// ```
+    pub fn set_scalar_scale(&mut self, handle: usize, scale: &str) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let scale = match scale {
+            "linear" => ScalarScale::Linear,
+            "log" => ScalarScale::Log,
+            "sqrt" => ScalarScale::Sqrt,
+            _ => return Err(JsValue::from_str("Unknown scale (expected linear, log or sqrt)")),
+        };
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ScalarTransformer>(block_id) {
+            if scale == ScalarScale::Log && block.min_val() <= 0.0 {
+                return Err(JsValue::from_str("Log scale requires min_val > 0"));
+            }
+            block.set_scale(scale);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a ScalarTransformer"))
+        }
+    }
+
+    /// Set the range adaptation mode of a ScalarTransformer block.
+    ///
+    /// `mode` is one of `"fixed"`, `"minmax"` or `"quantile"`; `num_points`
+    /// and `rate` are only used in quantile mode.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_scalar_adapt(latency, "quantile", 9, 0.01);
+    /// ```
+    pub fn set_scalar_adapt(
+        &mut self,
+        handle: usize,
+        mode: &str,
+        num_points: usize,
+        rate: f64,
+    ) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let adapt = match mode {
+            "fixed" => ScalarAdapt::Fixed,
+            "minmax" => ScalarAdapt::MinMax,
+            "quantile" => {
+                if num_points < 2 || !(rate > 0.0 && rate <= 1.0) {
+                    return Err(JsValue::from_str(
+                        "Quantile mode needs num_points >= 2 and rate in (0, 1]",
+                    ));
+                }
+                ScalarAdapt::Quantile { num_points, rate }
+            }
+            _ => return Err(JsValue::from_str("Unknown mode (expected fixed, minmax or quantile)")),
+        };
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ScalarTransformer>(block_id) {
+            block.set_adapt(adapt);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a ScalarTransformer"))
+        }
+    }
+
+    /// Set value for a DiscreteTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_discrete_value(encoder, 3);
+    /// ```
+    pub fn set_discrete_value(&mut self, handle: usize, value: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<DiscreteTransformer>(block_id) {
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a DiscreteTransformer"))
+        }
+    }
+
+    /// Set the stability threshold (fraction of range) of a PersistenceTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_persistence_threshold(encoder, 0.02);
+    /// ```
+    pub fn set_persistence_threshold(&mut self, handle: usize, threshold: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(JsValue::from_str("threshold must be in [0.0, 1.0]"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PersistenceTransformer>(block_id) {
+            block.set_threshold(threshold);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a PersistenceTransformer"))
+        }
+    }
+
+    /// Set value for a DeltaTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_delta_value(trend, 21.5);
+    /// ```
+    pub fn set_delta_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<DeltaTransformer>(block_id) {
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a DeltaTransformer"))
+        }
+    }
+
+    /// Set value for an RdseTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_rdse_value(encoder, 1234.5);
+    /// ```
+    pub fn set_rdse_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<RdseTransformer>(block_id) {
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an RdseTransformer"))
+        }
+    }
+
+    /// Set value for a CyclicTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_cyclic_value(encoder, 359.0);
+    /// ```
+    pub fn set_cyclic_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<CyclicTransformer>(block_id) {
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a CyclicTransformer"))
+        }
+    }
+
+    /// Set the Unix timestamp (seconds) for a DateTimeTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_date_time_value(dt, Date.now() / 1000);
+    /// ```
+    pub fn set_date_time_value(&mut self, handle: usize, timestamp: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<DateTimeTransformer>(block_id) {
+            block.set_value(timestamp.floor() as i64);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a DateTimeTransformer"))
+        }
+    }
+
+    /// Set the vector for a HyperGridTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_hypergrid_value(pos, new Float64Array([0.5, -2.0, 3.25]));
+    /// ```
+    pub fn set_hypergrid_value(&mut self, handle: usize, value: Vec<f64>) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<HyperGridTransformer>(block_id) {
+            if value.len() != block.num_dims() {
+                return Err(JsValue::from_str("Vector length does not match num_dims"));
+            }
+            block.set_value(&value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a HyperGridTransformer"))
+        }
+    }
+
+    /// Set the symbol for a SymbolTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_symbol_value(words, "hello");
+    /// ```
+    pub fn set_symbol_value(&mut self, handle: usize, symbol: &str) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<SymbolTransformer>(block_id) {
+            block.set_symbol(symbol);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a SymbolTransformer"))
+        }
+    }
+
+    /// Set pixel intensities (row-major, in [0, 1]) for an ImageTransformer block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_image_pixels(image, new Float64Array(28 * 28));
+    /// ```
+    pub fn set_image_pixels(&mut self, handle: usize, pixels: Vec<f64>) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ImageTransformer>(block_id) {
+            if pixels.len() != block.get_pixels().len() {
+                return Err(JsValue::from_str("pixels must have width * height values"));
+            }
+            block.set_pixels(&pixels);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an ImageTransformer"))
+        }
+    }
+
+    /// Set the active bit indices for a BitFieldSource block.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_bitfield_source_acts(source, new Uint32Array([3, 17, 512]));
+    /// ```
+    pub fn set_bitfield_source_acts(&mut self, handle: usize, acts: Vec<u32>) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<BitFieldSource>(block_id) {
+            let acts: Vec<usize> = acts.iter().map(|&a| a as usize).collect();
+            if acts.iter().any(|&a| a >= block.num_s()) {
+                return Err(JsValue::from_str("Active index must be < num_s"));
+            }
+            block.set_acts(&acts);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a BitFieldSource"))
+        }
+    }
+
+    /// Set the target value of an SdrClassifier created with a value range.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_sdr_classifier_value(readout, 42.0);
+    /// ```
+    pub fn set_sdr_classifier_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<SdrClassifier>(block_id) {
+            if block.value_range().is_none() {
+                return Err(JsValue::from_str("SdrClassifier has no value range"));
+            }
+            block.set_value(value);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an SdrClassifier"))
+        }
+    }
+
+    /// Get the predicted value of an SdrClassifier created with a value range.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const next = net.get_sdr_classifier_value(readout);
+    /// ```
+    pub fn get_sdr_classifier_value(&self, handle: usize) -> Result<f64, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<SdrClassifier>(block_id) {
+            if block.value_range().is_none() {
+                return Err(JsValue::from_str("SdrClassifier has no value range"));
+            }
+            Ok(block.get_predicted_value())
+        } else {
+            Err(JsValue::from_str("Block is not an SdrClassifier"))
+        }
+    }
+
+    /// Forget recent inputs of an SdrClassifier at a sequence boundary.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.reset_sdr_classifier(readout);
+    /// ```
+    pub fn reset_sdr_classifier(&mut self, handle: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<SdrClassifier>(block_id) {
+            block.reset();
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an SdrClassifier"))
+        }
+    }
+
+    /// Get the cluster ID of the current input of a PatternClusterer.
+    ///
+    /// Returns `undefined` if the input matched no cluster.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const id = net.get_cluster_id(clusterer);
+    /// ```
+    pub fn get_cluster_id(&self, handle: usize) -> Result<Option<usize>, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<PatternClusterer>(block_id) {
+            Ok(block.get_cluster())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClusterer"))
+        }
+    }
+
+    /// Get the number of clusters a PatternClusterer currently has.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const count = net.get_num_clusters(clusterer);
+    /// ```
+    pub fn get_num_clusters(&self, handle: usize) -> Result<usize, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<PatternClusterer>(block_id) {
+            Ok(block.num_clusters())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClusterer"))
+        }
+    }
+
+    /// Set the reward an ActionLearner received since its previous step.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_reward(agent, 1.0);
+    /// ```
+    pub fn set_reward(&mut self, handle: usize, reward: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ActionLearner>(block_id) {
+            block.set_reward(reward);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an ActionLearner"))
+        }
+    }
+
+    /// Learn the pending reward of an ActionLearner as a terminal outcome and
+    /// start a new episode.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_reward(agent, 1.0);
+    /// net.end_episode(agent);
+    /// ```
+    pub fn end_episode(&mut self, handle: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<ActionLearner>(block_id) {
+            block.end_episode();
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not an ActionLearner"))
+        }
+    }
+
+    /// Get the action an ActionLearner selected in the last step.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const action = net.get_action(agent);
+    /// ```
+    pub fn get_action(&self, handle: usize) -> Result<usize, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<ActionLearner>(block_id) {
+            Ok(block.get_action())
+        } else {
+            Err(JsValue::from_str("Block is not an ActionLearner"))
+        }
+    }
+
+    /// Get the action values an ActionLearner computed for its current input.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const values = net.get_action_values(agent); // Float64Array
+    /// ```
+    pub fn get_action_values(&self, handle: usize) -> Result<Vec<f64>, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<ActionLearner>(block_id) {
+            Ok(block.get_q_values().to_vec())
+        } else {
+            Err(JsValue::from_str("Block is not an ActionLearner"))
+        }
     }
 
-    /// Connect source block output to target block input.
+    /// Forget accumulated activity in a TemporalPooler at a sequence boundary.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.connect_to_input(encoder, pooler);
+    /// net.reset_temporal_pooler(tp);
     /// ```
-    pub fn connect_to_input(
-        &mut self,
-        source_handle: usize,
-        target_handle: usize,
-    ) -> Result<(), JsValue> {
-        if source_handle >= self.block_handles.len() {
-            return Err(JsValue::from_str("Invalid source handle"));
-        }
-        if target_handle >= self.block_handles.len() {
-            return Err(JsValue::from_str("Invalid target handle"));
+    pub fn reset_temporal_pooler(&mut self, handle: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
         }
 
-        let source_id = self.block_handles[source_handle].1;
-        let target_id = self.block_handles[target_handle].1;
-
-        self.net
-            .connect_to_input(source_id, target_id)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<TemporalPooler>(block_id) {
+            block.reset();
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a TemporalPooler"))
+        }
     }
 
-    /// Connect source block output to target block context input.
+    /// Make `symbol` share `num_shared` bits with `anchor` in a SymbolTransformer.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.connect_to_context(context_encoder, learner);
+    /// net.set_symbol_hint(words, "cat", "animal", 20);
     /// ```
-    pub fn connect_to_context(
+    pub fn set_symbol_hint(
         &mut self,
-        source_handle: usize,
-        target_handle: usize,
+        handle: usize,
+        symbol: &str,
+        anchor: &str,
+        num_shared: usize,
     ) -> Result<(), JsValue> {
-        if source_handle >= self.block_handles.len() {
-            return Err(JsValue::from_str("Invalid source handle"));
-        }
-        if target_handle >= self.block_handles.len() {
-            return Err(JsValue::from_str("Invalid target handle"));
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
         }
 
-        let source_id = self.block_handles[source_handle].1;
-        let target_id = self.block_handles[target_handle].1;
-
-        self.net
-            .connect_to_context(source_id, target_id)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<SymbolTransformer>(block_id) {
+            if num_shared > block.num_as() {
+                return Err(JsValue::from_str("num_shared must be <= num_as"));
+            }
+            block.set_hint(
+                SymbolTransformer::key(symbol),
+                SymbolTransformer::key(anchor),
+                num_shared,
+            );
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a SymbolTransformer"))
+        }
     }
 
-    /// Build the network (compute execution order).
-    ///
-    /// Must be called after adding all blocks and connections, before execute().
+    /// Set label for a PatternClassifier or SdrClassifier block.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.build();
+    /// net.set_classifier_label(classifier, 2);
     /// ```
-    pub fn build(&mut self) -> Result<(), JsValue> {
-        self.net
-            .build()
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    pub fn set_classifier_label(&mut self, handle: usize, label: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            block.set_label(label);
+            Ok(())
+        } else if let Ok(block) = self.net.get_mut::<SdrClassifier>(block_id) {
+            block.set_label(label);
+            Ok(())
+        } else {
+            Err(JsValue::from_str(
+                "Block is not a PatternClassifier or SdrClassifier",
+            ))
+        }
     }
 
-    /// Initialize a learning block (allocates memory structures).
-    ///
-    /// Must be called for PatternPooler, PatternClassifier, SequenceLearner,
-    /// and ContextLearner blocks before execution.
+    /// Set several labels of a PatternClassifier for multi-label learning.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.init_block(pooler);
-    /// net.init_block(learner);
+    /// net.set_classifier_labels(classifier, [0, 2]);
     /// ```
-    pub fn init_block(&mut self, handle: usize) -> Result<(), JsValue> {
+    pub fn set_classifier_labels(&mut self, handle: usize, labels: Vec<usize>) -> Result<(), JsValue> {
         if handle >= self.block_handles.len() {
             return Err(JsValue::from_str("Invalid block handle"));
         }
 
         let block_id = self.block_handles[handle].1;
-
-        // Try each block type that needs initialization
-        if let Ok(block) = self.net.get_mut::<PatternPooler>(block_id) {
-            return block
-                .init()
-                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
-        }
         if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
-            return block
-                .init()
-                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
-        }
-        if let Ok(block) = self.net.get_mut::<SequenceLearner>(block_id) {
-            return block
-                .init()
-                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
-        }
-        if let Ok(block) = self.net.get_mut::<ContextLearner>(block_id) {
-            return block
-                .init()
-                .map_err(|e| JsValue::from_str(&format!("{:?}", e)));
+            if labels.iter().any(|&l| l >= block.num_l()) {
+                return Err(JsValue::from_str("label must be < num_l"));
+            }
+            block.set_labels(&labels);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
         }
-
-        // Transformers don't need initialization
-        Ok(())
     }
 
-    /// Start recording execution for visualization.
+    /// Get every label whose score reaches its threshold in a PatternClassifier.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.start_recording();
+    /// const tags = net.get_classifier_predicted_labels(classifier);
     /// ```
-    pub fn start_recording(&mut self) {
-        self.net.start_recording();
+    pub fn get_classifier_predicted_labels(&self, handle: usize) -> Result<Vec<usize>, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<PatternClassifier>(block_id) {
+            Ok(block.predict_labels())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
+        }
     }
 
-    /// Execute the network one step.
-    ///
-    /// # Arguments
-    /// * `learn` - Whether to enable learning
+    /// Set the multi-label score threshold of one PatternClassifier label.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.execute(true);  // with learning
-    /// net.execute(false); // inference only
+    /// net.set_classifier_label_threshold(classifier, 2, 0.6);
     /// ```
-    pub fn execute(&mut self, learn: bool) -> Result<(), JsValue> {
-        self.net
-            .execute(learn)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    pub fn set_classifier_label_threshold(
+        &mut self,
+        handle: usize,
+        label: usize,
+        threshold: f64,
+    ) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(JsValue::from_str("label threshold must be in [0, 1]"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            if label >= block.num_l() {
+                return Err(JsValue::from_str("label must be < num_l"));
+            }
+            block.set_label_threshold(label, threshold);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
+        }
     }
 
-    /// Get current execution trace as JSON string.
-    ///
-    /// This stops and restarts recording to get a snapshot.
-    /// Returns null if recording was not started.
+    /// Clear the label of a PatternClassifier or SdrClassifier so following steps are unlabeled.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// const traceJson = net.get_trace_json();
-    /// if (traceJson) {
-    ///     const trace = JSON.parse(traceJson);
-    ///     // Update visualization
-    /// }
+    /// net.clear_classifier_label(classifier);
     /// ```
-    pub fn get_trace_json(&mut self) -> Option<String> {
-        if let Some(trace) = self.net.stop_recording() {
-            let json = trace.to_json().ok()?;
-            // Restart recording for next batch
-            self.net.start_recording();
-            Some(json)
+    pub fn clear_classifier_label(&mut self, handle: usize) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            block.clear_label();
+            Ok(())
+        } else if let Ok(block) = self.net.get_mut::<SdrClassifier>(block_id) {
+            block.clear_label();
+            Ok(())
         } else {
-            None
+            Err(JsValue::from_str(
+                "Block is not a PatternClassifier or SdrClassifier",
+            ))
         }
     }
 
-    /// Set value for a ScalarTransformer block.
+    /// Add a named label to a PatternClassifier and return its index.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.set_scalar_value(encoder, 42.5);
+    /// const bird = net.add_classifier_label(classifier, "bird");
     /// ```
-    pub fn set_scalar_value(&mut self, handle: usize, value: f64) -> Result<(), JsValue> {
+    pub fn add_classifier_label(&mut self, handle: usize, name: &str) -> Result<usize, JsValue> {
         if handle >= self.block_handles.len() {
             return Err(JsValue::from_str("Invalid block handle"));
         }
 
         let block_id = self.block_handles[handle].1;
-        if let Ok(block) = self.net.get_mut::<ScalarTransformer>(block_id) {
-            block.set_value(value);
-            Ok(())
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            if block.label_index(name).is_some() {
+                return Err(JsValue::from_str("Label name already exists"));
+            }
+            block
+                .add_label(name)
+                .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
         } else {
-            Err(JsValue::from_str("Block is not a ScalarTransformer"))
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
         }
     }
 
-    /// Set value for a DiscreteTransformer block.
+    /// Set the label of a PatternClassifier by name.
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.set_discrete_value(encoder, 3);
+    /// net.set_classifier_label_by_name(classifier, "bird");
     /// ```
-    pub fn set_discrete_value(&mut self, handle: usize, value: usize) -> Result<(), JsValue> {
+    pub fn set_classifier_label_by_name(&mut self, handle: usize, name: &str) -> Result<(), JsValue> {
         if handle >= self.block_handles.len() {
             return Err(JsValue::from_str("Invalid block handle"));
         }
 
         let block_id = self.block_handles[handle].1;
-        if let Ok(block) = self.net.get_mut::<DiscreteTransformer>(block_id) {
-            block.set_value(value);
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            if block.label_index(name).is_none() {
+                return Err(JsValue::from_str("Unknown label name"));
+            }
+            block.set_label_by_name(name);
             Ok(())
         } else {
-            Err(JsValue::from_str("Block is not a DiscreteTransformer"))
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
         }
     }
 
-    /// Set label for a PatternClassifier block.
+    /// Get the label names of a PatternClassifier (one per label, by index).
     ///
     /// # Example (JavaScript)
     /// ```javascript
-    /// net.set_classifier_label(classifier, 2);
+    /// const names = net.get_classifier_label_names(classifier);
     /// ```
-    pub fn set_classifier_label(&mut self, handle: usize, label: usize) -> Result<(), JsValue> {
+    pub fn get_classifier_label_names(&self, handle: usize) -> Result<Vec<String>, JsValue> {
         if handle >= self.block_handles.len() {
             return Err(JsValue::from_str("Invalid block handle"));
         }
 
         let block_id = self.block_handles[handle].1;
-        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
-            block.set_label(label);
-            Ok(())
+        if let Ok(block) = self.net.get::<PatternClassifier>(block_id) {
+            Ok(block.label_names().to_vec())
         } else {
             Err(JsValue::from_str("Block is not a PatternClassifier"))
         }
@@ -539,7 +1806,7 @@ impl WasmNetwork {
         ))
     }
 
-    /// Get classification probabilities from a PatternClassifier.
+    /// Get classification probabilities from a PatternClassifier or SdrClassifier.
     ///
     /// Returns an array of probabilities (one per label).
     ///
@@ -556,6 +1823,97 @@ impl WasmNetwork {
         let block_id = self.block_handles[handle].1;
         if let Ok(block) = self.net.get::<PatternClassifier>(block_id) {
             Ok(block.get_probabilities())
+        } else if let Ok(block) = self.net.get::<SdrClassifier>(block_id) {
+            Ok(block.get_probabilities().to_vec())
+        } else {
+            Err(JsValue::from_str(
+                "Block is not a PatternClassifier or SdrClassifier",
+            ))
+        }
+    }
+
+    /// Get calibrated confidences from a PatternClassifier.
+    ///
+    /// Returns an array of confidences (one per label) that sum to 1.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const conf = net.get_confidences(classifier);
+    /// ```
+    pub fn get_confidences(&self, handle: usize) -> Result<Vec<f64>, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<PatternClassifier>(block_id) {
+            Ok(block.get_confidences())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
+        }
+    }
+
+    /// Get the predicted label of a PatternClassifier, or undefined if the input is unknown.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// const label = net.get_classifier_prediction(classifier);
+    /// if (label === undefined) console.log("Novel input");
+    /// ```
+    pub fn get_classifier_prediction(&self, handle: usize) -> Result<Option<usize>, JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get::<PatternClassifier>(block_id) {
+            Ok(block.predict())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
+        }
+    }
+
+    /// Set the softmax temperature of a PatternClassifier.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_classifier_temperature(classifier, 0.05);
+    /// ```
+    pub fn set_classifier_temperature(&mut self, handle: usize, temperature: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+        if temperature <= 0.0 {
+            return Err(JsValue::from_str("temperature must be > 0"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            block.set_temperature(temperature);
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Block is not a PatternClassifier"))
+        }
+    }
+
+    /// Set the minimum calibrated score for a PatternClassifier to predict a known label.
+    ///
+    /// # Example (JavaScript)
+    /// ```javascript
+    /// net.set_classifier_unknown_threshold(classifier, 0.8);
+    /// ```
+    pub fn set_classifier_unknown_threshold(&mut self, handle: usize, threshold: f64) -> Result<(), JsValue> {
+        if handle >= self.block_handles.len() {
+            return Err(JsValue::from_str("Invalid block handle"));
+        }
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(JsValue::from_str("unknown threshold must be in [0, 1]"));
+        }
+
+        let block_id = self.block_handles[handle].1;
+        if let Ok(block) = self.net.get_mut::<PatternClassifier>(block_id) {
+            block.set_unknown_threshold(threshold);
+            Ok(())
         } else {
             Err(JsValue::from_str("Block is not a PatternClassifier"))
         }
@@ -734,14 +2092,40 @@ impl WasmNetwork {
                 "DiscreteTransformer"
             } else if self.net.get::<PersistenceTransformer>(*block_id).is_ok() {
                 "PersistenceTransformer"
+            } else if self.net.get::<DeltaTransformer>(*block_id).is_ok() {
+                "DeltaTransformer"
+            } else if self.net.get::<RdseTransformer>(*block_id).is_ok() {
+                "RdseTransformer"
+            } else if self.net.get::<CyclicTransformer>(*block_id).is_ok() {
+                "CyclicTransformer"
+            } else if self.net.get::<DateTimeTransformer>(*block_id).is_ok() {
+                "DateTimeTransformer"
+            } else if self.net.get::<HyperGridTransformer>(*block_id).is_ok() {
+                "HyperGridTransformer"
+            } else if self.net.get::<SymbolTransformer>(*block_id).is_ok() {
+                "SymbolTransformer"
+            } else if self.net.get::<ImageTransformer>(*block_id).is_ok() {
+                "ImageTransformer"
+            } else if self.net.get::<BitFieldSource>(*block_id).is_ok() {
+                "BitFieldSource"
             } else if self.net.get::<PatternPooler>(*block_id).is_ok() {
                 "PatternPooler"
             } else if self.net.get::<PatternClassifier>(*block_id).is_ok() {
                 "PatternClassifier"
+            } else if self.net.get::<SdrClassifier>(*block_id).is_ok() {
+                "SdrClassifier"
+            } else if self.net.get::<PatternClusterer>(*block_id).is_ok() {
+                "PatternClusterer"
+            } else if self.net.get::<ActionLearner>(*block_id).is_ok() {
+                "ActionLearner"
             } else if self.net.get::<SequenceLearner>(*block_id).is_ok() {
                 "SequenceLearner"
             } else if self.net.get::<ContextLearner>(*block_id).is_ok() {
                 "ContextLearner"
+            } else if self.net.get::<TemporalPooler>(*block_id).is_ok() {
+                "TemporalPooler"
+            } else if self.net.get::<DelayLine>(*block_id).is_ok() {
+                "DelayLine"
             } else {
                 "Unknown"
             };
@@ -0,0 +1,259 @@
+//! Comprehensive tests for ActionLearner.
+//!
+//! Tests cover:
+//! - Construction and parameter validation
+//! - Learning a shortest path in a small gridworld from a goal reward
+//! - Determinism for a given seed
+//! - Action output compatible with DiscreteTransformer
+//! - State values with a single action (TD(λ))
+//! - Network serialization of config and learned weights
+
+use gnomics::{
+    blocks::{ActionLearner, DiscreteTransformer},
+    Block, BlockId, InputAccess, Network, OutputAccess, Result,
+};
+
+const UP: usize = 0;
+const DOWN: usize = 1;
+const LEFT: usize = 2;
+const RIGHT: usize = 3;
+
+/// Square gridworld: start in one corner, reward 1.0 for reaching the opposite one.
+struct GridWorld {
+    size: usize,
+    pos: (usize, usize),
+}
+
+impl GridWorld {
+    fn new(size: usize) -> Self {
+        Self { size, pos: (0, 0) }
+    }
+
+    fn reset(&mut self) {
+        self.pos = (0, 0);
+    }
+
+    /// Cell index of the agent, used as the encoded state.
+    fn state(&self) -> usize {
+        self.pos.1 * self.size + self.pos.0
+    }
+
+    /// Move the agent (walls block moves) and return `(reward, done)`.
+    fn step(&mut self, action: usize) -> (f64, bool) {
+        let (x, y) = self.pos;
+        self.pos = match action {
+            UP => (x, y.saturating_sub(1)),
+            DOWN => (x, (y + 1).min(self.size - 1)),
+            LEFT => (x.saturating_sub(1), y),
+            RIGHT => ((x + 1).min(self.size - 1), y),
+            _ => unreachable!(),
+        };
+        if self.pos == (self.size - 1, self.size - 1) {
+            (1.0, true)
+        } else {
+            (0.0, false)
+        }
+    }
+}
+
+/// Gridworld cells encoded by a DiscreteTransformer and fed to an ActionLearner.
+fn grid_agent(size: usize, seed: u64) -> Result<(Network, BlockId, BlockId)> {
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(size * size, size * size * 8, 2, 0));
+    let agent = net.add(ActionLearner::new(4, 64, 0.5, 0.9, 0.8, 0.1, 2, seed));
+    net.connect_to_input(encoder, agent)?;
+    net.build()?;
+    net.get_mut::<ActionLearner>(agent)?.init()?;
+    Ok((net, encoder, agent))
+}
+
+/// Run one episode and return the number of steps taken (capped at `max_steps`).
+fn run_episode(
+    net: &mut Network,
+    encoder: BlockId,
+    agent: BlockId,
+    world: &mut GridWorld,
+    learn: bool,
+    max_steps: usize,
+) -> Result<usize> {
+    world.reset();
+    for step in 1..=max_steps {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(world.state());
+        net.execute(learn)?;
+
+        let action = net.get::<ActionLearner>(agent)?.get_action();
+        let (reward, done) = world.step(action);
+        let learner = net.get_mut::<ActionLearner>(agent)?;
+        learner.set_reward(reward);
+        if done {
+            if learn {
+                learner.end_episode();
+            }
+            return Ok(step);
+        }
+    }
+    Ok(max_steps)
+}
+
+fn train(seed: u64, episodes: usize) -> Result<(Network, BlockId, BlockId, Vec<usize>)> {
+    let (mut net, encoder, agent) = grid_agent(5, seed)?;
+    let mut world = GridWorld::new(5);
+    let lengths = (0..episodes)
+        .map(|_| run_episode(&mut net, encoder, agent, &mut world, true, 500))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((net, encoder, agent, lengths))
+}
+
+#[test]
+fn test_action_learner_basic_construction() {
+    let agent = ActionLearner::new(4, 64, 0.1, 0.9, 0.8, 0.1, 2, 0);
+    assert_eq!(agent.num_a(), 4);
+    assert_eq!(agent.num_s(), 64);
+    assert_eq!(agent.num_as(), 16);
+    assert_eq!(agent.gamma(), 0.9);
+    assert_eq!(agent.lambda(), 0.8);
+    assert_eq!(agent.epsilon(), 0.1);
+    assert_eq!(agent.output().borrow().state.num_bits(), 64);
+}
+
+#[test]
+#[should_panic(expected = "gamma must be in [0.0, 1.0]")]
+fn test_action_learner_invalid_gamma() {
+    ActionLearner::new(4, 64, 0.1, 1.5, 0.8, 0.1, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "num_s must be >= num_a")]
+fn test_action_learner_invalid_num_s() {
+    ActionLearner::new(4, 2, 0.1, 0.9, 0.8, 0.1, 2, 0);
+}
+
+#[test]
+fn test_action_learner_gridworld_shortest_path() -> Result<()> {
+    let (mut net, encoder, agent, lengths) = train(0, 200)?;
+
+    // Exploring episodes shorten as the goal reward propagates back
+    let early: usize = lengths[..10].iter().sum();
+    let late: usize = lengths[190..].iter().sum();
+    assert!(late < early, "late {} >= early {}", late, early);
+
+    // The greedy policy walks a shortest path (4 right, 4 down)
+    net.get_mut::<ActionLearner>(agent)?.set_epsilon(0.0);
+    let mut world = GridWorld::new(5);
+    let steps = run_episode(&mut net, encoder, agent, &mut world, false, 50)?;
+    assert_eq!(steps, 8);
+
+    // Values rise along the learned path towards the goal
+    let mut value_at = |cell: usize| -> Result<f64> {
+        net.get_mut::<DiscreteTransformer>(encoder)?.set_value(cell);
+        net.execute(false)?;
+        Ok(net.get::<ActionLearner>(agent)?.get_value())
+    };
+    assert!(value_at(19)? > value_at(12)?);
+    assert!(value_at(12)? > value_at(0)?);
+    assert!(value_at(0)? > 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_action_learner_deterministic_for_seed() -> Result<()> {
+    let (net_a, _, agent_a, lengths_a) = train(7, 30)?;
+    let (net_b, _, agent_b, lengths_b) = train(7, 30)?;
+    let (net_c, _, agent_c, lengths_c) = train(8, 30)?;
+
+    assert_eq!(lengths_a, lengths_b);
+    assert_eq!(
+        net_a.get::<ActionLearner>(agent_a)?.weights(),
+        net_b.get::<ActionLearner>(agent_b)?.weights()
+    );
+    assert!(
+        lengths_a != lengths_c
+            || net_a.get::<ActionLearner>(agent_a)?.weights()
+                != net_c.get::<ActionLearner>(agent_c)?.weights()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_action_learner_output_decodes_as_discrete_value() {
+    let mut state = DiscreteTransformer::new(4, 64, 2, 0);
+    let mut agent = ActionLearner::new(4, 64, 0.1, 0.9, 0.0, 1.0, 2, 3);
+    agent.input_mut().add_child(state.output(), 0);
+    agent.init().unwrap();
+
+    let decoder = DiscreteTransformer::new(4, 64, 2, 0);
+    for value in 0..8 {
+        state.set_value(value % 4);
+        state.execute(false).unwrap();
+        agent.execute(false).unwrap();
+
+        let acts = agent.output().borrow().state.get_acts();
+        assert_eq!(decoder.decode_values(&acts), vec![agent.get_action()]);
+    }
+}
+
+#[test]
+fn test_action_learner_state_values() {
+    // A 5-state chain walked left to right, rewarded at the end
+    let mut state = DiscreteTransformer::new(5, 80, 2, 0);
+    let mut critic = ActionLearner::new(1, 16, 0.2, 0.9, 0.5, 0.0, 2, 0);
+    critic.input_mut().add_child(state.output(), 0);
+    critic.init().unwrap();
+
+    for _ in 0..200 {
+        for s in 0..5 {
+            state.set_value(s);
+            state.execute(false).unwrap();
+            critic.execute(true).unwrap();
+        }
+        critic.set_reward(1.0);
+        critic.end_episode();
+    }
+
+    let values: Vec<f64> = (0..5)
+        .map(|s| {
+            state.set_value(s);
+            state.execute(false).unwrap();
+            critic.execute(false).unwrap();
+            critic.get_value()
+        })
+        .collect();
+
+    // V(s) approaches gamma^(steps to go)
+    for (s, &v) in values.iter().enumerate() {
+        let expected = 0.9f64.powi(4 - s as i32);
+        assert!((v - expected).abs() < 0.05, "V({}) = {}", s, v);
+    }
+}
+
+#[test]
+fn test_action_learner_network_state_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let (net, _, agent, _) = train(0, 20)?;
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<ActionLearner>(id).is_ok())
+        .unwrap();
+
+    let original = net.get::<ActionLearner>(agent)?;
+    let restored = loaded.get::<ActionLearner>(restored)?;
+    assert_eq!(restored.num_a(), 4);
+    assert_eq!(restored.gamma(), 0.9);
+    assert_eq!(restored.lambda(), 0.8);
+    // JSON keeps weights to within float parsing precision
+    assert_eq!(restored.weights().len(), original.weights().len());
+    assert!(restored
+        .weights()
+        .iter()
+        .zip(original.weights())
+        .all(|(x, y)| (x - y).abs() < 1e-12));
+
+    Ok(())
+}
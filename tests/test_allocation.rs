@@ -0,0 +1,275 @@
+//! Allocation tests for the Network execution hot path.
+//!
+//! Uses a counting global allocator to verify that a warmed-up network
+//! performs zero heap allocations per `execute()` call.
+
+use gnomics::{
+    blocks::{
+        ActionLearner, ContextLearner, DelayLine, DiscreteTransformer, PatternClassifier,
+        PatternClusterer, PatternPooler, ScalarTransformer, SdrClassifier, SequenceLearner,
+    },
+    Block, Network, Result,
+};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Global allocator that counts allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Count allocations performed by the current thread while running `f`.
+fn count_allocations<F: FnMut() -> Result<()>>(mut f: F) -> Result<usize> {
+    let before = ALLOCATIONS.with(|n| n.get());
+    f()?;
+    Ok(ALLOCATIONS.with(|n| n.get()) - before)
+}
+
+/// Run `step` for `warmup` steps, then assert the next `steps` allocate nothing.
+///
+/// `step` receives the network and the step index, counting on from the warm-up.
+fn assert_execute_allocation_free<F>(
+    net: &mut Network,
+    warmup: usize,
+    steps: usize,
+    mut step: F,
+) -> Result<()>
+where
+    F: FnMut(&mut Network, usize) -> Result<()>,
+{
+    for i in 0..warmup {
+        step(net, i)?;
+    }
+
+    let allocations = count_allocations(|| {
+        for i in warmup..warmup + steps {
+            step(net, i)?;
+        }
+        Ok(())
+    })?;
+
+    assert_eq!(allocations, 0, "steady-state execute() allocated");
+
+    Ok(())
+}
+
+#[test]
+fn test_execute_pooler_classifier_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let encoder = net.add(ScalarTransformer::new(0.0, 10.0, 1024, 128, 2, 0));
+    let pooler = net.add(PatternPooler::new(
+        1024, 40, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    let classifier = net.add(PatternClassifier::new(
+        4, 1024, 16, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0,
+    ));
+
+    net.connect_to_input(encoder, pooler)?;
+    net.connect_to_input(pooler, classifier)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+    net.get_mut::<PatternClassifier>(classifier)?.init()?;
+
+    let values = [1.0, 3.5, 6.0, 8.5];
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<ScalarTransformer>(encoder)?
+            .set_value(values[i % values.len()]);
+        net.get_mut::<PatternClassifier>(classifier)?
+            .set_label(i % values.len());
+        net.execute(true)
+    };
+
+    // Warm up: grow scratch buffers and let learning settle
+    assert_execute_allocation_free(&mut net, 100, 100, step)
+}
+
+#[test]
+fn test_execute_sequence_context_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let input_enc = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let context_enc = net.add(DiscreteTransformer::new(4, 128, 2, 0));
+    let seq = net.add(SequenceLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+    let ctx = net.add(ContextLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+
+    net.connect_to_input(input_enc, seq)?;
+    net.connect_to_input(input_enc, ctx)?;
+    net.connect_to_context(context_enc, ctx)?;
+    net.get_mut::<SequenceLearner>(seq)?.init()?;
+    net.get_mut::<ContextLearner>(ctx)?.init()?;
+    net.build()?;
+
+    let sequence = [0, 1, 2, 3, 4, 5, 6, 7];
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(input_enc)?
+            .set_value(sequence[i % sequence.len()]);
+        net.get_mut::<DiscreteTransformer>(context_enc)?
+            .set_value(i % 4);
+        net.execute(true)
+    };
+
+    // Warm up: learn the sequence so no new dendrites are needed
+    assert_execute_allocation_free(&mut net, 400, sequence.len() * 10, step)
+}
+
+#[test]
+fn test_execute_context_apical_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let input_enc = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let context_enc = net.add(DiscreteTransformer::new(4, 128, 2, 0));
+    let ctx = net.add(ContextLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+    let apical_enc = net.add(DiscreteTransformer::new(2, 128, 2, 0));
+
+    net.connect_to_input(input_enc, ctx)?;
+    net.connect_to_context(context_enc, ctx)?;
+    net.connect_to_apical(apical_enc, ctx)?;
+    net.build()?;
+    net.get_mut::<ContextLearner>(ctx)?.init()?;
+
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(input_enc)?
+            .set_value(i % 8);
+        net.get_mut::<DiscreteTransformer>(context_enc)?
+            .set_value(i % 4);
+        net.get_mut::<DiscreteTransformer>(apical_enc)?
+            .set_value(i % 2);
+        net.execute(true)
+    };
+
+    // Warm up: learn the associations so no new dendrites are needed
+    assert_execute_allocation_free(&mut net, 400, 80, step)
+}
+
+#[test]
+fn test_execute_sdr_classifier_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let encoder = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let seq = net.add(SequenceLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+    let readout = net.add(SdrClassifier::new(8, 2, 0.1, 2, 0).with_value_range(0.0, 8.0));
+
+    net.connect_to_input(encoder, seq)?;
+    net.connect_to_input(seq, readout)?;
+    net.build()?;
+    net.get_mut::<SequenceLearner>(seq)?.init()?;
+    net.get_mut::<SdrClassifier>(readout)?.init()?;
+
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(i % 8);
+        net.get_mut::<SdrClassifier>(readout)?
+            .set_value((i % 8) as f64);
+        net.execute(true)
+    };
+
+    // Warm up: learn the sequence and fill the readout's input history
+    assert_execute_allocation_free(&mut net, 400, 80, step)
+}
+
+#[test]
+fn test_execute_pattern_clusterer_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let encoder = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let clusterer = net.add(
+        PatternClusterer::new(16, 8, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0).with_maintenance_period(10),
+    );
+
+    net.connect_to_input(encoder, clusterer)?;
+    net.build()?;
+    net.get_mut::<PatternClusterer>(clusterer)?.init()?;
+
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(i % 8);
+        net.execute(true)
+    };
+
+    // Warm up: discover every cluster
+    assert_execute_allocation_free(&mut net, 40, 80, step)
+}
+
+#[test]
+fn test_execute_action_learner_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let encoder = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let agent = net.add(ActionLearner::new(4, 64, 0.1, 0.9, 0.8, 0.1, 2, 0));
+
+    net.connect_to_input(encoder, agent)?;
+    net.build()?;
+    net.get_mut::<ActionLearner>(agent)?.init()?;
+
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(i % 8);
+        net.get_mut::<ActionLearner>(agent)?
+            .set_reward(if i % 8 == 7 { 1.0 } else { 0.0 });
+        net.execute(true)
+    };
+
+    // Warm up: fill the eligibility traces
+    assert_execute_allocation_free(&mut net, 80, 80, step)
+}
+
+#[test]
+fn test_execute_delay_line_allocation_free() -> Result<()> {
+    let mut net = Network::new();
+
+    let encoder = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let delay = net.add(DelayLine::new(256, 1, 2, 0).with_lags(&[0, 1, 4]));
+    let readout = net.add(SdrClassifier::new(8, 0, 0.1, 2, 0));
+
+    net.connect_to_input(encoder, delay)?;
+    net.connect_to_input(delay, readout)?;
+    net.build()?;
+    net.get_mut::<DelayLine>(delay)?.init()?;
+    net.get_mut::<SdrClassifier>(readout)?.init()?;
+
+    let step = |net: &mut Network, i: usize| -> Result<()> {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(i % 8);
+        net.get_mut::<SdrClassifier>(readout)?
+            .set_label((i + 4) % 8);
+        net.execute(true)
+    };
+
+    // Warm up
+    assert_execute_allocation_free(&mut net, 16, 80, step)
+}
@@ -3,7 +3,7 @@
 //! These tests match the behavior demonstrated in the C++ test file
 //! (tests/cpp/test_bitfield.cpp) and add additional property-based tests.
 
-use gnomics::{bitfield_copy_words, BitField};
+use gnomics::{bitfield_copy_words, BitField, BITS_PER_WORD};
 use proptest::prelude::*;
 use rand::SeedableRng;
 
@@ -15,7 +15,7 @@ use rand::SeedableRng;
 fn test_construction() {
     let ba = BitField::new(1024);
     assert_eq!(ba.num_bits(), 1024);
-    assert_eq!(ba.num_words(), 32);
+    assert_eq!(ba.num_words(), 1024 / BITS_PER_WORD);
     assert_eq!(ba.num_set(), 0);
 }
 
@@ -235,6 +235,45 @@ fn test_find_next_set_bit_range() {
     assert!(next_bit >= 4 && next_bit < 12);
 }
 
+#[test]
+fn test_find_next_set_bit_range_within_word() {
+    let mut ba = BitField::new(1024);
+    ba.set_bit(2);
+    ba.set_bit(20);
+
+    // Range [4, 12) lies inside the first word and must not report bit 20 or 2
+    assert_eq!(ba.find_next_set_bit_range(4, 8), None);
+    assert_eq!(ba.find_next_set_bit_range(4, 17), Some(20));
+}
+
+#[test]
+fn test_find_next_set_bit_range_across_word_boundary() {
+    let beg = BITS_PER_WORD - 2;
+
+    // Range [beg, beg + 5) spills 3 bits into the second word
+    let mut ba = BitField::new(1024);
+    ba.set_bit(BITS_PER_WORD + 1);
+    assert_eq!(ba.find_next_set_bit_range(beg, 5), Some(BITS_PER_WORD + 1));
+
+    // Bit 1 lies in the first word but outside the range
+    let mut ba = BitField::new(1024);
+    ba.set_bit(1);
+    assert_eq!(ba.find_next_set_bit_range(beg, 5), None);
+}
+
+#[test]
+fn test_find_next_set_bit_range_wrapped() {
+    // [20, 50) on a 40-bit field covers 20..40 then 0..10
+    let mut ba = BitField::new(40);
+    ba.set_bit(35);
+    assert_eq!(ba.find_next_set_bit_range(20, 30), Some(35));
+
+    let mut ba = BitField::new(40);
+    ba.set_bit(5);
+    assert_eq!(ba.find_next_set_bit_range(20, 30), Some(5));
+    assert_eq!(ba.find_next_set_bit_range(20, 20), None);
+}
+
 // =============================================================================
 // Random Operations
 // =============================================================================
@@ -390,6 +429,7 @@ fn test_equality_performance() {
 // =============================================================================
 
 #[test]
+#[cfg(not(feature = "word64"))] // Asserts the 32-bit word layout
 fn test_word_access() {
     let mut ba = BitField::new(128);
     ba.set_range(0, 64);
@@ -415,12 +455,12 @@ fn test_bitfield_copy_words() {
 
 #[test]
 fn test_bitfield_copy_words_multiple() {
-    let mut src1 = BitField::new(64);
-    let mut src2 = BitField::new(64);
-    let mut dst = BitField::new(256);
+    let mut src1 = BitField::new(2 * BITS_PER_WORD);
+    let mut src2 = BitField::new(2 * BITS_PER_WORD);
+    let mut dst = BitField::new(4 * BITS_PER_WORD);
 
-    src1.set_range(0, 32);
-    src2.set_range(0, 32);
+    src1.set_range(0, BITS_PER_WORD);
+    src2.set_range(0, BITS_PER_WORD);
 
     // Copy src1 to words 0-1
     bitfield_copy_words(&mut dst, &src1, 0, 0, 2);
@@ -447,6 +487,7 @@ fn test_memory_usage() {
 }
 
 #[test]
+#[cfg(not(feature = "word64"))] // Asserts the 32-bit word layout
 fn test_num_words() {
     let ba = BitField::new(1024);
     assert_eq!(ba.num_words(), 32);
@@ -495,6 +536,25 @@ fn test_cross_word_boundary() {
 // =============================================================================
 
 proptest! {
+    #[test]
+    fn prop_find_next_set_bit_range_matches_reference(
+        n in 1..300usize,
+        seed in any::<u64>(),
+        beg_frac in 0.0..1.0f64,
+        len_frac in 0.0..1.0f64,
+        pct in 0.0..0.1f64,
+    ) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut ba = BitField::new(n);
+        ba.random_set_pct(&mut rng, pct);
+
+        let beg = ((beg_frac * n as f64) as usize).min(n - 1);
+        let len = ((len_frac * n as f64) as usize).clamp(1, n);
+        let expected = (0..len).map(|i| (beg + i) % n).find(|&i| ba.get_bit(i) == 1);
+
+        prop_assert_eq!(ba.find_next_set_bit_range(beg, len), expected);
+    }
+
     #[test]
     fn prop_set_get_consistency(bits in prop::collection::vec(any::<bool>(), 1..1000)) {
         let mut ba = BitField::new(bits.len());
@@ -518,6 +578,22 @@ proptest! {
         prop_assert_eq!(ba.num_set(), ba.get_acts().len());
     }
 
+    #[test]
+    fn prop_num_similar_matches_acts(n in 1..3000usize, seed in any::<u64>()) {
+        // Odd sizes exercise the scalar tails of the SIMD kernels
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut ba1 = BitField::new(n);
+        let mut ba2 = BitField::new(n);
+        ba1.random_set_pct(&mut rng, 0.3);
+        ba2.random_set_pct(&mut rng, 0.3);
+
+        let acts2 = ba2.get_acts();
+        let expected = ba1.get_acts().iter().filter(|a| acts2.contains(a)).count();
+
+        prop_assert_eq!(ba1.num_similar(&ba2), expected);
+        prop_assert_eq!((&ba1 & &ba2).num_set(), expected);
+    }
+
     #[test]
     fn prop_clear_all_zeros(n in 1..2000usize) {
         let mut ba = BitField::new(n);
@@ -0,0 +1,154 @@
+//! Comprehensive tests for BitFieldSource.
+//!
+//! Tests cover:
+//! - Setting patterns from BitFields and active indices
+//! - Persistence and change detection across steps
+//! - Driving downstream learners
+//! - Network serialization of config
+//! - Execution recording
+
+use gnomics::{
+    blocks::{BitFieldSource, PatternClassifier},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+#[test]
+fn test_source_basic_construction() {
+    let source = BitFieldSource::new(512, 3, 0);
+    assert_eq!(source.num_s(), 512);
+    assert_eq!(source.output().borrow().num_t(), 3);
+    assert_eq!(source.output().borrow().state.num_bits(), 512);
+}
+
+#[test]
+#[should_panic(expected = "num_t must be at least 2")]
+fn test_source_invalid_num_t() {
+    BitFieldSource::new(512, 1, 0);
+}
+
+#[test]
+fn test_source_set_state() {
+    let mut source = BitFieldSource::new(256, 2, 0);
+    let mut bf = BitField::new(256);
+    bf.set_range(100, 20);
+
+    source.set_state(&bf);
+    source.execute(false).unwrap();
+    assert_eq!(source.output().borrow().state, bf);
+}
+
+#[test]
+fn test_source_pattern_persists() {
+    let mut source = BitFieldSource::new(256, 2, 0);
+    source.set_acts(&[0, 128, 255]);
+    source.execute(false).unwrap();
+    assert!(source.output().borrow().has_changed());
+
+    // Pattern holds until replaced
+    source.execute(false).unwrap();
+    assert!(!source.output().borrow().has_changed());
+    assert_eq!(source.output().borrow().state.get_acts(), vec![0, 128, 255]);
+
+    source.set_acts(&[]);
+    source.execute(false).unwrap();
+    assert!(source.output().borrow().has_changed());
+    assert_eq!(source.output().borrow().state.num_set(), 0);
+}
+
+#[test]
+fn test_source_clear() {
+    let mut source = BitFieldSource::new(64, 2, 0);
+    source.set_acts(&[5, 6]);
+    source.execute(false).unwrap();
+
+    source.clear();
+    assert_eq!(source.get_state().num_set(), 0);
+    assert_eq!(source.output().borrow().state.num_set(), 0);
+}
+
+#[test]
+fn test_source_drives_classifier() -> Result<()> {
+    let mut net = Network::new();
+    let source = net.add(BitFieldSource::new(256, 2, 0));
+    let classifier = net.add(PatternClassifier::new(
+        2, 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0,
+    ));
+    net.connect_to_input(source, classifier)?;
+    net.build()?;
+    net.get_mut::<PatternClassifier>(classifier)?.init()?;
+
+    let patterns: [Vec<usize>; 2] = [(0..40).collect(), (200..240).collect()];
+    for _ in 0..10 {
+        for (label, acts) in patterns.iter().enumerate() {
+            net.get_mut::<BitFieldSource>(source)?.set_acts(acts);
+            net.get_mut::<PatternClassifier>(classifier)?
+                .set_label(label);
+            net.execute(true)?;
+        }
+    }
+
+    for (label, acts) in patterns.iter().enumerate() {
+        net.get_mut::<BitFieldSource>(source)?.set_acts(acts);
+        net.execute(false)?;
+        assert_eq!(
+            net.get::<PatternClassifier>(classifier)?
+                .get_predicted_label(),
+            label
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_source_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    net.add(BitFieldSource::new(300, 3, 7));
+    net.build()?;
+
+    let json = net.to_config()?.to_json()?;
+    let mut loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    loaded.build()?;
+    let restored = loaded.block_ids().next().unwrap();
+
+    let source = loaded.get_mut::<BitFieldSource>(restored)?;
+    assert_eq!(source.num_s(), 300);
+    assert_eq!(source.output().borrow().num_t(), 3);
+
+    source.set_acts(&[1, 299]);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<BitFieldSource>(restored)?
+            .output()
+            .borrow()
+            .state
+            .get_acts(),
+        vec![1, 299]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_source_recorded() -> Result<()> {
+    let mut net = Network::new();
+    let source = net.add(BitFieldSource::new(128, 2, 0));
+    net.set_block_name(source, "External");
+    net.build()?;
+
+    net.start_recording();
+    net.get_mut::<BitFieldSource>(source)?
+        .set_acts(&[10, 20, 30]);
+    net.execute(false)?;
+    let trace = net.stop_recording().unwrap();
+
+    let step = &trace.steps[0];
+    assert_eq!(step.block_states[&source].active_bits, vec![10, 20, 30]);
+    assert_eq!(step.block_metadata[&source].block_type, "BitFieldSource");
+    assert_eq!(step.block_metadata[&source].name, "External");
+
+    Ok(())
+}
@@ -2,8 +2,10 @@
 
 #![allow(unused_imports)]
 
-use gnomics::blocks::{ContextLearner, DiscreteTransformer};
-use gnomics::{Block, ContextAccess, InputAccess, OutputAccess};
+use gnomics::blocks::{BitFieldSource, ContextLearner, DiscreteTransformer, SequenceLearner};
+use gnomics::{
+    ApicalAccess, Block, ContextAccess, InputAccess, Network, OutputAccess, OutputPort, Result,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -340,3 +342,280 @@ fn test_context_learner_invalid_threshold() {
 fn test_context_learner_insufficient_history() {
     ContextLearner::new(10, 4, 8, 32, 20, 20, 2, 1, 1, false, 0);
 }
+
+/// Step a learner whose input, context and apical inputs are all sources.
+fn step_apical(
+    sources: &mut [BitFieldSource; 3],
+    learner: &mut ContextLearner,
+    context: &[usize],
+    apical: &[usize],
+    learn: bool,
+) -> Vec<usize> {
+    sources[0].set_acts(&[0]);
+    sources[1].set_acts(context);
+    sources[2].set_acts(apical);
+    for source in sources.iter_mut() {
+        source.execute(false).unwrap();
+    }
+    learner.execute(learn).unwrap();
+    learner.output().borrow().state.get_acts()
+}
+
+#[test]
+fn test_context_learner_apical_selects_statelets() {
+    let mut sources = [
+        BitFieldSource::new(10, 2, 0),
+        BitFieldSource::new(128, 2, 0),
+        BitFieldSource::new(128, 2, 0),
+    ];
+    let mut learner = ContextLearner::new(10, 4, 4, 16, 8, 20, 2, 1, 2, false, 3);
+    learner.input_mut().add_child(sources[0].output(), 0);
+    learner.context_mut().add_child(sources[1].output(), 0);
+    learner.apical_mut().add_child(sources[2].output(), 0);
+    learner.init().unwrap();
+    assert!(learner.has_apical());
+
+    let ctx_a: Vec<usize> = (0..24).collect();
+    let ctx_b: Vec<usize> = (64..88).collect();
+    let top_a: Vec<usize> = (0..24).collect();
+    let top_b: Vec<usize> = (64..88).collect();
+
+    // Each context is first seen with its own apical pattern
+    let s_a = step_apical(&mut sources, &mut learner, &ctx_a, &top_a, true);
+    let burst = step_apical(&mut sources, &mut learner, &ctx_b, &top_b, true);
+    assert_eq!(s_a.len(), 1);
+    assert_eq!(burst.len(), 2);
+    let s_b: Vec<usize> = burst.iter().copied().filter(|s| !s_a.contains(s)).collect();
+
+    // Without feedback, context B predicts both the burst statelets
+    assert_eq!(step_apical(&mut sources, &mut learner, &ctx_b, &[], false), burst);
+    assert_eq!(learner.get_anomaly_score(), 0.0);
+
+    // Feedback keeps only the statelet that learned it
+    assert_eq!(step_apical(&mut sources, &mut learner, &ctx_b, &top_a, false), s_a);
+    assert_eq!(step_apical(&mut sources, &mut learner, &ctx_b, &top_b, false), s_b);
+    assert_eq!(learner.get_apical_support().get_acts(), s_b);
+
+    // On surprise, the supported statelet fires instead of a random burst
+    let ctx_c: Vec<usize> = (32..56).collect();
+    assert_eq!(step_apical(&mut sources, &mut learner, &ctx_c, &top_b, false), s_b);
+    assert_eq!(learner.get_anomaly_score(), 1.0);
+}
+
+#[test]
+fn test_context_learner_state_restores_dendrite_usage() -> Result<()> {
+    use gnomics::network_config::BlockStateful;
+
+    let new_sources = || {
+        [
+            BitFieldSource::new(10, 2, 0),
+            BitFieldSource::new(128, 2, 0),
+            BitFieldSource::new(128, 2, 0),
+        ]
+    };
+    let new_learner = |sources: &[BitFieldSource; 3]| {
+        let mut learner = ContextLearner::new(10, 4, 4, 16, 8, 20, 2, 1, 2, false, 3);
+        learner.input_mut().add_child(sources[0].output(), 0);
+        learner.context_mut().add_child(sources[1].output(), 0);
+        learner.apical_mut().add_child(sources[2].output(), 0);
+        learner.init().unwrap();
+        learner
+    };
+    let mut sources = new_sources();
+    let mut learner = new_learner(&sources);
+
+    let ctx_a: Vec<usize> = (0..24).collect();
+    let ctx_b: Vec<usize> = (64..88).collect();
+    let top_a: Vec<usize> = (0..24).collect();
+    let top_b: Vec<usize> = (64..88).collect();
+    step_apical(&mut sources, &mut learner, &ctx_a, &top_a, true);
+    step_apical(&mut sources, &mut learner, &ctx_b, &top_b, true);
+    step_apical(&mut sources, &mut learner, &ctx_b, &[], false);
+    let s_b = step_apical(&mut sources, &mut learner, &ctx_b, &top_b, false);
+    assert_eq!(s_b.len(), 1);
+    let support = learner.get_apical_support().get_acts();
+    assert!(!support.is_empty());
+
+    let mut sources = new_sources();
+    let mut restored = new_learner(&sources);
+    restored.from_state(&learner.to_state()?)?;
+
+    // Restored dendrites still recognize context and feedback
+    assert_eq!(
+        step_apical(&mut sources, &mut restored, &ctx_b, &top_b, false),
+        s_b
+    );
+    assert_eq!(restored.get_apical_support().get_acts(), support);
+    assert_eq!(restored.get_anomaly_score(), 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_context_learner_apical_network_round_trip() -> Result<()> {
+    use gnomics::execution_recorder::ConnectionType;
+    use gnomics::network_config::{InputType, NetworkConfig};
+
+    let mut net = Network::new();
+    let input = net.add(DiscreteTransformer::new(10, 10, 2, 0));
+    let context = net.add(DiscreteTransformer::new(5, 128, 2, 0));
+    let learner = net.add(
+        ContextLearner::new(10, 4, 4, 16, 8, 20, 2, 1, 2, false, 0).with_apical_threshold(6),
+    );
+    let top = net.add(DiscreteTransformer::new(4, 128, 2, 0));
+
+    net.connect_to_input(input, learner)?;
+    net.connect_to_context(context, learner)?;
+    net.connect_to_apical(top, learner)?;
+    assert!(net.connect_to_apical(top, context).is_err());
+    net.build()?;
+    net.get_mut::<ContextLearner>(learner)?.init()?;
+
+    for i in 0..8 {
+        net.get_mut::<DiscreteTransformer>(input)?.set_value(i % 4);
+        net.get_mut::<DiscreteTransformer>(context)?.set_value(i % 2);
+        net.get_mut::<DiscreteTransformer>(top)?.set_value(i % 4);
+        net.execute(true)?;
+    }
+
+    // Recorder reports the apical connection
+    net.start_recording();
+    let trace = net.stop_recording().unwrap();
+    assert!(trace.connections.iter().any(|c| {
+        matches!(c.connection_type, ConnectionType::Apical)
+            && c.source_id == top
+            && c.target_id == learner
+    }));
+
+    let config = net.to_config_with_state()?;
+    assert_eq!(
+        config
+            .connections
+            .iter()
+            .filter(|c| c.input_type == InputType::Apical)
+            .count(),
+        1
+    );
+
+    let loaded = Network::from_config_with_state(&NetworkConfig::from_json(&config.to_json()?)?)?;
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<ContextLearner>(id).is_ok())
+        .unwrap();
+    let restored = loaded.get::<ContextLearner>(restored)?;
+    let original = net.get::<ContextLearner>(learner)?;
+    assert!(restored.has_apical());
+    assert_eq!(restored.apical_threshold(), 6);
+    assert_eq!(restored.apical().num_bits(), 128);
+    assert_eq!(
+        restored.apical_memory().unwrap().get_all_permanences(),
+        original.apical_memory().unwrap().get_all_permanences()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_context_learner_predicts_inactive_columns() {
+    let mut input = BitFieldSource::new(10, 2, 0);
+    let mut context = BitFieldSource::new(128, 2, 0);
+    let mut learner = ContextLearner::new(10, 4, 4, 16, 8, 20, 2, 1, 2, false, 0);
+    learner.input_mut().add_child(input.output(), 0);
+    learner.context_mut().add_child(context.output(), 0);
+    learner.init().unwrap();
+    learner.set_predicted_enabled(true);
+
+    let ctx_a: Vec<usize> = (0..24).collect();
+    let ctx_b: Vec<usize> = (64..88).collect();
+    let mut show = |column: usize, ctx: &[usize], learn: bool| {
+        input.set_acts(&[column]);
+        context.set_acts(ctx);
+        input.execute(false).unwrap();
+        context.execute(false).unwrap();
+        learner.execute(learn).unwrap();
+        (
+            learner.output().borrow().state.get_acts(),
+            learner.predicted().borrow().state.get_acts(),
+            learner.get_anomaly_score(),
+        )
+    };
+
+    let (learned_a, _, _) = show(0, &ctx_a, true);
+    show(1, &ctx_b, true);
+
+    // Context A predicts column 0's statelet even while column 1 is active
+    let (_, predicted, anomaly) = show(1, &ctx_a, false);
+    assert_eq!(predicted, learned_a);
+    assert_eq!(anomaly, 1.0);
+
+    // A recognized column's statelet is also predicted
+    let (active, predicted, anomaly) = show(0, &ctx_a, false);
+    assert_eq!(active, predicted);
+    assert_eq!(anomaly, 0.0);
+}
+
+#[test]
+fn test_context_learner_predicted_ports_round_trip() -> Result<()> {
+    use gnomics::network_config::{InputType, NetworkConfig};
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(5, 40, 2, 0));
+    let seq = net.add(SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42));
+    let learner = net.add(ContextLearner::new(40, 4, 4, 16, 8, 20, 2, 1, 2, false, 0));
+
+    net.connect_to_input(encoder, seq)?;
+    net.connect_to_input(encoder, learner)?;
+    net.connect_to_context_with_port(seq, learner, OutputPort::Predicted, 0)?;
+    net.connect_to_apical_with_port(seq, learner, OutputPort::Predicted, 0)?;
+    assert!(net
+        .connect_to_context_with_port(encoder, learner, OutputPort::Predicted, 0)
+        .is_err());
+    assert!(net.get::<SequenceLearner>(seq)?.predicted_enabled());
+    net.build()?;
+    net.get_mut::<SequenceLearner>(seq)?.init()?;
+    net.get_mut::<ContextLearner>(learner)?.init()?;
+
+    for i in 0..30 {
+        net.get_mut::<DiscreteTransformer>(encoder)?.set_value(i % 3);
+        net.execute(true)?;
+    }
+
+    // The context reads the sequence learner's predictions
+    let predicted = net
+        .get::<SequenceLearner>(seq)?
+        .predicted()
+        .borrow()
+        .state
+        .get_acts();
+    assert!(!predicted.is_empty());
+    assert_eq!(
+        net.get::<ContextLearner>(learner)?.context().state.get_acts(),
+        predicted
+    );
+
+    // Both ports survive a config round trip
+    let config = net.to_config()?;
+    for input_type in [InputType::Context, InputType::Apical] {
+        assert!(config
+            .connections
+            .iter()
+            .any(|c| c.input_type == input_type && c.source_port == OutputPort::Predicted));
+    }
+
+    let loaded = Network::from_config(&NetworkConfig::from_json(&config.to_json()?)?)?;
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<ContextLearner>(id).is_ok())
+        .unwrap();
+    let restored = loaded.get::<ContextLearner>(restored)?;
+    let context_sources = restored.context().get_source_ports();
+    let apical_sources = restored.apical().get_source_ports();
+    assert_eq!(context_sources.len(), 1);
+    assert_eq!(context_sources, apical_sources);
+    assert_eq!(context_sources[0].1, OutputPort::Predicted);
+    assert!(loaded
+        .get::<SequenceLearner>(context_sources[0].0)?
+        .predicted_enabled());
+
+    Ok(())
+}
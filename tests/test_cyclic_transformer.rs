@@ -0,0 +1,168 @@
+//! Comprehensive tests for CyclicTransformer.
+//!
+//! Tests cover:
+//! - Wrap-around overlap across the period boundary
+//! - Periodicity and negative values
+//! - Overlap as a function of cyclic distance
+//! - Change detection
+//! - Wrap-aware range decoding
+//! - Network serialization of config
+
+use gnomics::{
+    blocks::{CyclicTransformer, PatternPooler},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+fn encode(ct: &mut CyclicTransformer, value: f64) -> BitField {
+    ct.set_value(value);
+    ct.execute(false).unwrap();
+    ct.output().borrow().state.clone()
+}
+
+#[test]
+fn test_cyclic_basic_construction() {
+    let ct = CyclicTransformer::new(360.0, 1024, 64, 3, 0);
+    assert_eq!(ct.period(), 360.0);
+    assert_eq!(ct.num_s(), 1024);
+    assert_eq!(ct.num_as(), 64);
+    assert_eq!(ct.output().borrow().num_t(), 3);
+}
+
+#[test]
+#[should_panic(expected = "num_as must be <= num_s")]
+fn test_cyclic_invalid_num_as() {
+    CyclicTransformer::new(360.0, 32, 64, 2, 0);
+}
+
+#[test]
+fn test_cyclic_wrap_around_overlap() {
+    let mut ct = CyclicTransformer::new(360.0, 1024, 64, 2, 0);
+    let a = encode(&mut ct, 359.0);
+    let b = encode(&mut ct, 1.0);
+    let c = encode(&mut ct, 180.0);
+
+    assert_eq!(a.num_set(), 64);
+    assert_eq!(b.num_set(), 64);
+
+    // 2 degrees apart across the boundary: ~6 bits shifted
+    assert!(a.num_similar(&b) >= 56, "overlap {}", a.num_similar(&b));
+    assert_eq!(a.num_similar(&c), 0);
+}
+
+#[test]
+fn test_cyclic_periodicity() {
+    let mut ct = CyclicTransformer::new(24.0, 1024, 64, 2, 0);
+
+    for &h in &[0.0, 5.5, 13.0, 23.9] {
+        let base = encode(&mut ct, h);
+        for k in [-2.0, -1.0, 1.0, 3.0] {
+            assert_eq!(
+                encode(&mut ct, h + k * 24.0),
+                base,
+                "hour {} + {} periods",
+                h,
+                k
+            );
+        }
+    }
+}
+
+#[test]
+fn test_cyclic_overlap_tracks_cyclic_distance() {
+    let mut ct = CyclicTransformer::new(100.0, 1000, 100, 2, 0);
+    let base = encode(&mut ct, 10.0);
+
+    // Overlap depends only on cyclic distance, in both directions
+    for d in 0..=10 {
+        let fwd = encode(&mut ct, 10.0 + d as f64);
+        let back = encode(&mut ct, 10.0 - d as f64);
+        assert_eq!(base.num_similar(&fwd), 100 - 10 * d);
+        assert_eq!(base.num_similar(&back), 100 - 10 * d);
+    }
+}
+
+#[test]
+fn test_cyclic_change_detection() {
+    let mut ct = CyclicTransformer::new(360.0, 360, 36, 2, 0);
+    encode(&mut ct, 10.0);
+    assert!(ct.output().borrow().has_changed());
+
+    // Same window start: output unchanged
+    encode(&mut ct, 10.4);
+    assert!(!ct.output().borrow().has_changed());
+
+    // One full period later is the same window
+    encode(&mut ct, 370.0);
+    assert!(!ct.output().borrow().has_changed());
+
+    encode(&mut ct, 11.0);
+    assert!(ct.output().borrow().has_changed());
+}
+
+#[test]
+fn test_cyclic_decode_range_round_trip() {
+    let mut ct = CyclicTransformer::new(24.0, 1024, 64, 2, 0);
+
+    // Every hour decodes back to itself, including windows that wrap
+    for hour in 0..24 {
+        let value = hour as f64 + 0.5;
+        let acts = encode(&mut ct, value).get_acts();
+        let (lo, hi) = ct.decode_range(&acts).unwrap();
+        assert!((lo - value).abs() < 0.05, "{value}: lo = {lo}");
+        assert!((hi - value).abs() < 0.05, "{value}: hi = {hi}");
+    }
+
+    // Union of 23:00 and 01:00 crosses midnight
+    let mut acts = encode(&mut ct, 23.0).get_acts();
+    acts.extend(encode(&mut ct, 1.0).get_acts());
+    let (lo, hi) = ct.decode_range(&acts).unwrap();
+    assert!(lo > hi);
+    assert!((lo - 23.0).abs() < 0.05 && (hi - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn test_cyclic_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(CyclicTransformer::new(7.0, 512, 32, 2, 0));
+    let pooler = net.add(PatternPooler::new(
+        256, 16, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    net.get_mut::<CyclicTransformer>(encoder)?.set_value(6.5);
+    net.execute(true)?;
+    let expected = net
+        .get::<CyclicTransformer>(encoder)?
+        .output()
+        .borrow()
+        .state
+        .clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<CyclicTransformer>(id).is_ok())
+        .unwrap();
+
+    assert_eq!(loaded.get::<CyclicTransformer>(restored)?.period(), 7.0);
+    loaded
+        .get_mut::<CyclicTransformer>(restored)?
+        .set_value(6.5);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<CyclicTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        expected
+    );
+
+    Ok(())
+}
@@ -0,0 +1,208 @@
+//! Comprehensive tests for DateTimeTransformer.
+//!
+//! Tests cover:
+//! - Field layout and per-field active counts
+//! - Wrap-around of time-of-day, day-of-week and season
+//! - Weekend and holiday flags
+//! - Change detection
+//! - Network serialization of config
+
+use gnomics::{
+    blocks::{DateTimeFields, DateTimeTransformer, PatternPooler},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+const DAY: i64 = 86_400;
+
+// 2024-06-10 00:00:00 UTC, a Monday
+const MONDAY: i64 = 1_717_977_600;
+
+fn encode(dt: &mut DateTimeTransformer, timestamp: i64) -> BitField {
+    dt.set_value(timestamp);
+    dt.execute(false).unwrap();
+    dt.output().borrow().state.clone()
+}
+
+fn count_in(bf: &BitField, beg: usize, len: usize) -> usize {
+    (beg..beg + len).filter(|&i| bf.get_bit(i) == 1).count()
+}
+
+fn full_fields() -> DateTimeFields {
+    DateTimeFields::default()
+        .time_of_day(480, 48)
+        .day_of_week(280, 40)
+        .weekend(64, 32)
+        .holidays(64, 32, &[(2024, 12, 25)])
+        .season(365, 30)
+}
+
+#[test]
+fn test_date_time_field_layout() {
+    let mut dt = DateTimeTransformer::new(full_fields(), 2, 0);
+    assert_eq!(dt.num_s(), 480 + 280 + 64 + 64 + 365);
+
+    let bf = encode(&mut dt, MONDAY + 9 * 3600);
+    assert_eq!(count_in(&bf, 0, 480), 48);
+    assert_eq!(count_in(&bf, 480, 280), 40);
+    assert_eq!(count_in(&bf, 760, 64), 32);
+    assert_eq!(count_in(&bf, 824, 64), 32);
+    assert_eq!(count_in(&bf, 888, 365), 30);
+    assert_eq!(bf.num_set(), 48 + 40 + 32 + 32 + 30);
+}
+
+#[test]
+fn test_date_time_midnight_wraps() {
+    let mut dt = DateTimeTransformer::new(DateTimeFields::default().time_of_day(480, 48), 2, 0);
+
+    let before = encode(&mut dt, MONDAY - 60);
+    let after = encode(&mut dt, MONDAY + 60);
+    let noon = encode(&mut dt, MONDAY + 12 * 3600);
+
+    assert!(before.num_similar(&after) >= 46);
+    assert_eq!(before.num_similar(&noon), 0);
+
+    // Same time on different days encodes identically
+    assert_eq!(encode(&mut dt, MONDAY + 5 * DAY + 60), after);
+}
+
+#[test]
+fn test_date_time_week_wraps() {
+    let mut dt = DateTimeTransformer::new(DateTimeFields::default().day_of_week(280, 40), 2, 0);
+
+    let sunday_night = encode(&mut dt, MONDAY - 3600);
+    let monday_morning = encode(&mut dt, MONDAY + 3600);
+    let thursday = encode(&mut dt, MONDAY + 3 * DAY);
+
+    assert!(sunday_night.num_similar(&monday_morning) >= 35);
+    assert_eq!(sunday_night.num_similar(&thursday), 0);
+}
+
+#[test]
+fn test_date_time_weekend_flag() {
+    let mut dt = DateTimeTransformer::new(DateTimeFields::default().weekend(64, 32), 2, 0);
+
+    let weekdays: Vec<BitField> = (0..5).map(|d| encode(&mut dt, MONDAY + d * DAY)).collect();
+    let sat = encode(&mut dt, MONDAY + 5 * DAY);
+    let sun = encode(&mut dt, MONDAY + 6 * DAY);
+
+    assert!(weekdays.iter().all(|w| *w == weekdays[0]));
+    assert_eq!(sat, sun);
+    assert_eq!(sat.num_similar(&weekdays[0]), 0);
+}
+
+#[test]
+fn test_date_time_holiday_flag() {
+    let fields = DateTimeFields::default().holidays(64, 32, &[(2024, 12, 25), (2025, 1, 1)]);
+    let mut dt = DateTimeTransformer::new(fields, 2, 0);
+
+    // 2024-12-25 00:00:00 UTC
+    let christmas = 1_735_084_800;
+    dt.set_value(christmas + 20 * 3600);
+    assert!(dt.is_holiday());
+    dt.set_value(christmas + DAY);
+    assert!(!dt.is_holiday());
+    dt.set_value(christmas + 7 * DAY);
+    assert!(dt.is_holiday());
+
+    let holiday = encode(&mut dt, christmas);
+    let normal = encode(&mut dt, christmas - DAY);
+    assert_eq!(holiday.num_similar(&normal), 0);
+}
+
+#[test]
+fn test_date_time_unsorted_holiday_days() {
+    // 2025-01-01, 2024-12-25 and 2024-07-04 as days since 1970-01-01, unsorted
+    let mut fields = DateTimeFields::default().holidays(64, 32, &[]);
+    fields.holiday_days = vec![20_089, 20_082, 19_908, 20_082];
+    let mut dt = DateTimeTransformer::new(fields, 2, 0);
+
+    for days in [19_908, 20_082, 20_089] {
+        dt.set_value(days * DAY);
+        assert!(dt.is_holiday());
+    }
+    dt.set_value(20_083 * DAY);
+    assert!(!dt.is_holiday());
+}
+
+#[test]
+fn test_date_time_season_wraps_new_year() {
+    let mut dt = DateTimeTransformer::new(DateTimeFields::default().season(365, 30), 2, 0);
+
+    // 2024-01-01 00:00:00 UTC
+    let new_year = 1_704_067_200;
+    let dec_31 = encode(&mut dt, new_year - 12 * 3600);
+    let jan_1 = encode(&mut dt, new_year + 12 * 3600);
+    let july = encode(&mut dt, new_year + 182 * DAY);
+
+    assert!(dec_31.num_similar(&jan_1) >= 28);
+    assert_eq!(dec_31.num_similar(&july), 0);
+}
+
+#[test]
+fn test_date_time_change_detection() {
+    let mut dt = DateTimeTransformer::new(DateTimeFields::default().time_of_day(24, 2), 2, 0);
+    encode(&mut dt, MONDAY);
+    assert!(dt.output().borrow().has_changed());
+
+    // Same hour bucket: output unchanged
+    encode(&mut dt, MONDAY + 1800);
+    assert!(!dt.output().borrow().has_changed());
+
+    encode(&mut dt, MONDAY + 3600);
+    assert!(dt.output().borrow().has_changed());
+}
+
+#[test]
+fn test_date_time_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(DateTimeTransformer::new(
+        full_fields().utc_offset(3600),
+        2,
+        0,
+    ));
+    let pooler = net.add(PatternPooler::new(
+        256, 16, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    net.get_mut::<DateTimeTransformer>(encoder)?
+        .set_value(MONDAY);
+    net.execute(true)?;
+    let expected = net
+        .get::<DateTimeTransformer>(encoder)?
+        .output()
+        .borrow()
+        .state
+        .clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<DateTimeTransformer>(id).is_ok())
+        .unwrap();
+
+    assert_eq!(
+        loaded.get::<DateTimeTransformer>(restored)?.fields(),
+        &full_fields().utc_offset(3600)
+    );
+    loaded
+        .get_mut::<DateTimeTransformer>(restored)?
+        .set_value(MONDAY);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<DateTimeTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        expected
+    );
+
+    Ok(())
+}
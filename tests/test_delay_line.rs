@@ -0,0 +1,171 @@
+//! Comprehensive tests for DelayLine.
+//!
+//! Tests cover:
+//! - Construction and parameter validation
+//! - Windows longer than the upstream block's history depth
+//! - Chosen subsets of lags
+//! - A readout learning from a past frame through the window
+//! - Network serialization of the lag configuration
+
+use gnomics::{
+    blocks::{BitFieldSource, DelayLine, DiscreteTransformer, SdrClassifier},
+    Block, BlockId, InputAccess, Network, OutputAccess, Result,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Source (num_t = 2) feeding a DelayLine in a network.
+fn source_delay(delay: DelayLine) -> Result<(Network, BlockId, BlockId)> {
+    let mut net = Network::new();
+    let source = net.add(BitFieldSource::new(delay.num_i(), 2, 0));
+    let delay = net.add(delay);
+    net.connect_to_input(source, delay)?;
+    net.build()?;
+    net.get_mut::<DelayLine>(delay)?.init()?;
+    Ok((net, source, delay))
+}
+
+#[test]
+fn test_delay_line_basic_construction() {
+    let delay = DelayLine::new(128, 4, 2, 0);
+    assert_eq!(delay.num_i(), 128);
+    assert_eq!(delay.lags(), &[0, 1, 2, 3]);
+    assert_eq!(delay.max_lag(), 3);
+    assert_eq!(delay.frame_offset(2), 256);
+    assert_eq!(delay.output().borrow().state.num_bits(), 512);
+}
+
+#[test]
+#[should_panic(expected = "num_k must be > 0")]
+fn test_delay_line_invalid_num_k() {
+    DelayLine::new(128, 0, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "lags must not be empty")]
+fn test_delay_line_invalid_lags() {
+    DelayLine::new(128, 2, 2, 0).with_lags(&[]);
+}
+
+#[test]
+fn test_delay_line_window_longer_than_source_history() -> Result<()> {
+    let (mut net, source, delay) = source_delay(DelayLine::new(64, 6, 2, 0))?;
+
+    for t in 0..8 {
+        net.get_mut::<BitFieldSource>(source)?.set_acts(&[t]);
+        net.execute(false)?;
+    }
+
+    // Frame j holds the input from j steps ago: 7, 6, ..., 2
+    let d = net.get::<DelayLine>(delay)?;
+    let expected: Vec<usize> = (0..6).map(|j| d.frame_offset(j) + 7 - j).collect();
+    assert_eq!(d.output().borrow().state.get_acts(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_delay_line_subset_of_lags() -> Result<()> {
+    let (mut net, source, delay) = source_delay(DelayLine::new(64, 1, 2, 0).with_lags(&[4, 0]))?;
+    let num_bits = net
+        .get::<DelayLine>(delay)?
+        .output()
+        .borrow()
+        .state
+        .num_bits();
+    assert_eq!(num_bits, 128);
+
+    for t in 0..10 {
+        net.get_mut::<BitFieldSource>(source)?.set_acts(&[t]);
+        net.execute(false)?;
+    }
+
+    // Frames follow the given lag order: lag 4 first, then lag 0
+    let d = net.get::<DelayLine>(delay)?;
+    assert_eq!(
+        d.output().borrow().state.get_acts(),
+        vec![5, d.frame_offset(1) + 9]
+    );
+    assert_eq!(d.get_frame(2).get_acts(), vec![7]);
+
+    Ok(())
+}
+
+#[test]
+fn test_delay_line_readout_learns_past_frame() -> Result<()> {
+    // The label is the value seen two steps ago, which only the window holds
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 64, 2, 0));
+    let delay = net.add(DelayLine::new(64, 3, 2, 0));
+    let readout = net.add(SdrClassifier::new(4, 0, 0.2, 2, 0));
+    net.connect_to_input(encoder, delay)?;
+    net.connect_to_input(delay, readout)?;
+    net.build()?;
+    net.get_mut::<DelayLine>(delay)?.init()?;
+    net.get_mut::<SdrClassifier>(readout)?.init()?;
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let values: Vec<usize> = (0..600).map(|_| rng.gen_range(0..4)).collect();
+    let mut correct = 0;
+    for t in 0..values.len() {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(values[t]);
+        let learn = t < 500;
+        if t >= 2 {
+            net.get_mut::<SdrClassifier>(readout)?
+                .set_label(values[t - 2]);
+        }
+        net.execute(learn)?;
+        if !learn && net.get::<SdrClassifier>(readout)?.get_predicted_label() == values[t - 2] {
+            correct += 1;
+        }
+    }
+    assert!(correct >= 95, "only {} of 100 correct", correct);
+
+    Ok(())
+}
+
+#[test]
+fn test_delay_line_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let (net, _, _) = source_delay(DelayLine::new(100, 1, 3, 0).with_lags(&[0, 2, 8]))?;
+
+    let json = net.to_config()?.to_json()?;
+    let mut loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    loaded.build()?;
+    let ids: Vec<BlockId> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<DelayLine>(id).is_ok())
+        .unwrap();
+    let restored_source = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    {
+        let d = loaded.get::<DelayLine>(restored)?;
+        assert_eq!(d.num_i(), 100);
+        assert_eq!(d.lags(), &[0, 2, 8]);
+        assert_eq!(d.output().borrow().num_t(), 3);
+    }
+
+    for t in 0..9 {
+        loaded
+            .get_mut::<BitFieldSource>(restored_source)?
+            .set_acts(&[t]);
+        loaded.execute(false)?;
+    }
+    let d = loaded.get::<DelayLine>(restored)?;
+    let expected = vec![8, d.frame_offset(1) + 6, d.frame_offset(2)];
+    assert_eq!(d.output().borrow().state.get_acts(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_delay_line_wrong_input_width() {
+    let source = BitFieldSource::new(32, 2, 0);
+    let mut delay = DelayLine::new(64, 2, 2, 0);
+    delay.input_mut().add_child(source.output(), 0);
+    assert!(delay.init().is_err());
+}
@@ -0,0 +1,189 @@
+//! Comprehensive tests for DeltaTransformer.
+//!
+//! Tests cover:
+//! - Signed delta encoding and saturation
+//! - Rate of change over irregular intervals
+//! - Smoothing
+//! - Change detection
+//! - Non-finite values
+//! - Network serialization of config
+
+use gnomics::{blocks::DeltaTransformer, BitField, Block, Network, OutputAccess, Result};
+
+fn encode(dt: &mut DeltaTransformer, value: f64) -> BitField {
+    dt.set_value(value);
+    dt.execute(false).unwrap();
+    dt.output().borrow().state.clone()
+}
+
+#[test]
+fn test_delta_basic_construction() {
+    let dt = DeltaTransformer::new(-5.0, 5.0, 2048, 256, 3, 0);
+    assert_eq!(dt.min_delta(), -5.0);
+    assert_eq!(dt.max_delta(), 5.0);
+    assert_eq!(dt.num_s(), 2048);
+    assert_eq!(dt.num_as(), 256);
+    assert_eq!(dt.output().borrow().num_t(), 3);
+}
+
+#[test]
+#[should_panic(expected = "num_as must be <= num_s")]
+fn test_delta_invalid_num_as() {
+    DeltaTransformer::new(-1.0, 1.0, 64, 128, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "smoothing must be in (0.0, 1.0]")]
+fn test_delta_invalid_smoothing() {
+    DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0).with_smoothing(0.0);
+}
+
+#[test]
+fn test_delta_rising_flat_falling() {
+    let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    encode(&mut dt, 0.0);
+
+    let rising = encode(&mut dt, 1.0);
+    let flat = encode(&mut dt, 1.0);
+    let falling = encode(&mut dt, 0.0);
+
+    assert_eq!(rising.num_set(), 128);
+    assert_eq!(rising.num_similar(&flat), 0);
+    assert_eq!(flat.num_similar(&falling), 0);
+    assert!(falling.get_acts()[0] < flat.get_acts()[0]);
+    assert!(flat.get_acts()[0] < rising.get_acts()[0]);
+}
+
+#[test]
+fn test_delta_independent_of_level() {
+    let mut low = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    let mut high = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    encode(&mut low, 10.0);
+    encode(&mut high, 500.0);
+
+    // Same step at different levels gives the same code
+    assert_eq!(encode(&mut low, 10.3), encode(&mut high, 500.3));
+}
+
+#[test]
+fn test_delta_similar_deltas_overlap() {
+    let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    encode(&mut dt, 0.0);
+    let small = encode(&mut dt, 0.20);
+    let close = encode(&mut dt, 0.45);
+    let far = encode(&mut dt, 1.45);
+
+    assert!(small.num_similar(&close) > 64);
+    assert!(small.num_similar(&far) < small.num_similar(&close));
+}
+
+#[test]
+fn test_delta_rate_of_change() {
+    let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    dt.set_value(0.0);
+    dt.execute(false).unwrap();
+
+    // +2 over 4 time units equals +0.5 over 1
+    dt.set_value_dt(2.0, 4.0);
+    dt.execute(false).unwrap();
+    let slow = dt.output().borrow().state.clone();
+    assert_eq!(dt.get_delta(), 0.5);
+
+    let mut per_step = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    encode(&mut per_step, 0.0);
+    assert_eq!(encode(&mut per_step, 0.5), slow);
+}
+
+#[test]
+fn test_delta_smoothing_suppresses_spikes() {
+    let mut raw = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    let mut smooth = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0).with_smoothing(0.2);
+
+    for dt in [&mut raw, &mut smooth] {
+        for v in [0.0, 0.0, 0.0, 1.0, 1.0] {
+            encode(dt, v);
+        }
+    }
+
+    // One-step spike has passed for the raw encoder but still lingers when smoothed
+    assert_eq!(raw.get_delta(), 0.0);
+    assert!(smooth.get_delta() > 0.1 && smooth.get_delta() < 0.2);
+}
+
+#[test]
+fn test_delta_change_detection() {
+    let mut dt = DeltaTransformer::new(-1.0, 1.0, 1024, 128, 2, 0);
+    encode(&mut dt, 0.0);
+    encode(&mut dt, 0.5);
+    assert!(dt.output().borrow().has_changed());
+
+    // Constant slope keeps the same code
+    encode(&mut dt, 1.0);
+    assert!(!dt.output().borrow().has_changed());
+
+    encode(&mut dt, 1.0);
+    assert!(dt.output().borrow().has_changed());
+}
+
+#[test]
+fn test_delta_skips_non_finite_values() {
+    let mut dt = DeltaTransformer::new(-2.0, 2.0, 1024, 128, 2, 0).with_smoothing(0.5);
+    encode(&mut dt, 0.0);
+    encode(&mut dt, 2.0);
+    encode(&mut dt, 4.0);
+    let before = dt.get_delta();
+
+    // A gap in the signal encodes as empty without poisoning the average
+    for value in [f64::NAN, f64::INFINITY] {
+        assert_eq!(encode(&mut dt, value).num_set(), 0);
+        assert_eq!(dt.get_delta(), before);
+    }
+
+    // Resumes from the last finite value, window unchanged by the gap
+    let mut reference = DeltaTransformer::new(-2.0, 2.0, 1024, 128, 2, 0).with_smoothing(0.5);
+    for value in [0.0, 2.0, 4.0, 6.0] {
+        encode(&mut reference, value);
+    }
+    assert_eq!(encode(&mut dt, 6.0), reference.output().borrow().state);
+    assert!(dt.get_delta().is_finite());
+}
+
+#[test]
+fn test_delta_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(DeltaTransformer::new(-3.0, 3.0, 512, 64, 2, 9).with_smoothing(0.5));
+    net.build()?;
+
+    let json = net.to_config()?.to_json()?;
+    let mut loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    loaded.build()?;
+    let restored = loaded.block_ids().next().unwrap();
+
+    {
+        let dt = loaded.get::<DeltaTransformer>(restored)?;
+        assert_eq!((dt.min_delta(), dt.max_delta()), (-3.0, 3.0));
+        assert_eq!(dt.smoothing(), 0.5);
+    }
+
+    for v in [1.0, 2.5, 2.0] {
+        net.get_mut::<DeltaTransformer>(encoder)?.set_value(v);
+        net.execute(false)?;
+        loaded.get_mut::<DeltaTransformer>(restored)?.set_value(v);
+        loaded.execute(false)?;
+    }
+    assert_eq!(
+        loaded
+            .get::<DeltaTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        net.get::<DeltaTransformer>(encoder)?
+            .output()
+            .borrow()
+            .state
+    );
+
+    Ok(())
+}
@@ -456,3 +456,20 @@ fn test_discrete_day_of_week_example() {
         "Different days should have no overlap"
     );
 }
+
+#[test]
+fn test_discrete_decode_values() {
+    let mut encoder = DiscreteTransformer::new(8, 256, 2, 0);
+
+    let mut acts = Vec::new();
+    for value in [1, 6] {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        acts.extend(encoder.output().borrow().state.get_acts());
+    }
+    assert_eq!(encoder.decode_values(&acts), vec![1, 6]);
+
+    // Fewer than half of a window does not decode
+    acts.truncate(10);
+    assert!(encoder.decode_values(&acts).is_empty());
+}
@@ -0,0 +1,173 @@
+//! Comprehensive tests for HyperGridTransformer.
+//!
+//! Tests cover:
+//! - Fixed sparsity for arbitrary vectors
+//! - Overlap as a function of Euclidean distance
+//! - Rotation-invariant distance behavior
+//! - Seed determinism
+//! - Network serialization of config
+
+use gnomics::{
+    blocks::{HyperGridTransformer, PatternPooler},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+fn encode(hg: &mut HyperGridTransformer, value: &[f64]) -> BitField {
+    hg.set_value(value);
+    hg.execute(false).unwrap();
+    hg.output().borrow().state.clone()
+}
+
+#[test]
+fn test_hypergrid_basic_construction() {
+    let hg = HyperGridTransformer::new(3, 32, 8, 2, 0.5, 16.0, 3, 0);
+    assert_eq!(hg.num_dims(), 3);
+    assert_eq!(hg.num_grids(), 32);
+    assert_eq!(hg.num_bins(), 8);
+    assert_eq!(hg.grid_dims(), 2);
+    assert_eq!(hg.num_s(), 32 * 64);
+    assert_eq!(hg.output().borrow().num_t(), 3);
+}
+
+#[test]
+#[should_panic(expected = "value must have num_dims components")]
+fn test_hypergrid_wrong_dimension() {
+    let mut hg = HyperGridTransformer::new(3, 8, 8, 2, 1.0, 2.0, 2, 0);
+    hg.set_value(&[1.0, 2.0]);
+}
+
+#[test]
+fn test_hypergrid_fixed_sparsity() {
+    let mut hg = HyperGridTransformer::new(4, 48, 6, 2, 0.5, 50.0, 2, 1);
+
+    for v in [[0.0; 4], [1e6, -1e6, 3.0, 0.1], [-0.3, 2.2, 9.9, -41.0]] {
+        assert_eq!(encode(&mut hg, &v).num_set(), 48);
+    }
+}
+
+#[test]
+fn test_hypergrid_overlap_tracks_distance() {
+    let mut hg = HyperGridTransformer::new(2, 128, 8, 2, 1.0, 64.0, 2, 0);
+    let origin = [3.0, -1.0];
+    let base = encode(&mut hg, &origin);
+
+    // Average over directions to smooth out grid alignment
+    let mean_overlap = |hg: &mut HyperGridTransformer, r: f64| -> f64 {
+        let total: usize = (0..16)
+            .map(|k| {
+                let theta = k as f64 * std::f64::consts::PI / 8.0;
+                let p = [origin[0] + r * theta.cos(), origin[1] + r * theta.sin()];
+                base.num_similar(&encode(hg, &p))
+            })
+            .sum();
+        total as f64 / 16.0
+    };
+
+    let mut prev = 128.0;
+    for r in [0.05, 0.2, 0.5, 1.0, 2.0, 4.0] {
+        let overlap = mean_overlap(&mut hg, r);
+        assert!(
+            overlap < prev,
+            "distance {}: overlap {} >= {}",
+            r,
+            overlap,
+            prev
+        );
+        prev = overlap;
+    }
+
+    // Very close points share nearly every cell; very far points almost none
+    assert!(mean_overlap(&mut hg, 0.01) > 120.0);
+    assert!(mean_overlap(&mut hg, 1000.0) < 8.0);
+}
+
+#[test]
+fn test_hypergrid_joint_structure() {
+    // Points at the same distance from the origin have similar overlap
+    // regardless of direction, unlike per-axis scalar encoders
+    let mut hg = HyperGridTransformer::new(2, 256, 8, 2, 1.0, 32.0, 2, 4);
+    let base = encode(&mut hg, &[0.0, 0.0]);
+
+    let axis = base.num_similar(&encode(&mut hg, &[2.0, 0.0])) as f64;
+    let diagonal = base.num_similar(&encode(&mut hg, &[2f64.sqrt(), 2f64.sqrt()])) as f64;
+    assert!(
+        (axis - diagonal).abs() < 40.0,
+        "axis {} vs diagonal {}",
+        axis,
+        diagonal
+    );
+}
+
+#[test]
+fn test_hypergrid_seed_determinism() {
+    let mut a = HyperGridTransformer::new(3, 32, 8, 2, 1.0, 10.0, 2, 9);
+    let mut b = HyperGridTransformer::new(3, 32, 8, 2, 1.0, 10.0, 2, 9);
+    let mut c = HyperGridTransformer::new(3, 32, 8, 2, 1.0, 10.0, 2, 10);
+
+    let v = [0.7, -3.1, 5.0];
+    let (ea, eb, ec) = (encode(&mut a, &v), encode(&mut b, &v), encode(&mut c, &v));
+    assert_eq!(ea, eb);
+    assert!(ea.num_similar(&ec) < 16);
+}
+
+#[test]
+fn test_hypergrid_change_detection() {
+    let mut hg = HyperGridTransformer::new(2, 16, 4, 2, 10.0, 20.0, 2, 0);
+    encode(&mut hg, &[0.0, 0.0]);
+    assert!(hg.output().borrow().has_changed());
+
+    // Tiny move stays in the same cells
+    encode(&mut hg, &[1e-9, 0.0]);
+    assert!(!hg.output().borrow().has_changed());
+
+    encode(&mut hg, &[7.0, 3.0]);
+    assert!(hg.output().borrow().has_changed());
+}
+
+#[test]
+fn test_hypergrid_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(HyperGridTransformer::new(3, 32, 4, 2, 1.0, 8.0, 2, 5));
+    let pooler = net.add(PatternPooler::new(
+        256, 16, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    let v = [1.5, -0.25, 4.0];
+    net.get_mut::<HyperGridTransformer>(encoder)?.set_value(&v);
+    net.execute(true)?;
+    let expected = net
+        .get::<HyperGridTransformer>(encoder)?
+        .output()
+        .borrow()
+        .state
+        .clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<HyperGridTransformer>(id).is_ok())
+        .unwrap();
+
+    // Grids are regenerated from the seed, so encodings match exactly
+    loaded
+        .get_mut::<HyperGridTransformer>(restored)?
+        .set_value(&v);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<HyperGridTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        expected
+    );
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! Comprehensive tests for ImageTransformer.
+//!
+//! Tests cover:
+//! - Threshold, mean and edge binarization
+//! - Downsampling
+//! - Shape export to a topological PatternPooler
+//! - Network serialization of config
+
+use gnomics::{
+    blocks::{ImageBinarize, ImageTransformer, PatternPooler},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+/// 8x8 image with a filled 4x4 square at (2, 2) of the given brightness.
+fn square(on: f64, off: f64) -> Vec<f64> {
+    let mut pixels = vec![off; 64];
+    for y in 2..6 {
+        for x in 2..6 {
+            pixels[y * 8 + x] = on;
+        }
+    }
+    pixels
+}
+
+fn encode(it: &mut ImageTransformer, pixels: &[f64]) -> BitField {
+    it.set_pixels(pixels);
+    it.execute(false).unwrap();
+    it.output().borrow().state.clone()
+}
+
+#[test]
+fn test_image_basic_construction() {
+    let it = ImageTransformer::new(28, 20, 3, 0).with_downsample(4);
+    assert_eq!(it.input_shape(), (28, 20));
+    assert_eq!(it.output_shape(), (7, 5));
+    assert_eq!(it.num_s(), 35);
+    assert_eq!(it.output().borrow().num_t(), 3);
+}
+
+#[test]
+fn test_image_threshold_binary_data() {
+    let mut it = ImageTransformer::new(8, 8, 2, 0);
+    let bf = encode(&mut it, &square(1.0, 0.0));
+
+    let expected: Vec<usize> = (2..6)
+        .flat_map(|y| (2..6).map(move |x| y * 8 + x))
+        .collect();
+    assert_eq!(bf.get_acts(), expected);
+}
+
+#[test]
+fn test_image_u8_pixels() {
+    let mut it = ImageTransformer::new(2, 2, 2, 0).with_binarize(ImageBinarize::Threshold(0.5));
+    it.set_pixels_u8(&[0, 127, 128, 255]);
+    it.execute(false).unwrap();
+    assert_eq!(it.output().borrow().state.get_acts(), vec![2, 3]);
+}
+
+#[test]
+fn test_image_mean_ignores_brightness() {
+    let mut it = ImageTransformer::new(8, 8, 2, 0).with_binarize(ImageBinarize::Mean);
+
+    let dim = encode(&mut it, &square(0.3, 0.1));
+    let bright = encode(&mut it, &square(0.95, 0.75));
+    assert_eq!(dim, bright);
+    assert_eq!(dim.num_set(), 16);
+}
+
+#[test]
+fn test_image_edges_outline_square() {
+    let mut it = ImageTransformer::new(8, 8, 2, 0).with_binarize(ImageBinarize::Edge(2.0));
+    let bf = encode(&mut it, &square(1.0, 0.0));
+
+    // Interior and far background have no gradient; the boundary does
+    assert_eq!(bf.get_bit(0), 0);
+    assert_eq!(bf.get_bit(3 * 8 + 3), 0);
+    assert_eq!(bf.get_bit(2 * 8 + 2), 1);
+    assert_eq!(bf.get_bit(2 * 8 + 1), 1);
+    assert!(bf.num_set() > 0 && bf.num_set() < 36);
+}
+
+#[test]
+fn test_image_downsampling() {
+    let mut it = ImageTransformer::new(8, 8, 2, 0)
+        .with_downsample(2)
+        .with_binarize(ImageBinarize::Threshold(0.99));
+
+    // The square covers pooled cells (1..3, 1..3) fully
+    let bf = encode(&mut it, &square(1.0, 0.0));
+    assert_eq!(bf.get_acts(), vec![5, 6, 9, 10]);
+}
+
+#[test]
+fn test_image_change_detection() {
+    let mut it = ImageTransformer::new(8, 8, 2, 0);
+    encode(&mut it, &square(1.0, 0.0));
+    assert!(it.output().borrow().has_changed());
+
+    it.execute(false).unwrap();
+    assert!(!it.output().borrow().has_changed());
+
+    encode(&mut it, &square(0.0, 1.0));
+    assert!(it.output().borrow().has_changed());
+}
+
+#[test]
+fn test_image_feeds_topological_pooler() -> Result<()> {
+    let mut net = Network::new();
+    let image = ImageTransformer::new(16, 16, 2, 0).with_downsample(2);
+    let topology = image.pooler_topology(8, 8, 1, 2);
+    let encoder = net.add(image);
+    let pooler = net.add(
+        PatternPooler::new(64, 8, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0).with_topology(topology),
+    );
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    // Pooler input grid matches the image output grid
+    let topo = *net.get::<PatternPooler>(pooler)?.topology().unwrap();
+    assert_eq!((topo.input_width, topo.input_height), (8, 8));
+
+    let mut pixels = vec![0.0; 256];
+    for (i, p) in pixels.iter_mut().enumerate() {
+        if (i % 16) < 8 {
+            *p = 1.0;
+        }
+    }
+    net.get_mut::<ImageTransformer>(encoder)?
+        .set_pixels(&pixels);
+    net.execute(true)?;
+
+    // Local inhibition picks winners only where the image has content
+    let acts = net
+        .get::<PatternPooler>(pooler)?
+        .output()
+        .borrow()
+        .state
+        .get_acts();
+    assert!(!acts.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_image_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(
+        ImageTransformer::new(8, 8, 2, 0)
+            .with_downsample(2)
+            .with_binarize(ImageBinarize::Edge(1.5)),
+    );
+    net.build()?;
+
+    let json = net.to_config()?.to_json()?;
+    let mut loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    loaded.build()?;
+    let restored = loaded.block_ids().next().unwrap();
+
+    {
+        let it = loaded.get::<ImageTransformer>(restored)?;
+        assert_eq!(it.input_shape(), (8, 8));
+        assert_eq!(it.output_shape(), (4, 4));
+        assert_eq!(it.binarize(), ImageBinarize::Edge(1.5));
+    }
+
+    let pixels = square(1.0, 0.0);
+    net.get_mut::<ImageTransformer>(encoder)?
+        .set_pixels(&pixels);
+    net.execute(false)?;
+    loaded
+        .get_mut::<ImageTransformer>(restored)?
+        .set_pixels(&pixels);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<ImageTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        net.get::<ImageTransformer>(encoder)?
+            .output()
+            .borrow()
+            .state
+    );
+
+    Ok(())
+}
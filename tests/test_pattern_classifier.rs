@@ -6,9 +6,15 @@
 //! - Classification accuracy improvement with training
 //! - Probability calculation
 //! - Integration with encoders
+//! - Unlabeled steps, runtime labels and label names
+//! - Calibrated scores, unknown-input rejection and top-k predictions
+//! - Multi-label learning, per-label thresholds and metrics
 
 #![allow(unused_imports)]
-use gnomics::{Block, InputAccess, OutputAccess, PatternClassifier, ScalarTransformer};
+use gnomics::{
+    Block, DiscreteTransformer, InputAccess, MemoryAccess, OutputAccess, PatternClassifier,
+    ScalarTransformer,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -292,3 +298,556 @@ fn test_classifier_probability_distribution() {
         assert!(p <= 1.0, "Probabilities should be <= 1.0");
     }
 }
+
+fn train_discrete(
+    encoder: &mut DiscreteTransformer,
+    classifier: &mut PatternClassifier,
+    samples: &[(usize, usize)],
+    epochs: usize,
+) {
+    for _ in 0..epochs {
+        for &(value, label) in samples {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            classifier.set_label(label);
+            classifier.execute(true).unwrap();
+        }
+    }
+}
+
+fn predict_discrete(
+    encoder: &mut DiscreteTransformer,
+    classifier: &mut PatternClassifier,
+    value: usize,
+) -> usize {
+    encoder.set_value(value);
+    encoder.execute(false).unwrap();
+    classifier.execute(false).unwrap();
+    classifier.get_predicted_label()
+}
+
+#[test]
+fn test_classifier_unlabeled_steps_do_not_learn() {
+    let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+    let mut classifier = PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.input_mut().add_child(encoder.output(), 0);
+    classifier.init().unwrap();
+
+    train_discrete(&mut encoder, &mut classifier, &[(0, 0)], 1);
+    assert_eq!(classifier.get_label(), Some(0));
+
+    classifier.clear_label();
+    assert_eq!(classifier.get_label(), None);
+    let before = classifier.memory().get_all_permanences();
+
+    for value in [1, 2, 3] {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        classifier.execute(true).unwrap();
+    }
+    assert_eq!(classifier.memory().get_all_permanences(), before);
+}
+
+#[test]
+fn test_classifier_add_label_preserves_existing() {
+    let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+    let mut classifier = PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.input_mut().add_child(encoder.output(), 0);
+    classifier.init().unwrap();
+
+    train_discrete(&mut encoder, &mut classifier, &[(0, 0), (1, 1)], 10);
+    let before = classifier.memory().get_all_permanences();
+
+    let new_label = classifier.add_label("2").unwrap();
+    assert_eq!(new_label, 2);
+    assert_eq!(classifier.num_l(), 3);
+    assert_eq!(classifier.num_s(), 192);
+    assert_eq!(classifier.output().borrow().state.num_bits(), 192);
+    assert_eq!(classifier.get_probabilities().len(), 3);
+    assert_eq!(classifier.get_statelet_labels()[191], 2);
+    assert_eq!(classifier.memory().get_all_permanences()[..128], before[..]);
+
+    // New class learns alongside the old ones
+    train_discrete(&mut encoder, &mut classifier, &[(2, 2)], 10);
+    assert_eq!(predict_discrete(&mut encoder, &mut classifier, 0), 0);
+    assert_eq!(predict_discrete(&mut encoder, &mut classifier, 1), 1);
+    assert_eq!(predict_discrete(&mut encoder, &mut classifier, 2), 2);
+}
+
+#[test]
+fn test_classifier_add_label_refuses_with_readers() -> gnomics::Result<()> {
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 256, 2, 0));
+    let classifier = net.add(PatternClassifier::new(
+        2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0,
+    ));
+    let reader = net.add(PatternClassifier::new(
+        2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0,
+    ));
+    net.connect_to_input(encoder, classifier)?;
+    net.connect_to_input(classifier, reader)?;
+    net.build()?;
+
+    // Growing the output would desynchronize the reader's input
+    let c = net.get_mut::<PatternClassifier>(classifier)?;
+    assert!(c.add_label("extra").is_err());
+    assert_eq!(c.num_l(), 2);
+    assert_eq!(c.num_s(), 128);
+    assert_eq!(c.label_names(), ["0", "1"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_classifier_add_label_reuses_leftover_statelets() {
+    // 100 / 3 = 33 per label, leaving one unused statelet
+    let mut classifier = PatternClassifier::new(3, 100, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.add_label("3").unwrap();
+    assert_eq!(classifier.num_s(), 132);
+    assert_eq!(classifier.num_spl(), 33);
+}
+
+#[test]
+fn test_classifier_label_names() {
+    let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+    let mut classifier = PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+        .with_label_names(&["cat", "dog"]);
+    classifier.input_mut().add_child(encoder.output(), 0);
+    classifier.init().unwrap();
+    assert_eq!(classifier.label_names(), ["cat", "dog"]);
+    assert_eq!(classifier.label_index("dog"), Some(1));
+    assert_eq!(classifier.label_index("bird"), None);
+
+    for _ in 0..10 {
+        for (value, name) in [(0, "cat"), (1, "dog")] {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            classifier.set_label_by_name(name);
+            classifier.execute(true).unwrap();
+        }
+    }
+
+    predict_discrete(&mut encoder, &mut classifier, 1);
+    assert_eq!(classifier.get_predicted_label_name(), "dog");
+}
+
+#[test]
+fn test_classifier_default_label_names() {
+    let classifier = PatternClassifier::new(3, 96, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    assert_eq!(classifier.label_names(), ["0", "1", "2"]);
+    assert_eq!(classifier.label_name(2), "2");
+}
+
+#[test]
+#[should_panic(expected = "already exists")]
+fn test_classifier_duplicate_label_name() {
+    let mut classifier = PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+        .with_label_names(&["cat", "dog"]);
+    classifier.add_label("cat").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "unknown label name")]
+fn test_classifier_unknown_label_name() {
+    let mut classifier = PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.set_label_by_name("bird");
+}
+
+#[test]
+fn test_classifier_config_rejects_invalid_parameters() -> gnomics::Result<()> {
+    use gnomics::network_config::BlockConfig;
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    net.add(
+        PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+            .with_label_names(&["cat", "dog"]),
+    );
+    let config = net.to_config()?;
+
+    // Hand-edited configs get errors instead of builder panics
+    let edits: [fn(&mut BlockConfig); 3] = [
+        |c| {
+            if let BlockConfig::PatternClassifier { label_names, .. } = c {
+                label_names[1] = "cat".into();
+            }
+        },
+        |c| {
+            if let BlockConfig::PatternClassifier { temperature, .. } = c {
+                *temperature = 0.0;
+            }
+        },
+        |c| {
+            if let BlockConfig::PatternClassifier { label_thresholds, .. } = c {
+                label_thresholds[0] = 1.5;
+            }
+        },
+    ];
+    for edit in edits {
+        let mut bad = config.clone();
+        edit(&mut bad.block_info[0].config);
+        assert!(Network::from_config(&bad).is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_classifier_grown_labels_round_trip() -> gnomics::Result<()> {
+    use gnomics::network_config::NetworkConfig;
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 256, 2, 0));
+    let classifier = net.add(
+        PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 3)
+            .with_label_names(&["cat", "dog"]),
+    );
+    net.connect_to_input(encoder, classifier)?;
+    net.build()?;
+    net.get_mut::<PatternClassifier>(classifier)?.init()?;
+
+    let train = |net: &mut Network, samples: &[(usize, &str)]| -> gnomics::Result<()> {
+        for _ in 0..10 {
+            for &(value, name) in samples {
+                net.get_mut::<DiscreteTransformer>(encoder)?
+                    .set_value(value);
+                net.get_mut::<PatternClassifier>(classifier)?
+                    .set_label_by_name(name);
+                net.execute(true)?;
+            }
+        }
+        Ok(())
+    };
+    train(&mut net, &[(0, "cat"), (1, "dog")])?;
+    net.get_mut::<PatternClassifier>(classifier)?
+        .add_label("bird")?;
+    train(&mut net, &[(2, "bird")])?;
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let ids: Vec<_> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<PatternClassifier>(id).is_ok())
+        .unwrap();
+    let restored_encoder = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    {
+        let c = loaded.get::<PatternClassifier>(restored)?;
+        assert_eq!(c.label_names(), ["cat", "dog", "bird"]);
+        assert_eq!(c.num_s(), 192);
+        assert_eq!(
+            c.memory().get_all_permanences(),
+            net.get::<PatternClassifier>(classifier)?
+                .memory()
+                .get_all_permanences()
+        );
+    }
+
+    for (value, name) in [(0, "cat"), (1, "dog"), (2, "bird")] {
+        loaded
+            .get_mut::<DiscreteTransformer>(restored_encoder)?
+            .set_value(value);
+        loaded.execute(false)?;
+        let c = loaded.get::<PatternClassifier>(restored)?;
+        assert_eq!(c.get_predicted_label_name(), name);
+        // Receptors of the grown group match too, not just permanences
+        assert_eq!(
+            c.memory().addrs(191),
+            net.get::<PatternClassifier>(classifier)?
+                .memory()
+                .addrs(191)
+        );
+    }
+
+    Ok(())
+}
+
+fn discrete_classifier(num_v: usize, num_l: usize) -> (DiscreteTransformer, PatternClassifier) {
+    let encoder = DiscreteTransformer::new(num_v, 256, 2, 0);
+    let mut classifier =
+        PatternClassifier::new(num_l, num_l * 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.input_mut().add_child(encoder.output(), 0);
+    classifier.init().unwrap();
+    (encoder, classifier)
+}
+
+#[test]
+fn test_classifier_rejects_novel_input() {
+    let (mut encoder, classifier) = discrete_classifier(8, 3);
+    let mut classifier = classifier.with_unknown_threshold(0.8);
+    train_discrete(&mut encoder, &mut classifier, &[(0, 0), (1, 1), (2, 2)], 10);
+
+    for (value, label) in [(0, 0), (1, 1), (2, 2)] {
+        predict_discrete(&mut encoder, &mut classifier, value);
+        assert!(!classifier.is_unknown());
+        assert_eq!(classifier.predict(), Some(label));
+        assert!(classifier.get_scores()[label] >= 0.8);
+    }
+
+    // Values never trained on match no group well
+    for value in [5, 7] {
+        predict_discrete(&mut encoder, &mut classifier, value);
+        assert!(classifier.is_unknown());
+        assert_eq!(classifier.predict(), None);
+    }
+}
+
+#[test]
+fn test_classifier_confidences_and_top_k() {
+    let (mut encoder, mut classifier) = discrete_classifier(4, 3);
+    train_discrete(&mut encoder, &mut classifier, &[(0, 0), (1, 1), (2, 2)], 10);
+    predict_discrete(&mut encoder, &mut classifier, 1);
+
+    let conf = classifier.get_confidences();
+    assert!((conf.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(conf[1] > 0.9);
+
+    let top = classifier.get_top_k(2);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[0], (1, conf[1]));
+    assert!(top[0].1 >= top[1].1);
+    assert_eq!(classifier.get_top_k(10).len(), 3);
+
+    // A higher temperature flattens the distribution
+    classifier.set_temperature(10.0);
+    assert!(classifier.get_confidences()[1] < conf[1]);
+}
+
+#[test]
+fn test_classifier_untrained_labels_score_zero() {
+    let (mut encoder, mut classifier) = discrete_classifier(4, 2);
+    train_discrete(&mut encoder, &mut classifier, &[(0, 0)], 5);
+    predict_discrete(&mut encoder, &mut classifier, 0);
+
+    assert_eq!(classifier.get_scores()[1], 0.0);
+    // The default threshold never rejects
+    assert_eq!(classifier.unknown_threshold(), 0.0);
+    assert_eq!(classifier.predict(), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "temperature must be > 0")]
+fn test_classifier_invalid_temperature() {
+    PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0).with_temperature(0.0);
+}
+
+#[test]
+fn test_classifier_calibration_round_trip() -> gnomics::Result<()> {
+    use gnomics::network_config::NetworkConfig;
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 256, 2, 0));
+    let classifier = net.add(
+        PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+            .with_temperature(0.2)
+            .with_unknown_threshold(0.7),
+    );
+    net.connect_to_input(encoder, classifier)?;
+    net.build()?;
+    net.get_mut::<PatternClassifier>(classifier)?.init()?;
+
+    for _ in 0..10 {
+        for (value, label) in [(0, 0), (1, 1)] {
+            net.get_mut::<DiscreteTransformer>(encoder)?
+                .set_value(value);
+            net.get_mut::<PatternClassifier>(classifier)?
+                .set_label(label);
+            net.execute(true)?;
+        }
+    }
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let ids: Vec<_> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<PatternClassifier>(id).is_ok())
+        .unwrap();
+    let restored_encoder = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    {
+        let c = loaded.get::<PatternClassifier>(restored)?;
+        assert_eq!(c.temperature(), 0.2);
+        assert_eq!(c.unknown_threshold(), 0.7);
+    }
+
+    for value in [0, 1, 3] {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(value);
+        net.execute(false)?;
+        loaded
+            .get_mut::<DiscreteTransformer>(restored_encoder)?
+            .set_value(value);
+        loaded.execute(false)?;
+        let original = net.get::<PatternClassifier>(classifier)?;
+        let c = loaded.get::<PatternClassifier>(restored)?;
+        assert_eq!(c.get_scores(), original.get_scores());
+        assert_eq!(c.predict(), original.predict());
+    }
+
+    Ok(())
+}
+
+/// Classifier over a source whose active bits are the union of tag patterns.
+fn tag_classifier(num_tags: usize) -> (gnomics::BitFieldSource, PatternClassifier) {
+    let source = gnomics::BitFieldSource::new(num_tags * 32, 2, 0);
+    let mut classifier =
+        PatternClassifier::new(num_tags, num_tags * 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.input_mut().add_child(source.output(), 0);
+    classifier.init().unwrap();
+    (source, classifier)
+}
+
+fn show_tags(source: &mut gnomics::BitFieldSource, tags: &[usize]) {
+    let acts: Vec<usize> = tags.iter().flat_map(|&t| t * 32..t * 32 + 32).collect();
+    source.set_acts(&acts);
+    source.execute(false).unwrap();
+}
+
+#[test]
+fn test_classifier_multi_label_prediction() {
+    let (mut source, mut classifier) = tag_classifier(4);
+    let events: [&[usize]; 8] = [&[0], &[1], &[2], &[3], &[0, 1], &[1, 2], &[2, 3], &[0, 3]];
+
+    for _ in 0..20 {
+        for tags in events {
+            show_tags(&mut source, tags);
+            classifier.set_labels(tags);
+            classifier.execute(true).unwrap();
+        }
+    }
+
+    // Pairs never seen together during training are recognized too
+    classifier.clear_label();
+    let mut metrics = gnomics::MultiLabelMetrics::new(4);
+    for tags in events.iter().chain([&[0, 2][..], &[1, 3][..]].iter()) {
+        show_tags(&mut source, tags);
+        classifier.execute(false).unwrap();
+        assert_eq!(classifier.predict_labels(), *tags);
+        metrics.add(&classifier.predict_labels(), tags);
+    }
+    assert_eq!(metrics.num_samples(), 10);
+    assert_eq!(metrics.subset_accuracy(), 1.0);
+    assert_eq!(metrics.hamming_loss(), 0.0);
+    assert_eq!(metrics.micro_f1(), 1.0);
+}
+
+#[test]
+fn test_classifier_multi_label_no_cross_punishment() {
+    let (mut source, mut classifier) = tag_classifier(4);
+
+    // Both labels always share the same input: neither group may suppress the other
+    for _ in 0..20 {
+        show_tags(&mut source, &[0, 1]);
+        classifier.set_labels(&[0, 1]);
+        classifier.execute(true).unwrap();
+        show_tags(&mut source, &[2]);
+        classifier.set_label(2);
+        classifier.execute(true).unwrap();
+    }
+
+    show_tags(&mut source, &[0, 1]);
+    classifier.execute(false).unwrap();
+    assert_eq!(classifier.predict_labels(), vec![0, 1]);
+}
+
+#[test]
+fn test_classifier_set_labels() {
+    let mut classifier = PatternClassifier::new(4, 256, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0)
+        .with_label_names(&["a", "b", "c", "d"]);
+
+    classifier.set_labels(&[3, 1, 3]);
+    assert_eq!(classifier.get_active_labels(), &[1, 3]);
+    assert_eq!(classifier.get_label(), Some(1));
+
+    classifier.set_labels_by_name(&["c", "a"]);
+    assert_eq!(classifier.get_active_labels(), &[0, 2]);
+
+    classifier.set_label(1);
+    assert_eq!(classifier.get_active_labels(), &[1]);
+
+    classifier.set_labels(&[]);
+    assert_eq!(classifier.get_label(), None);
+}
+
+#[test]
+#[should_panic(expected = "label must be < num_l")]
+fn test_classifier_set_labels_invalid() {
+    let mut classifier = PatternClassifier::new(4, 256, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0);
+    classifier.set_labels(&[1, 4]);
+}
+
+#[test]
+fn test_classifier_label_thresholds() {
+    let (mut source, mut classifier) = tag_classifier(3);
+    for _ in 0..20 {
+        for tag in 0..3 {
+            show_tags(&mut source, &[tag]);
+            classifier.set_label(tag);
+            classifier.execute(true).unwrap();
+        }
+    }
+    classifier.clear_label();
+    show_tags(&mut source, &[0, 2]);
+    classifier.execute(false).unwrap();
+    assert_eq!(classifier.predict_labels(), vec![0, 2]);
+
+    // Absent labels still score a little; a zero threshold lets one through
+    classifier.set_label_threshold(1, 0.0);
+    assert_eq!(classifier.predict_labels(), vec![0, 1, 2]);
+    assert_eq!(classifier.label_thresholds(), &[0.5, 0.0, 0.5]);
+
+    classifier.set_label_thresholds(0.5);
+    assert_eq!(classifier.predict_labels(), vec![0, 2]);
+}
+
+#[test]
+fn test_classifier_label_thresholds_round_trip() -> gnomics::Result<()> {
+    use gnomics::network_config::NetworkConfig;
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    let classifier = net.add(
+        PatternClassifier::new(2, 128, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0).with_label_thresholds(0.3),
+    );
+    {
+        let c = net.get_mut::<PatternClassifier>(classifier)?;
+        c.add_label("extra")?;
+        c.set_label_threshold(2, 0.9);
+    }
+
+    let json = net.to_config()?.to_json()?;
+    let mut loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    loaded.build()?;
+    let id = loaded.block_ids().next().unwrap();
+    assert_eq!(
+        loaded.get::<PatternClassifier>(id)?.label_thresholds(),
+        &[0.3, 0.3, 0.9]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_label_metrics() {
+    let mut metrics = gnomics::MultiLabelMetrics::new(3);
+    metrics.add(&[0, 1], &[0, 1]);
+    metrics.add(&[0, 2], &[0]);
+    metrics.add(&[], &[1]);
+
+    assert_eq!(metrics.subset_accuracy(), 1.0 / 3.0);
+    assert_eq!(metrics.hamming_loss(), 2.0 / 9.0);
+    assert_eq!(metrics.precision(0), 1.0);
+    assert_eq!(metrics.recall(1), 0.5);
+    assert_eq!(metrics.precision(2), 0.0);
+    // tp = 3, fp = 1, fn = 1
+    assert_eq!(metrics.micro_f1(), 0.75);
+    assert!((metrics.macro_f1() - (1.0 + 2.0 / 3.0 + 0.0) / 3.0).abs() < 1e-12);
+}
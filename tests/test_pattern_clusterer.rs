@@ -0,0 +1,240 @@
+//! Comprehensive tests for PatternClusterer.
+//!
+//! Tests cover:
+//! - Construction and parameter validation
+//! - Online discovery of clusters from noisy patterns
+//! - Cluster ID output as a discrete SDR
+//! - Merging of near-duplicate prototypes and pruning of idle ones
+//! - Network serialization of config and learned prototypes
+
+use gnomics::{
+    blocks::{BitFieldSource, PatternClusterer},
+    Block, BlockId, InputAccess, Network, OutputAccess, Result,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Clusterer over a 512-bit source (pct_pool 1.0 so matches are exact).
+fn source_clusterer(num_c: usize, match_thr: f64) -> (BitFieldSource, PatternClusterer) {
+    let source = BitFieldSource::new(512, 2, 0);
+    let mut clusterer = PatternClusterer::new(num_c, 8, match_thr, 20, 2, 1, 1.0, 0.3, 2, 0);
+    clusterer.input_mut().add_child(source.output(), 0);
+    clusterer.init().unwrap();
+    (source, clusterer)
+}
+
+/// Base pattern `p`: 40 bits in its own 128-bit region.
+fn base_pattern(p: usize) -> Vec<usize> {
+    (p * 128..p * 128 + 40).collect()
+}
+
+/// Base pattern `p` with `num_noise` bits moved elsewhere in its region.
+fn noisy_pattern(p: usize, num_noise: usize, rng: &mut StdRng) -> Vec<usize> {
+    let mut acts = base_pattern(p);
+    for _ in 0..num_noise {
+        let i = rng.gen_range(0..acts.len());
+        acts[i] = p * 128 + rng.gen_range(40..128);
+    }
+    acts.sort_unstable();
+    acts.dedup();
+    acts
+}
+
+fn show(
+    source: &mut BitFieldSource,
+    clusterer: &mut PatternClusterer,
+    acts: &[usize],
+    learn: bool,
+) -> Option<usize> {
+    source.set_acts(acts);
+    source.execute(false).unwrap();
+    clusterer.execute(learn).unwrap();
+    clusterer.get_cluster()
+}
+
+#[test]
+fn test_pattern_clusterer_basic_construction() {
+    let c = PatternClusterer::new(32, 8, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0);
+    assert_eq!(c.num_c(), 32);
+    assert_eq!(c.num_spc(), 8);
+    assert_eq!(c.match_threshold(), 0.5);
+    assert_eq!(c.num_clusters(), 0);
+    assert_eq!(c.get_cluster(), None);
+    assert_eq!(c.output().borrow().state.num_bits(), 256);
+}
+
+#[test]
+#[should_panic(expected = "match_thr must be in (0.0, 1.0]")]
+fn test_pattern_clusterer_invalid_match_threshold() {
+    PatternClusterer::new(32, 8, 0.0, 20, 2, 1, 0.8, 0.3, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "merge_thr must be in (0.0, 1.0]")]
+fn test_pattern_clusterer_invalid_merge_threshold() {
+    PatternClusterer::new(32, 8, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0).with_merge_threshold(1.5);
+}
+
+#[test]
+fn test_pattern_clusterer_discovers_noisy_clusters() {
+    let (mut source, mut clusterer) = source_clusterer(16, 0.6);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..30 {
+        for p in 0..4 {
+            show(
+                &mut source,
+                &mut clusterer,
+                &noisy_pattern(p, 6, &mut rng),
+                true,
+            );
+        }
+    }
+
+    assert_eq!(clusterer.num_clusters(), 4);
+
+    // Every fresh noisy variant maps to its pattern's cluster
+    let ids: Vec<Option<usize>> = (0..4)
+        .map(|p| show(&mut source, &mut clusterer, &base_pattern(p), false))
+        .collect();
+    for (p, &id) in ids.iter().enumerate() {
+        assert!(id.is_some());
+        for _ in 0..5 {
+            let acts = noisy_pattern(p, 6, &mut rng);
+            assert_eq!(show(&mut source, &mut clusterer, &acts, false), id);
+        }
+    }
+
+    let mut unique = ids.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), 4);
+}
+
+#[test]
+fn test_pattern_clusterer_output_is_discrete_sdr() {
+    let (mut source, mut clusterer) = source_clusterer(4, 0.5);
+    show(&mut source, &mut clusterer, &base_pattern(0), true);
+    let id = show(&mut source, &mut clusterer, &base_pattern(1), true).unwrap();
+
+    let acts = clusterer.output().borrow().state.get_acts();
+    assert_eq!(acts, (id * 8..id * 8 + 8).collect::<Vec<_>>());
+
+    // Unknown patterns have no cluster and no output in inference
+    assert_eq!(
+        show(&mut source, &mut clusterer, &base_pattern(2), false),
+        None
+    );
+    assert_eq!(clusterer.output().borrow().state.num_set(), 0);
+    assert!(clusterer.get_match() < 0.5);
+}
+
+#[test]
+fn test_pattern_clusterer_merges_near_duplicates() {
+    let (mut source, mut clusterer) = source_clusterer(4, 1.0);
+    clusterer.set_maintenance_period(2);
+
+    // 38 of 40 bits shared: below the exact match threshold, above the merge threshold
+    let mut variant: Vec<usize> = (0..38).collect();
+    variant.extend([100, 101]);
+
+    assert_eq!(
+        show(&mut source, &mut clusterer, &base_pattern(0), true),
+        Some(0)
+    );
+    // The variant starts cluster 1, which the maintenance pass merges into cluster 0
+    assert_eq!(show(&mut source, &mut clusterer, &variant, true), Some(0));
+    assert_eq!(clusterer.num_clusters(), 1);
+    assert_eq!(clusterer.cluster_counts(), &[2, 0, 0, 0]);
+    assert_eq!(
+        clusterer.output().borrow().state.get_acts(),
+        (0..8).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_pattern_clusterer_prunes_idle_clusters() {
+    let (mut source, mut clusterer) = source_clusterer(4, 0.5);
+    clusterer.set_maintenance_period(5);
+    clusterer.set_max_idle(10);
+
+    show(&mut source, &mut clusterer, &base_pattern(0), true);
+    for _ in 0..9 {
+        show(&mut source, &mut clusterer, &base_pattern(1), true);
+    }
+    assert_eq!(clusterer.num_clusters(), 2);
+
+    for _ in 0..10 {
+        show(&mut source, &mut clusterer, &base_pattern(1), true);
+    }
+    assert_eq!(clusterer.num_clusters(), 1);
+    assert!(!clusterer.is_cluster(0));
+
+    // The pruned ID is reused by the next new cluster
+    assert_eq!(
+        show(&mut source, &mut clusterer, &base_pattern(2), true),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_pattern_clusterer_network_state_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let source = net.add(BitFieldSource::new(512, 2, 0));
+    let clusterer = net.add(
+        PatternClusterer::new(8, 4, 0.5, 20, 2, 1, 0.8, 0.3, 2, 0)
+            .with_merge_threshold(0.8)
+            .with_max_idle(500),
+    );
+    net.connect_to_input(source, clusterer)?;
+    net.build()?;
+    net.get_mut::<PatternClusterer>(clusterer)?.init()?;
+
+    for _ in 0..5 {
+        for p in 0..3 {
+            net.get_mut::<BitFieldSource>(source)?
+                .set_acts(&base_pattern(p));
+            net.execute(true)?;
+        }
+    }
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let ids: Vec<BlockId> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<PatternClusterer>(id).is_ok())
+        .unwrap();
+    let restored_source = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    {
+        let c = loaded.get::<PatternClusterer>(restored)?;
+        let original = net.get::<PatternClusterer>(clusterer)?;
+        assert_eq!(c.merge_threshold(), 0.8);
+        assert_eq!(c.max_idle(), 500);
+        assert_eq!(c.num_clusters(), 3);
+        assert_eq!(c.cluster_counts(), original.cluster_counts());
+        for id in 0..3 {
+            assert_eq!(c.get_prototype(id), original.get_prototype(id));
+        }
+    }
+
+    for p in 0..3 {
+        net.get_mut::<BitFieldSource>(source)?
+            .set_acts(&base_pattern(p));
+        net.execute(false)?;
+        loaded
+            .get_mut::<BitFieldSource>(restored_source)?
+            .set_acts(&base_pattern(p));
+        loaded.execute(false)?;
+        assert_eq!(
+            loaded.get::<PatternClusterer>(restored)?.get_cluster(),
+            net.get::<PatternClusterer>(clusterer)?.get_cluster()
+        );
+    }
+
+    Ok(())
+}
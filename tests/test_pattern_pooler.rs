@@ -6,6 +6,7 @@
 //! - Learning convergence
 //! - Integration with encoders
 //! - Sparse representation properties
+//! - Topological mode (local receptive fields and local inhibition)
 
 #![allow(unused_imports)]
 use gnomics::{Block, PatternPooler, ScalarTransformer};
@@ -220,3 +221,138 @@ fn test_pooler_sparse_representation() {
         sparsity * 100.0
     );
 }
+
+#[test]
+fn test_pooler_topology_receptive_fields_are_local() {
+    let mut encoder = ScalarTransformer::new(0.0, 1.0, 64, 8, 2, 0);
+    let topology = PoolerTopology::line(64, 32, 2, 2);
+    let mut pooler = PatternPooler::new(32, 4, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+        .with_topology(topology);
+
+    pooler.input_mut().add_child(encoder.output(), 0);
+    pooler.init().unwrap();
+
+    for s in 0..32 {
+        let field = topology.receptive_field(s);
+        assert_eq!(field.len(), 5);
+
+        let mut addrs = pooler.memory().addrs(s);
+        addrs.sort_unstable();
+        assert_eq!(addrs, field, "statelet {} pools outside its field", s);
+    }
+
+    // Fields follow the statelet position across the input
+    assert_eq!(topology.receptive_field(0), vec![0, 1, 2, 3, 4]);
+    assert_eq!(topology.receptive_field(31), vec![59, 60, 61, 62, 63]);
+
+    encoder.set_value(0.5);
+    encoder.execute(false).unwrap();
+    pooler.execute(false).unwrap();
+}
+
+#[test]
+fn test_pooler_topology_2d_fields() {
+    let topology = PoolerTopology::new(8, 8, 4, 4, 1, 1);
+
+    // Statelet (1, 2) maps to input center (3, 5)
+    let field = topology.receptive_field(2 * 4 + 1);
+    assert_eq!(field, vec![34, 35, 36, 42, 43, 44, 50, 51, 52]);
+}
+
+#[test]
+fn test_pooler_topology_local_inhibition_spreads_winners() {
+    // Dense input so every statelet has similar overlap
+    let mut encoder = ScalarTransformer::new(0.0, 1.0, 256, 256, 2, 0);
+    let topology = PoolerTopology::new(16, 16, 8, 8, 2, 1);
+    let mut pooler = PatternPooler::new(64, 8, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+        .with_topology(topology);
+
+    pooler.input_mut().add_child(encoder.output(), 0);
+    pooler.init().unwrap();
+
+    encoder.set_value(0.5);
+    encoder.execute(false).unwrap();
+    pooler.execute(false).unwrap();
+
+    let acts = pooler.output().borrow().state.get_acts();
+    assert!(!acts.is_empty());
+
+    // Every 4x4 quadrant of the statelet grid has at least one winner
+    for qy in 0..2 {
+        for qx in 0..2 {
+            let found = acts.iter().any(|&s| (s % 8) / 4 == qx && (s / 8) / 4 == qy);
+            assert!(found, "no winner in quadrant ({}, {})", qx, qy);
+        }
+    }
+}
+
+#[test]
+fn test_pooler_topology_input_size_mismatch() {
+    let encoder = ScalarTransformer::new(0.0, 1.0, 64, 8, 2, 0);
+    let mut pooler = PatternPooler::new(32, 4, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+        .with_topology(PoolerTopology::line(128, 32, 2, 2));
+
+    pooler.input_mut().add_child(encoder.output(), 0);
+    assert!(pooler.init().is_err());
+}
+
+#[test]
+#[should_panic(expected = "output grid must contain num_s statelets")]
+fn test_pooler_topology_wrong_output_grid() {
+    PatternPooler::new(32, 4, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+        .with_topology(PoolerTopology::line(64, 16, 2, 2));
+}
+
+#[test]
+fn test_pooler_topology_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let topology = PoolerTopology::new(16, 16, 8, 8, 2, 1);
+
+    let mut net = Network::new();
+    let encoder = net.add(ScalarTransformer::new(0.0, 1.0, 256, 32, 2, 0));
+    let pooler = net.add(
+        PatternPooler::new(64, 8, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0).with_topology(topology),
+    );
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+
+    let json = net.to_config()?.to_json()?;
+    let loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+
+    let loaded_pooler = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<PatternPooler>(id).is_ok())
+        .unwrap();
+    assert_eq!(
+        loaded.get::<PatternPooler>(loaded_pooler)?.topology(),
+        Some(&topology)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pooler_topology_config_rejects_invalid_grid() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    net.add(
+        PatternPooler::new(64, 8, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0)
+            .with_topology(PoolerTopology::new(16, 16, 8, 8, 2, 1)),
+    );
+    let json = net.to_config()?.to_json()?;
+
+    // Hand-edited configs bypass PoolerTopology::new()
+    for (from, to) in [
+        ("\"output_width\": 8", "\"output_width\": 0"),
+        ("\"input_height\": 16", "\"input_height\": 0"),
+        ("\"output_height\": 8", "\"output_height\": 4"),
+    ] {
+        assert!(json.contains(from));
+        let config = NetworkConfig::from_json(&json.replace(from, to))?;
+        assert!(Network::from_config(&config).is_err());
+    }
+
+    Ok(())
+}
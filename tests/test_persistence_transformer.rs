@@ -5,6 +5,7 @@
 //! - Reset on significant value changes
 //! - Stable value tracking
 //! - Temporal encoding properties
+//! - Configurable stability threshold and its serialization
 
 use gnomics::{Block, OutputAccess, PersistenceTransformer};
 
@@ -481,3 +482,55 @@ fn test_persistence_practical_temperature_example() {
     // Stable patterns at different persistence should differ
     assert_ne!(stable_pattern, hot_pattern);
 }
+
+#[test]
+fn test_persistence_threshold_sensitivity() {
+    // 2% threshold: 3 units on a 0-100 range is a change
+    let mut strict =
+        PersistenceTransformer::new(0.0, 100.0, 1024, 128, 100, 2, 0).with_threshold(0.02);
+    // 50% threshold: only large jumps reset
+    let mut loose =
+        PersistenceTransformer::new(0.0, 100.0, 1024, 128, 100, 2, 0).with_threshold(0.5);
+
+    for pt in [&mut strict, &mut loose] {
+        pt.set_value(80.0);
+        for _ in 0..5 {
+            pt.execute(false).unwrap();
+        }
+        pt.set_value(83.0);
+        pt.execute(false).unwrap();
+    }
+    assert_eq!(strict.get_counter(), 0);
+    assert_eq!(loose.get_counter(), 5);
+
+    loose.set_value(40.0); // 43% of range
+    loose.execute(false).unwrap();
+    assert_eq!(loose.get_counter(), 6);
+}
+
+#[test]
+fn test_persistence_threshold_config_round_trip() -> gnomics::Result<()> {
+    use gnomics::network_config::{BlockConfig, NetworkConfig};
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    net.add(PersistenceTransformer::new(0.0, 10.0, 512, 64, 20, 2, 0).with_threshold(0.25));
+    net.build()?;
+
+    let json = net.to_config()?.to_json()?;
+    let loaded = Network::from_config(&NetworkConfig::from_json(&json)?)?;
+    let id = loaded.block_ids().next().unwrap();
+    assert_eq!(loaded.get::<PersistenceTransformer>(id)?.threshold(), 0.25);
+
+    // Configs written before the threshold existed load with the old 10% default
+    let legacy: BlockConfig = serde_json::from_str(
+        r#"{"PersistenceTransformer":{"min_val":0.0,"max_val":1.0,"num_s":64,"num_as":8,"max_step":10,"num_t":2,"seed":0}}"#,
+    )
+    .unwrap();
+    match legacy {
+        BlockConfig::PersistenceTransformer { threshold, .. } => assert_eq!(threshold, 0.1),
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! Comprehensive tests for RdseTransformer.
+//!
+//! Tests cover:
+//! - Encoding of unbounded and drifting values
+//! - Overlap as a function of bucket distance
+//! - Seed determinism
+//! - Network serialization of config and offset state
+
+use gnomics::{
+    blocks::{PatternPooler, RdseTransformer},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+fn encode(rdse: &mut RdseTransformer, value: f64) -> BitField {
+    rdse.set_value(value);
+    rdse.execute(false).unwrap();
+    rdse.output().borrow().state.clone()
+}
+
+#[test]
+fn test_rdse_basic_construction() {
+    let rdse = RdseTransformer::new(0.25, 2048, 40, 3, 7);
+    assert_eq!(rdse.resolution(), 0.25);
+    assert_eq!(rdse.num_s(), 2048);
+    assert_eq!(rdse.num_as(), 40);
+    assert_eq!(rdse.output().borrow().num_t(), 3);
+}
+
+#[test]
+#[should_panic(expected = "num_as must be <= num_s")]
+fn test_rdse_invalid_num_as() {
+    RdseTransformer::new(1.0, 32, 64, 2, 0);
+}
+
+#[test]
+fn test_rdse_unbounded_values() {
+    let mut rdse = RdseTransformer::new(1.0, 2048, 40, 2, 0);
+
+    for &v in &[0.0, -1e9, 1e12, 3.5, -7.25, 1e15] {
+        let bf = encode(&mut rdse, v);
+        assert_eq!(bf.num_set(), 40, "value {} should encode to 40 bits", v);
+    }
+}
+
+#[test]
+fn test_rdse_overlap_tracks_distance() {
+    let mut rdse = RdseTransformer::new(1.0, 4096, 40, 2, 0);
+    let base = encode(&mut rdse, 100.0);
+
+    let mut prev_overlap = 40;
+    for d in 1..=40 {
+        let bf = encode(&mut rdse, 100.0 + d as f64);
+        let overlap = base.num_similar(&bf);
+
+        // About num_as - d shared bits (allowing for rare hash collisions)
+        assert!(
+            overlap <= prev_overlap + 1,
+            "overlap should shrink with distance"
+        );
+        assert!(
+            overlap + 3 >= 40 - d && overlap <= 40 - d + 3,
+            "distance {}: overlap {}",
+            d,
+            overlap
+        );
+        prev_overlap = overlap;
+    }
+
+    // Far-away values are nearly disjoint
+    let far = encode(&mut rdse, 1_000.0);
+    assert!(base.num_similar(&far) <= 3);
+}
+
+#[test]
+fn test_rdse_same_bucket_same_pattern() {
+    let mut rdse = RdseTransformer::new(1.0, 2048, 40, 2, 0);
+    let a = encode(&mut rdse, 10.0);
+    let b = encode(&mut rdse, 10.4);
+    let c = encode(&mut rdse, 10.6);
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_rdse_seed_determinism() {
+    let mut a = RdseTransformer::new(1.0, 2048, 40, 2, 11);
+    let mut b = RdseTransformer::new(1.0, 2048, 40, 2, 11);
+    let mut c = RdseTransformer::new(1.0, 2048, 40, 2, 12);
+
+    for &v in &[5.0, 6.0, 100.0] {
+        let (ea, eb, ec) = (encode(&mut a, v), encode(&mut b, v), encode(&mut c, v));
+        assert_eq!(ea, eb);
+        assert!(
+            ea.num_similar(&ec) < 10,
+            "different seeds should give different codes"
+        );
+    }
+}
+
+#[test]
+fn test_rdse_change_detection() {
+    let mut rdse = RdseTransformer::new(1.0, 2048, 40, 2, 0);
+    encode(&mut rdse, 3.0);
+    assert!(rdse.output().borrow().has_changed());
+
+    // Same bucket: output unchanged
+    encode(&mut rdse, 3.2);
+    assert!(!rdse.output().borrow().has_changed());
+
+    encode(&mut rdse, 9.0);
+    assert!(rdse.output().borrow().has_changed());
+}
+
+#[test]
+fn test_rdse_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(RdseTransformer::new(0.5, 1024, 20, 2, 3));
+    let pooler = net.add(PatternPooler::new(
+        512, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    for &v in &[250.0, 251.0, 252.5] {
+        net.get_mut::<RdseTransformer>(encoder)?.set_value(v);
+        net.execute(true)?;
+    }
+    let expected = net
+        .get::<RdseTransformer>(encoder)?
+        .output()
+        .borrow()
+        .state
+        .clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<RdseTransformer>(id).is_ok())
+        .unwrap();
+
+    // Offset survives serialization, so the same value gives the same code
+    assert_eq!(
+        loaded.get::<RdseTransformer>(restored)?.offset(),
+        Some(250.0)
+    );
+    loaded
+        .get_mut::<RdseTransformer>(restored)?
+        .set_value(252.5);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<RdseTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        expected
+    );
+
+    Ok(())
+}
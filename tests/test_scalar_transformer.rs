@@ -392,3 +392,186 @@ fn test_scalar_small_active_percentage() {
 
     assert_eq!(st.output().borrow().state.num_set(), 100);
 }
+
+#[test]
+fn test_scalar_log_scale_resolution() {
+    use gnomics::blocks::ScalarScale;
+
+    // Latencies from 1 ms to 60 s
+    let mut st =
+        ScalarTransformer::new(1.0, 60_000.0, 2048, 64, 2, 0).with_scale(ScalarScale::Log);
+
+    let mut encode = |v: f64| {
+        st.set_value(v);
+        st.execute(false).unwrap();
+        st.output().borrow().state.clone()
+    };
+
+    // 1 ms vs 2 ms are as distinguishable as 10 s vs 20 s
+    let low = encode(1.0).num_similar(&encode(2.0));
+    let high = encode(10_000.0).num_similar(&encode(20_000.0));
+    assert!((low as i64 - high as i64).abs() <= 1);
+    assert!(low < 64);
+
+    // Linear scale would put 1 ms and 50 ms in the same window
+    assert!(encode(1.0).num_similar(&encode(50.0)) < 32);
+}
+
+#[test]
+fn test_scalar_sqrt_scale() {
+    use gnomics::blocks::ScalarScale;
+
+    let st = ScalarTransformer::new(0.0, 100.0, 1024, 64, 2, 0).with_scale(ScalarScale::Sqrt);
+    assert!((st.position(25.0) - 0.5).abs() < 1e-12);
+    assert_eq!(st.position(100.0), 1.0);
+}
+
+#[test]
+#[should_panic(expected = "custom curve must be non-decreasing")]
+fn test_scalar_custom_curve_must_increase() {
+    use gnomics::blocks::ScalarScale;
+
+    ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0)
+        .with_scale(ScalarScale::Custom(vec![0.0, 0.7, 0.5, 1.0]));
+}
+
+#[test]
+fn test_scalar_adaptive_min_max() {
+    use gnomics::blocks::ScalarAdapt;
+
+    let mut st = ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0).with_adapt(ScalarAdapt::MinMax);
+
+    // Raw value is kept, range grows when learning
+    st.set_value(10.0);
+    assert_eq!(st.get_value(), 10.0);
+    st.execute(true).unwrap();
+    assert_eq!(st.max_val(), 10.0);
+
+    st.set_value(-2.0);
+    st.execute(true).unwrap();
+    assert_eq!(st.min_val(), -2.0);
+    assert_eq!(st.max_val(), 10.0);
+
+    // Values inside the range do not change it
+    st.set_value(3.0);
+    st.execute(true).unwrap();
+    assert_eq!((st.min_val(), st.max_val()), (-2.0, 10.0));
+
+    // Extremes of the adapted range map to the ends of statelet space
+    st.set_value(10.0);
+    st.execute(false).unwrap();
+    assert_eq!(*st.output().borrow().state.get_acts().last().unwrap(), 1023);
+}
+
+#[test]
+fn test_scalar_adaptive_quantiles() {
+    use gnomics::blocks::ScalarAdapt;
+
+    let mut st = ScalarTransformer::new(0.0, 1.0, 1024, 64, 2, 0)
+        .with_adapt(ScalarAdapt::Quantile { num_points: 5, rate: 0.005 });
+
+    // Heavy-tailed data: most values near 1, a few up to 1000
+    let mut x: u64 = 12345;
+    for _ in 0..20_000 {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let u = (x >> 11) as f64 / (1u64 << 53) as f64;
+        st.set_value(1.0 / (1.0 - u * 0.999));
+        st.execute(true).unwrap();
+    }
+
+    let q = st.quantiles().to_vec();
+    assert_eq!(q.len(), 5);
+    assert!(q.windows(2).all(|w| w[0] <= w[1]));
+
+    // Median of 1 / (1 - 0.999u) is about 2
+    assert!(q[2] > 1.5 && q[2] < 3.0, "median estimate {}", q[2]);
+
+    // Quantile ranks spread the bulk of the data over the statelets
+    assert!((st.position(q[2]) - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_scalar_adapted_state_round_trip() -> gnomics::Result<()> {
+    use gnomics::blocks::{ScalarAdapt, ScalarScale};
+    use gnomics::network_config::NetworkConfig;
+    use gnomics::Network;
+
+    let mut net = Network::new();
+    let encoder = net.add(
+        ScalarTransformer::new(1.0, 10.0, 1024, 64, 2, 0)
+            .with_scale(ScalarScale::Log)
+            .with_adapt(ScalarAdapt::MinMax),
+    );
+    net.build()?;
+
+    for &v in &[0.5, 3.0, 400.0] {
+        net.get_mut::<ScalarTransformer>(encoder)?.set_value(v);
+        net.execute(true)?;
+    }
+    net.get_mut::<ScalarTransformer>(encoder)?.set_value(20.0);
+    net.execute(false)?;
+    let expected = net.get::<ScalarTransformer>(encoder)?.output().borrow().state.clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let restored = loaded.block_ids().next().unwrap();
+
+    let st = loaded.get::<ScalarTransformer>(restored)?;
+    assert_eq!(st.scale(), &ScalarScale::Log);
+    assert_eq!((st.min_val(), st.max_val()), (0.5, 400.0));
+
+    loaded.get_mut::<ScalarTransformer>(restored)?.set_value(20.0);
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded.get::<ScalarTransformer>(restored)?.output().borrow().state,
+        expected
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scalar_config_without_scale_fields() {
+    use gnomics::blocks::{ScalarAdapt, ScalarScale};
+    use gnomics::network_config::BlockConfig;
+
+    // Configs saved before scales existed still load as linear/fixed
+    let json = r#"{"ScalarTransformer":{"min_val":0.0,"max_val":1.0,"num_s":64,"num_as":8,"num_t":2,"seed":0}}"#;
+    let config: BlockConfig = serde_json::from_str(json).unwrap();
+    match config {
+        BlockConfig::ScalarTransformer { scale, adapt, .. } => {
+            assert_eq!(scale, ScalarScale::Linear);
+            assert_eq!(adapt, ScalarAdapt::Fixed);
+        }
+        _ => panic!("wrong config variant"),
+    }
+}
+
+#[test]
+fn test_scalar_decode_range_inverts_log_scale() {
+    use gnomics::blocks::ScalarScale;
+
+    let mut encoder =
+        ScalarTransformer::new(1.0, 60_000.0, 2048, 64, 2, 0).with_scale(ScalarScale::Log);
+
+    for value in [3.0, 250.0, 42_000.0] {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        let acts = encoder.output().borrow().state.get_acts();
+        let (lo, hi) = encoder.decode_range(&acts).unwrap();
+        assert!(lo <= value * 1.01 && hi >= value * 0.99, "{value}: {lo}..{hi}");
+        assert!(hi / lo < 1.02);
+    }
+
+    // Two windows decode to the range spanning both values
+    encoder.set_value(10.0);
+    encoder.execute(false).unwrap();
+    let mut acts = encoder.output().borrow().state.get_acts();
+    encoder.set_value(1000.0);
+    encoder.execute(false).unwrap();
+    acts.extend(encoder.output().borrow().state.get_acts());
+    let (lo, hi) = encoder.decode_range(&acts).unwrap();
+    assert!((lo - 10.0).abs() < 0.2 && (hi - 1000.0).abs() < 20.0);
+
+    assert_eq!(encoder.decode_range(&[]), None);
+}
@@ -0,0 +1,187 @@
+//! Comprehensive tests for SdrClassifier.
+//!
+//! Tests cover:
+//! - Construction and parameter validation
+//! - Label classification of the current input
+//! - Multi-step-ahead prediction on top of a SequenceLearner
+//! - Scalar value prediction over buckets
+//! - Network serialization of config and learned weights
+
+use gnomics::{
+    blocks::{DiscreteTransformer, SdrClassifier, SequenceLearner},
+    Block, BlockId, InputAccess, Network, OutputAccess, Result,
+};
+
+#[test]
+fn test_sdr_classifier_basic_construction() {
+    let c = SdrClassifier::new(10, 1, 0.1, 2, 0);
+    assert_eq!(c.num_l(), 10);
+    assert_eq!(c.steps(), 1);
+    assert_eq!(c.alpha(), 0.1);
+    assert_eq!(c.value_range(), None);
+    assert_eq!(c.output().borrow().state.num_bits(), 10);
+}
+
+#[test]
+#[should_panic(expected = "alpha must be > 0")]
+fn test_sdr_classifier_invalid_alpha() {
+    SdrClassifier::new(10, 1, 0.0, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "value range not set")]
+fn test_sdr_classifier_value_without_range() {
+    let mut c = SdrClassifier::new(10, 1, 0.1, 2, 0);
+    c.set_value(1.0);
+}
+
+#[test]
+fn test_sdr_classifier_current_input() {
+    let mut encoder = DiscreteTransformer::new(8, 256, 2, 0);
+    let mut c = SdrClassifier::new(2, 0, 0.1, 2, 0);
+    c.input_mut().add_child(encoder.output(), 0);
+    c.init().unwrap();
+
+    // Even values are label 0, odd values label 1
+    for _ in 0..10 {
+        for value in 0..8 {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            c.set_label(value % 2);
+            c.execute(true).unwrap();
+        }
+    }
+
+    c.clear_label();
+    for value in 0..8 {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        c.execute(false).unwrap();
+        assert_eq!(c.get_predicted_label(), value % 2);
+        assert!(c.get_probabilities()[value % 2] > 0.9);
+        assert_eq!(c.output().borrow().state.get_acts(), vec![value % 2]);
+    }
+}
+
+fn sequence_network(steps: usize) -> Result<(Network, BlockId, BlockId)> {
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 256, 2, 0));
+    let learner = net.add(SequenceLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+    let readout = net.add(SdrClassifier::new(4, steps, 0.2, 2, 0));
+    net.connect_to_input(encoder, learner)?;
+    net.connect_to_input(learner, readout)?;
+    net.build()?;
+    net.get_mut::<SequenceLearner>(learner)?.init()?;
+    net.get_mut::<SdrClassifier>(readout)?.init()?;
+    Ok((net, encoder, readout))
+}
+
+#[test]
+fn test_sdr_classifier_multi_step_prediction() -> Result<()> {
+    for steps in [1, 2] {
+        let (mut net, encoder, readout) = sequence_network(steps)?;
+
+        // Cycle 0 -> 1 -> 2 -> 3 -> 0; the label at each step is the current value
+        for _ in 0..30 {
+            for value in 0..4 {
+                net.get_mut::<DiscreteTransformer>(encoder)?
+                    .set_value(value);
+                net.get_mut::<SdrClassifier>(readout)?.set_label(value);
+                net.execute(true)?;
+            }
+        }
+
+        net.get_mut::<SdrClassifier>(readout)?.clear_label();
+        for value in 0..4 {
+            net.get_mut::<DiscreteTransformer>(encoder)?
+                .set_value(value);
+            net.execute(false)?;
+            assert_eq!(
+                net.get::<SdrClassifier>(readout)?.get_predicted_label(),
+                (value + steps) % 4
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_sdr_classifier_value_prediction() {
+    let mut encoder = DiscreteTransformer::new(4, 256, 2, 0);
+    let mut c = SdrClassifier::new(10, 0, 0.1, 2, 0).with_value_range(0.0, 100.0);
+    c.input_mut().add_child(encoder.output(), 0);
+    c.init().unwrap();
+
+    let targets = [12.0, 37.0, 61.0, 88.0];
+    for _ in 0..50 {
+        for (value, &target) in targets.iter().enumerate() {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            c.set_value(target);
+            c.execute(true).unwrap();
+        }
+    }
+
+    c.clear_label();
+    for (value, &target) in targets.iter().enumerate() {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        c.execute(false).unwrap();
+        // Bucket averages converge on the actual values, not bucket centers
+        assert!((c.get_predicted_value() - target).abs() < 0.5);
+    }
+}
+
+#[test]
+fn test_sdr_classifier_network_state_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(4, 256, 2, 0));
+    let readout = net.add(SdrClassifier::new(8, 0, 0.1, 2, 0).with_value_range(0.0, 8.0));
+    net.connect_to_input(encoder, readout)?;
+    net.build()?;
+    net.get_mut::<SdrClassifier>(readout)?.init()?;
+
+    for _ in 0..10 {
+        for value in 0..4 {
+            net.get_mut::<DiscreteTransformer>(encoder)?
+                .set_value(value);
+            net.get_mut::<SdrClassifier>(readout)?
+                .set_value(value as f64 * 2.0 + 0.3);
+            net.execute(true)?;
+        }
+    }
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let ids: Vec<BlockId> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<SdrClassifier>(id).is_ok())
+        .unwrap();
+    let restored_encoder = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    {
+        let c = loaded.get::<SdrClassifier>(restored)?;
+        let original = net.get::<SdrClassifier>(readout)?;
+        assert_eq!(c.value_range(), Some((0.0, 8.0)));
+        // JSON keeps weights to within float parsing precision
+        let close = |a: &[f64], b: &[f64]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12)
+        };
+        assert!(close(c.weights(), original.weights()));
+        assert!(close(c.bucket_values(), original.bucket_values()));
+    }
+
+    loaded
+        .get_mut::<DiscreteTransformer>(restored_encoder)?
+        .set_value(2);
+    loaded.execute(false)?;
+    assert!((loaded.get::<SdrClassifier>(restored)?.get_predicted_value() - 4.3).abs() < 0.1);
+
+    Ok(())
+}
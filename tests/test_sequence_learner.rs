@@ -1,8 +1,11 @@
 //! Tests for SequenceLearner block
 
 #![allow(unused_imports)]
-use gnomics::blocks::{DiscreteTransformer, SequenceLearner};
-use gnomics::{Block, ContextAccess, InputAccess, OutputAccess, ScalarTransformer};
+use gnomics::blocks::{CyclicTransformer, DiscreteTransformer, PatternPooler, SequenceLearner};
+use gnomics::{
+    Block, ContextAccess, InputAccess, Network, OutputAccess, OutputPort, Result,
+    ScalarTransformer,
+};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -410,3 +413,251 @@ fn test_sequence_learner_invalid_threshold() {
 fn test_sequence_learner_insufficient_history() {
     SequenceLearner::new(10, 4, 8, 32, 20, 20, 2, 1, 1, false, 0);
 }
+
+#[test]
+fn test_sequence_learner_predicts_next_statelets() {
+    let mut encoder = DiscreteTransformer::new(5, 40, 2, 42);
+    let mut learner = SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42);
+    learner.input_mut().add_child(encoder.output(), 0);
+    learner.init().unwrap();
+    learner.set_predicted_enabled(true);
+
+    for _ in 0..10 {
+        for value in [0, 1, 2] {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            learner.execute(true).unwrap();
+        }
+    }
+
+    // After 0, the predicted output holds the statelets expected for 1
+    encoder.set_value(0);
+    encoder.execute(false).unwrap();
+    learner.execute(false).unwrap();
+    let predicted = learner.predicted().borrow().state.get_acts();
+    let mut columns: Vec<usize> = predicted.iter().map(|s| s / 10).collect();
+    columns.dedup();
+
+    encoder.set_value(1);
+    encoder.execute(false).unwrap();
+    learner.execute(false).unwrap();
+    assert_eq!(learner.get_anomaly_score(), 0.0);
+    assert_eq!(columns, encoder.output().borrow().state.get_acts());
+    assert_eq!(learner.output().borrow().state.get_acts(), predicted);
+}
+
+#[test]
+fn test_sequence_learner_predictions_disabled_by_default() {
+    let mut encoder = DiscreteTransformer::new(5, 40, 2, 42);
+    let mut learner = SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42);
+    learner.input_mut().add_child(encoder.output(), 0);
+    learner.init().unwrap();
+    assert!(!learner.predicted_enabled());
+
+    let mut step = |learner: &mut SequenceLearner, value: usize| {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        learner.execute(true).unwrap();
+        learner.predicted().borrow().state.num_set()
+    };
+
+    // Nothing is predicted until enabled, even once the sequence is learned
+    for i in 0..30 {
+        assert_eq!(step(&mut learner, i % 3), 0);
+    }
+
+    learner.set_predicted_enabled(true);
+    assert!(step(&mut learner, 0) > 0);
+
+    learner.set_predicted_enabled(false);
+    assert_eq!(learner.predicted().borrow().state.num_set(), 0);
+}
+
+#[test]
+fn test_sequence_learner_predicted_network_connection() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(5, 40, 2, 42));
+    let learner = net.add(SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42));
+    let pooler = net.add(PatternPooler::new(256, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0));
+
+    net.connect_to_input(encoder, learner)?;
+    net.connect_predicted_to_input(learner, pooler)?;
+    assert!(net.connect_predicted_to_input(encoder, pooler).is_err());
+    net.build()?;
+    net.get_mut::<SequenceLearner>(learner)?.init()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    for i in 0..30 {
+        net.get_mut::<DiscreteTransformer>(encoder)?.set_value(i % 3);
+        net.execute(true)?;
+    }
+
+    // The pooler reads this step's predictions
+    let predicted = net
+        .get::<SequenceLearner>(learner)?
+        .predicted()
+        .borrow()
+        .state
+        .get_acts();
+    assert!(!predicted.is_empty());
+    assert_eq!(
+        net.get::<PatternPooler>(pooler)?.input().state.get_acts(),
+        predicted
+    );
+
+    // The port survives a config round trip
+    let config = net.to_config()?;
+    let ports: Vec<OutputPort> = config.connections.iter().map(|c| c.source_port).collect();
+    assert!(ports.contains(&OutputPort::Predicted));
+
+    let loaded = Network::from_config(&NetworkConfig::from_json(&config.to_json()?)?)?;
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<PatternPooler>(id).is_ok())
+        .unwrap();
+    let sources = loaded.get::<PatternPooler>(restored)?.input().get_source_ports();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].1, OutputPort::Predicted);
+    assert!(loaded.get::<SequenceLearner>(sources[0].0).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_sequence_learner_explain_anomaly() {
+    let mut encoder = DiscreteTransformer::new(5, 40, 2, 42);
+    let mut learner = SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42);
+    learner.input_mut().add_child(encoder.output(), 0);
+    learner.init().unwrap();
+    learner.set_predicted_enabled(true);
+
+    let mut show = |value: usize, learn: bool| {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        learner.execute(learn).unwrap();
+        learner.explain_anomaly()
+    };
+
+    for _ in 0..10 {
+        for value in [0, 1, 2] {
+            show(value, true);
+        }
+    }
+
+    // A predicted step explains nothing
+    let explanation = show(0, true);
+    assert_eq!(explanation.anomaly_score, 0.0);
+    assert!(explanation.surprised_columns.is_empty());
+    assert!(explanation.missing_columns.is_empty());
+
+    // Skip 1: value 2's columns surprise, value 1's columns go missing
+    let explanation = show(2, true);
+    assert_eq!(explanation.anomaly_score, 1.0);
+    assert_eq!(explanation.surprised_columns, (16..24).collect::<Vec<_>>());
+    assert_eq!(explanation.expected_columns, (8..16).collect::<Vec<_>>());
+    assert_eq!(explanation.missing_columns, explanation.expected_columns);
+    assert_eq!(explanation.expected_range, None);
+
+    // Each surprised column learned 1 -> 2, which only partly matches 0 -> 2
+    assert_eq!(explanation.best_matches.len(), 8);
+    for m in &explanation.best_matches {
+        assert!(explanation.surprised_columns.contains(&m.column));
+        assert_eq!(m.statelet / 10, m.column);
+        assert_eq!(m.dendrite / 10, m.statelet);
+        assert!(m.overlap < 6);
+    }
+}
+
+#[test]
+fn test_sequence_learner_explain_anomaly_sparse_predicted() {
+    let mut encoder = DiscreteTransformer::new(5, 40, 2, 42);
+    let mut learner = SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42);
+    learner.input_mut().add_child(encoder.output(), 0);
+    learner.init().unwrap();
+    learner.set_predicted_enabled(true);
+    learner.predicted().borrow_mut().set_sparse(true);
+
+    for value in [0, 1, 2].repeat(10).into_iter().chain([0, 2]) {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        learner.execute(true).unwrap();
+    }
+
+    // Sparse predicted history explains the skip like dense history
+    let explanation = learner.explain_anomaly();
+    assert_eq!(explanation.expected_columns, (8..16).collect::<Vec<_>>());
+    assert_eq!(explanation.missing_columns, explanation.expected_columns);
+}
+
+#[test]
+fn test_network_explain_anomaly_decodes_expected_range() -> Result<()> {
+    let mut net = Network::new();
+    let encoder = net.add(ScalarTransformer::new(0.0, 100.0, 40, 8, 2, 0));
+    let learner = net.add(SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42));
+    net.connect_to_input(encoder, learner)?;
+    net.build()?;
+    net.get_mut::<SequenceLearner>(learner)?.init()?;
+    net.get_mut::<SequenceLearner>(learner)?
+        .set_predicted_enabled(true);
+
+    let values = [0.0, 50.0, 100.0];
+    for i in 0..30 {
+        net.get_mut::<ScalarTransformer>(encoder)?
+            .set_value(values[i % 3]);
+        net.execute(true)?;
+    }
+
+    // 0.0 -> 100.0 skips the expected 50.0
+    for value in [0.0, 100.0] {
+        net.get_mut::<ScalarTransformer>(encoder)?.set_value(value);
+        net.execute(false)?;
+    }
+    let explanation = net.explain_anomaly(learner)?;
+    assert_eq!(explanation.anomaly_score, 1.0);
+
+    let (lo, hi) = explanation.expected_range.unwrap();
+    assert!((lo - 50.0).abs() < 1.0, "lo = {lo}");
+    assert!((hi - 50.0).abs() < 1.0, "hi = {hi}");
+
+    // Other blocks cannot explain anomalies
+    assert!(net.explain_anomaly(encoder).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_network_explain_anomaly_decodes_wrapped_cyclic_range() -> Result<()> {
+    let mut net = Network::new();
+    let encoder = net.add(CyclicTransformer::new(360.0, 40, 8, 2, 0));
+    let learner = net.add(SequenceLearner::new(40, 10, 10, 12, 6, 20, 2, 1, 3, false, 42));
+    net.connect_to_input(encoder, learner)?;
+    net.build()?;
+    net.get_mut::<SequenceLearner>(learner)?.init()?;
+    net.get_mut::<SequenceLearner>(learner)?
+        .set_predicted_enabled(true);
+
+    // 355 degrees encodes as a window wrapping from statelet 39 to 6
+    let values = [120.0, 355.0, 240.0];
+    for i in 0..30 {
+        net.get_mut::<CyclicTransformer>(encoder)?
+            .set_value(values[i % 3]);
+        net.execute(true)?;
+    }
+
+    // 120 -> 240 skips the expected 355
+    for value in [120.0, 240.0] {
+        net.get_mut::<CyclicTransformer>(encoder)?.set_value(value);
+        net.execute(false)?;
+    }
+    let explanation = net.explain_anomaly(learner)?;
+    assert_eq!(explanation.anomaly_score, 1.0);
+
+    // One statelet is 9 degrees wide
+    let (lo, hi) = explanation.expected_range.unwrap();
+    assert!((lo - 355.0).abs() < 9.0, "lo = {lo}");
+    assert!((hi - 355.0).abs() < 9.0, "hi = {hi}");
+
+    Ok(())
+}
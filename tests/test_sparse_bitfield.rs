@@ -0,0 +1,187 @@
+//! Integration tests for SparseBitField.
+//!
+//! Verifies that the sparse representation matches BitField for every shared
+//! operation, and that sparse outputs flow through BlockInput and Network.
+
+use gnomics::{
+    blocks::{PatternPooler, ScalarTransformer},
+    BitField, Block, BlockInput, BlockOutput, Network, OutputAccess, Result, SparseBitField,
+};
+use proptest::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// =============================================================================
+// BlockInput / BlockOutput
+// =============================================================================
+
+#[test]
+fn test_pull_from_mixed_children() {
+    let mut dense = BlockOutput::new();
+    dense.setup(2, 100);
+    dense.state.set_acts(&[1, 99]);
+    dense.store();
+
+    let mut sparse = BlockOutput::new();
+    sparse.setup(2, 5000);
+    sparse.set_sparse(true);
+    sparse.state.set_acts(&[0, 2500, 4999]);
+    sparse.store();
+
+    let dense = Rc::new(RefCell::new(dense));
+    let sparse = Rc::new(RefCell::new(sparse));
+
+    let mut input = BlockInput::new();
+    input.add_child(Rc::clone(&dense), 0);
+    input.add_child(Rc::clone(&sparse), 0);
+    input.pull();
+
+    let offset = 100usize.div_ceil(gnomics::BITS_PER_WORD) * gnomics::BITS_PER_WORD;
+    assert_eq!(
+        input.state.get_acts(),
+        vec![1, 99, offset, offset + 2500, offset + 4999]
+    );
+
+    // Changing the sparse child clears stale bits in its range
+    {
+        let mut s = sparse.borrow_mut();
+        s.step();
+        s.state.set_acts(&[10]);
+        s.store();
+    }
+    input.pull();
+    assert_eq!(input.state.get_acts(), vec![1, 99, offset + 10]);
+}
+
+#[test]
+fn test_network_with_sparse_encoder_output() -> Result<()> {
+    // Same pipeline twice: dense vs sparse encoder history must give identical
+    // pooler output
+    let run = |sparse: bool| -> Result<Vec<Vec<usize>>> {
+        let mut net = Network::new();
+        let encoder = net.add(ScalarTransformer::new(0.0, 10.0, 4096, 64, 2, 0));
+        let pooler = net.add(PatternPooler::new(
+            512, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+        ));
+        net.set_sparse_output(encoder, sparse)?;
+
+        net.connect_to_input(encoder, pooler)?;
+        net.build()?;
+        net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+        let mut outputs = Vec::new();
+        for &v in &[1.0, 4.0, 7.5, 1.0] {
+            net.get_mut::<ScalarTransformer>(encoder)?.set_value(v);
+            net.execute(true)?;
+            outputs.push(
+                net.get::<PatternPooler>(pooler)?
+                    .output()
+                    .borrow()
+                    .state
+                    .get_acts(),
+            );
+        }
+        Ok(outputs)
+    };
+
+    assert_eq!(run(false)?, run(true)?);
+    Ok(())
+}
+
+#[test]
+fn test_network_config_keeps_sparse_output() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(ScalarTransformer::new(0.0, 10.0, 4096, 64, 2, 0));
+    let pooler = net.add(PatternPooler::new(
+        512, 20, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.set_sparse_output(encoder, true)?;
+
+    let config = net.to_config()?;
+    let sparse: Vec<bool> = config.block_info.iter().map(|i| i.sparse_output).collect();
+    assert_eq!(sparse, vec![true, false]);
+
+    let loaded = Network::from_config(&NetworkConfig::from_json(&config.to_json()?)?)?;
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<ScalarTransformer>(id).is_ok())
+        .unwrap();
+    assert!(loaded
+        .get::<ScalarTransformer>(restored)?
+        .output()
+        .borrow()
+        .is_sparse());
+
+    Ok(())
+}
+
+// =============================================================================
+// Property-Based Tests
+// =============================================================================
+
+fn dense_from(num_bits: usize, acts: &[usize]) -> BitField {
+    let mut bf = BitField::new(num_bits);
+    bf.set_acts(acts);
+    bf
+}
+
+fn sparse_from(num_bits: usize, acts: &[usize]) -> SparseBitField {
+    let mut sb = SparseBitField::new(num_bits);
+    sb.set_acts(acts);
+    sb
+}
+
+proptest! {
+    #[test]
+    fn prop_conversion_round_trip(
+        num_bits in 1usize..2000,
+        acts in prop::collection::vec(0usize..2000, 0..100),
+    ) {
+        let bf = dense_from(num_bits, &acts);
+        let sb = SparseBitField::from(&bf);
+
+        prop_assert_eq!(sb.get_acts(), bf.get_acts());
+        prop_assert_eq!(sb.num_set(), bf.num_set());
+        prop_assert_eq!(BitField::from(&sb), bf);
+    }
+
+    #[test]
+    fn prop_ops_match_dense(
+        num_bits in 1usize..2000,
+        a in prop::collection::vec(0usize..2000, 0..100),
+        b in prop::collection::vec(0usize..2000, 0..100),
+    ) {
+        let (da, db) = (dense_from(num_bits, &a), dense_from(num_bits, &b));
+        let (sa, sb) = (sparse_from(num_bits, &a), sparse_from(num_bits, &b));
+
+        prop_assert_eq!((&sa & &sb).get_acts(), (&da & &db).get_acts());
+        prop_assert_eq!((&sa | &sb).get_acts(), (&da | &db).get_acts());
+        prop_assert_eq!((&sa ^ &sb).get_acts(), (&da ^ &db).get_acts());
+        prop_assert_eq!(sa.num_similar(&sb), da.num_similar(&db));
+        prop_assert_eq!(sa.num_similar_dense(&db), da.num_similar(&db));
+    }
+
+    #[test]
+    fn prop_bit_ops_match_dense(
+        num_bits in 1usize..500,
+        ops in prop::collection::vec((0usize..500, 0u8..3), 0..200),
+    ) {
+        let mut bf = BitField::new(num_bits);
+        let mut sb = SparseBitField::new(num_bits);
+
+        for (idx, op) in ops {
+            let idx = idx % num_bits;
+            match op {
+                0 => { bf.set_bit(idx); sb.set_bit(idx); }
+                1 => { bf.clear_bit(idx); sb.clear_bit(idx); }
+                _ => { bf.toggle_bit(idx); sb.toggle_bit(idx); }
+            }
+            prop_assert_eq!(sb.get_bit(idx), bf.get_bit(idx));
+        }
+
+        prop_assert_eq!(sb.get_acts(), bf.get_acts());
+    }
+}
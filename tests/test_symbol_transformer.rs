@@ -0,0 +1,183 @@
+//! Comprehensive tests for SymbolTransformer.
+//!
+//! Tests cover:
+//! - Arbitrary keys and strings without panics
+//! - Determinism and near-orthogonality of unrelated symbols
+//! - Similarity hints
+//! - Change detection
+//! - Network serialization of config and hint state
+
+use gnomics::{
+    blocks::{PatternPooler, SymbolTransformer},
+    BitField, Block, Network, OutputAccess, Result,
+};
+
+fn encode(st: &mut SymbolTransformer, symbol: &str) -> BitField {
+    st.set_symbol(symbol);
+    st.execute(false).unwrap();
+    st.output().borrow().state.clone()
+}
+
+#[test]
+fn test_symbol_basic_construction() {
+    let st = SymbolTransformer::new(2048, 40, 3, 0);
+    assert_eq!(st.num_s(), 2048);
+    assert_eq!(st.num_as(), 40);
+    assert_eq!(st.output().borrow().num_t(), 3);
+}
+
+#[test]
+#[should_panic(expected = "num_as must be <= num_s")]
+fn test_symbol_invalid_num_as() {
+    SymbolTransformer::new(32, 64, 2, 0);
+}
+
+#[test]
+fn test_symbol_open_vocabulary() {
+    let mut st = SymbolTransformer::new(2048, 40, 2, 0);
+
+    for key in [0, 1, u64::MAX, 0xDEAD_BEEF, 1 << 63] {
+        st.set_value(key);
+        st.execute(false).unwrap();
+        assert_eq!(st.output().borrow().state.num_set(), 40);
+    }
+    for word in ["", "user-12345", "ERROR: disk full", "日本語"] {
+        assert_eq!(encode(&mut st, word).num_set(), 40);
+    }
+}
+
+#[test]
+fn test_symbol_deterministic() {
+    let mut a = SymbolTransformer::new(2048, 40, 2, 5);
+    let mut b = SymbolTransformer::new(2048, 40, 2, 5);
+
+    // Same symbol always maps to the same pattern, in any order
+    let first = encode(&mut a, "alpha");
+    encode(&mut a, "beta");
+    assert_eq!(encode(&mut a, "alpha"), first);
+    assert_eq!(encode(&mut b, "alpha"), first);
+
+    // String and key forms are interchangeable
+    b.set_value(SymbolTransformer::key("alpha"));
+    b.execute(false).unwrap();
+    assert_eq!(b.output().borrow().state, first);
+}
+
+#[test]
+fn test_symbol_unrelated_overlap_small() {
+    let mut st = SymbolTransformer::new(2048, 40, 2, 0);
+    let patterns: Vec<BitField> = (0..50)
+        .map(|i| encode(&mut st, &format!("word{}", i)))
+        .collect();
+
+    // Expected chance overlap is 40 * 40 / 2048 < 1 bit
+    for i in 0..patterns.len() {
+        for j in (i + 1)..patterns.len() {
+            assert!(patterns[i].num_similar(&patterns[j]) <= 5);
+        }
+    }
+}
+
+#[test]
+fn test_symbol_seed_changes_patterns() {
+    let mut a = SymbolTransformer::new(2048, 40, 2, 1);
+    let mut b = SymbolTransformer::new(2048, 40, 2, 2);
+    assert!(encode(&mut a, "x").num_similar(&encode(&mut b, "x")) <= 5);
+}
+
+#[test]
+fn test_symbol_similarity_hints() {
+    let mut st = SymbolTransformer::new(2048, 40, 2, 0);
+    let animal = SymbolTransformer::key("animal");
+    st.set_hint(SymbolTransformer::key("cat"), animal, 24);
+    st.set_hint(SymbolTransformer::key("dog"), animal, 16);
+
+    let anchor = encode(&mut st, "animal");
+    let cat = encode(&mut st, "cat");
+    let dog = encode(&mut st, "dog");
+    let car = encode(&mut st, "car");
+
+    assert_eq!(cat.num_set(), 40);
+    assert!(cat.num_similar(&anchor) >= 24);
+    assert!(dog.num_similar(&anchor) >= 16);
+    assert!(cat.num_similar(&dog) >= 16);
+    assert!(cat.num_similar(&car) <= 5);
+
+    // Removing the hint restores the independent pattern
+    st.remove_hint(SymbolTransformer::key("cat"));
+    assert!(encode(&mut st, "cat").num_similar(&anchor) <= 5);
+}
+
+#[test]
+fn test_symbol_change_detection() {
+    let mut st = SymbolTransformer::new(2048, 40, 2, 0);
+    encode(&mut st, "a");
+    assert!(st.output().borrow().has_changed());
+
+    encode(&mut st, "a");
+    assert!(!st.output().borrow().has_changed());
+
+    encode(&mut st, "b");
+    assert!(st.output().borrow().has_changed());
+}
+
+#[test]
+fn test_symbol_network_config_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(SymbolTransformer::new(1024, 20, 2, 3));
+    let pooler = net.add(PatternPooler::new(
+        256, 16, 20, 2, 1, 0.8, 0.5, 0.3, false, 2, 0,
+    ));
+    net.connect_to_input(encoder, pooler)?;
+    net.build()?;
+    net.get_mut::<PatternPooler>(pooler)?.init()?;
+
+    {
+        let st = net.get_mut::<SymbolTransformer>(encoder)?;
+        st.set_hint(
+            SymbolTransformer::key("GET /a"),
+            SymbolTransformer::key("GET"),
+            10,
+        );
+        st.set_symbol("GET /a");
+    }
+    net.execute(true)?;
+    let expected = net
+        .get::<SymbolTransformer>(encoder)?
+        .output()
+        .borrow()
+        .state
+        .clone();
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+
+    let restored = loaded
+        .block_ids()
+        .find(|&id| loaded.get::<SymbolTransformer>(id).is_ok())
+        .unwrap();
+
+    // Hints survive serialization, so the same symbol gives the same code
+    assert_eq!(
+        loaded
+            .get::<SymbolTransformer>(restored)?
+            .hint(SymbolTransformer::key("GET /a")),
+        Some((SymbolTransformer::key("GET"), 10))
+    );
+    loaded
+        .get_mut::<SymbolTransformer>(restored)?
+        .set_symbol("GET /a");
+    loaded.execute(false)?;
+    assert_eq!(
+        loaded
+            .get::<SymbolTransformer>(restored)?
+            .output()
+            .borrow()
+            .state,
+        expected
+    );
+
+    Ok(())
+}
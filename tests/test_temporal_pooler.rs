@@ -0,0 +1,273 @@
+//! Comprehensive tests for TemporalPooler.
+//!
+//! Tests cover:
+//! - Stable output across the positions of a learned sequence
+//! - Sequence classification with SequenceLearner and PatternClassifier
+//! - Reset at sequence boundaries
+//! - Context masking
+//! - Network serialization of config and learned state
+
+use gnomics::{
+    blocks::{
+        BitFieldSource, DiscreteTransformer, PatternClassifier, SequenceLearner, TemporalPooler,
+    },
+    BitField, Block, BlockId, InputAccess, Network, OutputAccess, Result,
+};
+
+const SEQ_A: [usize; 4] = [0, 1, 2, 3];
+const SEQ_B: [usize; 4] = [4, 5, 6, 7];
+
+fn pooler() -> TemporalPooler {
+    TemporalPooler::new(512, 20, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0)
+}
+
+/// Run a sequence through encoder and pooler, returning the pooler output at each step.
+fn run_sequence(
+    encoder: &mut DiscreteTransformer,
+    tp: &mut TemporalPooler,
+    seq: &[usize],
+    learn: bool,
+) -> Vec<BitField> {
+    tp.reset();
+    seq.iter()
+        .map(|&value| {
+            encoder.set_value(value);
+            encoder.execute(false).unwrap();
+            tp.execute(learn).unwrap();
+            tp.output().borrow().state.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_temporal_pooler_basic_construction() {
+    let tp = pooler();
+    assert_eq!(tp.num_s(), 512);
+    assert_eq!(tp.num_as(), 20);
+    assert_eq!(tp.decay(), 0.8);
+    assert_eq!(tp.output().borrow().num_t(), 2);
+}
+
+#[test]
+#[should_panic(expected = "num_as must be <= num_s")]
+fn test_temporal_pooler_invalid_num_as() {
+    TemporalPooler::new(64, 128, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0);
+}
+
+#[test]
+fn test_temporal_pooler_output_stable_within_sequence() {
+    let mut encoder = DiscreteTransformer::new(8, 512, 2, 0);
+    let mut tp = pooler();
+    tp.input_mut().add_child(encoder.output(), 0);
+    tp.init().unwrap();
+
+    for _ in 0..20 {
+        run_sequence(&mut encoder, &mut tp, &SEQ_A, true);
+        run_sequence(&mut encoder, &mut tp, &SEQ_B, true);
+    }
+
+    let a = run_sequence(&mut encoder, &mut tp, &SEQ_A, false);
+    let b = run_sequence(&mut encoder, &mut tp, &SEQ_B, false);
+
+    // Inputs at different positions share no bits, yet pooled outputs mostly agree
+    assert!(a[1].num_similar(&a[3]) >= 14);
+    assert!(b[1].num_similar(&b[3]) >= 14);
+    // Different sequences stay distinct
+    assert!(a[3].num_similar(&b[3]) <= 5);
+}
+
+#[test]
+fn test_temporal_pooler_lets_go_of_old_sequence() {
+    let mut encoder = DiscreteTransformer::new(8, 512, 2, 0);
+    let mut tp = pooler();
+    tp.input_mut().add_child(encoder.output(), 0);
+    tp.init().unwrap();
+
+    for _ in 0..20 {
+        run_sequence(&mut encoder, &mut tp, &SEQ_A, true);
+        run_sequence(&mut encoder, &mut tp, &SEQ_B, true);
+    }
+    let b = run_sequence(&mut encoder, &mut tp, &SEQ_B, false);
+
+    // Without a reset, sequence A's representation gives way once B takes over
+    run_sequence(&mut encoder, &mut tp, &SEQ_A, false);
+    for value in SEQ_B {
+        encoder.set_value(value);
+        encoder.execute(false).unwrap();
+        tp.execute(false).unwrap();
+    }
+    assert!(tp.output().borrow().state.num_similar(&b[3]) >= 14);
+}
+
+#[test]
+fn test_temporal_pooler_reset() {
+    let mut encoder = DiscreteTransformer::new(8, 512, 2, 0);
+    let mut tp = pooler();
+    tp.input_mut().add_child(encoder.output(), 0);
+    tp.init().unwrap();
+
+    run_sequence(&mut encoder, &mut tp, &SEQ_A, false);
+    assert_eq!(tp.union().num_set(), 4 * 64);
+
+    tp.reset();
+    assert_eq!(tp.union().num_set(), 0);
+    assert!(tp.pooling().iter().all(|&p| p == 0.0));
+
+    // After a reset only the current input contributes
+    encoder.set_value(5);
+    encoder.execute(false).unwrap();
+    tp.execute(false).unwrap();
+    assert_eq!(tp.union(), &encoder.output().borrow().state);
+}
+
+#[test]
+fn test_temporal_pooler_context_mask() -> Result<()> {
+    let mut net = Network::new();
+    let input = net.add(BitFieldSource::new(128, 2, 0));
+    let mask = net.add(BitFieldSource::new(128, 2, 0));
+    let tp = net.add(TemporalPooler::new(
+        64, 4, 20, 2, 1, 0.8, 0.5, 0.3, 0.5, 2, 0,
+    ));
+    net.connect_to_input(input, tp)?;
+    net.connect_to_context(mask, tp)?;
+    net.build()?;
+    net.get_mut::<TemporalPooler>(tp)?.init()?;
+
+    net.get_mut::<BitFieldSource>(input)?
+        .set_acts(&(0..32).collect::<Vec<_>>());
+    net.get_mut::<BitFieldSource>(mask)?
+        .set_acts(&(16..64).collect::<Vec<_>>());
+    net.execute(false)?;
+
+    // Only predicted input bits enter the union
+    assert_eq!(
+        net.get::<TemporalPooler>(tp)?.union().get_acts(),
+        (16..32).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_temporal_pooler_context_width_mismatch() {
+    let input = BitFieldSource::new(128, 2, 0);
+    let mask = BitFieldSource::new(64, 2, 0);
+    let mut tp = pooler();
+    tp.input_mut().add_child(input.output(), 0);
+    gnomics::ContextAccess::context_mut(&mut tp).add_child(mask.output(), 0);
+    assert!(tp.init().is_err());
+}
+
+fn sequence_network() -> Result<(Network, [BlockId; 4])> {
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(8, 256, 2, 0));
+    let learner = net.add(SequenceLearner::new(
+        256, 4, 8, 32, 20, 20, 2, 1, 2, false, 0,
+    ));
+    let tp = net.add(TemporalPooler::new(
+        512, 20, 20, 2, 1, 0.8, 0.5, 0.3, 0.8, 2, 0,
+    ));
+    let classifier = net.add(PatternClassifier::new(
+        2, 64, 8, 20, 2, 1, 0.8, 0.5, 0.3, 2, 0,
+    ));
+    net.connect_to_input(encoder, learner)?;
+    net.connect_to_input(learner, tp)?;
+    net.connect_to_input(tp, classifier)?;
+    net.build()?;
+    net.get_mut::<SequenceLearner>(learner)?.init()?;
+    net.get_mut::<TemporalPooler>(tp)?.init()?;
+    net.get_mut::<PatternClassifier>(classifier)?.init()?;
+    Ok((net, [encoder, learner, tp, classifier]))
+}
+
+#[test]
+fn test_temporal_pooler_sequence_classification() -> Result<()> {
+    let (mut net, [encoder, _, tp, classifier]) = sequence_network()?;
+
+    // Same elements in a different order: only the sequence tells them apart
+    let sequences: [[usize; 4]; 2] = [[0, 1, 2, 3], [3, 2, 1, 0]];
+
+    for _ in 0..30 {
+        for (label, seq) in sequences.iter().enumerate() {
+            net.get_mut::<TemporalPooler>(tp)?.reset();
+            net.get_mut::<PatternClassifier>(classifier)?
+                .set_label(label);
+            for &value in seq {
+                net.get_mut::<DiscreteTransformer>(encoder)?
+                    .set_value(value);
+                net.execute(true)?;
+            }
+        }
+    }
+
+    for (label, seq) in sequences.iter().enumerate() {
+        net.get_mut::<TemporalPooler>(tp)?.reset();
+        for &value in seq {
+            net.get_mut::<DiscreteTransformer>(encoder)?
+                .set_value(value);
+            net.execute(false)?;
+        }
+        assert_eq!(
+            net.get::<PatternClassifier>(classifier)?
+                .get_predicted_label(),
+            label
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_temporal_pooler_network_state_round_trip() -> Result<()> {
+    use gnomics::network_config::NetworkConfig;
+
+    let mut net = Network::new();
+    let encoder = net.add(DiscreteTransformer::new(8, 512, 2, 0));
+    let tp = net.add(TemporalPooler::new(
+        256, 10, 20, 2, 1, 0.8, 0.5, 0.3, 0.6, 2, 5,
+    ));
+    net.connect_to_input(encoder, tp)?;
+    net.build()?;
+    net.get_mut::<TemporalPooler>(tp)?.init()?;
+
+    for _ in 0..5 {
+        for value in SEQ_A {
+            net.get_mut::<DiscreteTransformer>(encoder)?
+                .set_value(value);
+            net.execute(true)?;
+        }
+    }
+
+    let json = net.to_config_with_state()?.to_json()?;
+    let mut loaded = Network::from_config_with_state(&NetworkConfig::from_json(&json)?)?;
+    let ids: Vec<BlockId> = loaded.block_ids().collect();
+    let restored = ids
+        .iter()
+        .copied()
+        .find(|&id| loaded.get::<TemporalPooler>(id).is_ok())
+        .unwrap();
+    let restored_encoder = ids.iter().copied().find(|&id| id != restored).unwrap();
+
+    assert_eq!(loaded.get::<TemporalPooler>(restored)?.decay(), 0.6);
+
+    net.get_mut::<TemporalPooler>(tp)?.reset();
+    for value in SEQ_A {
+        net.get_mut::<DiscreteTransformer>(encoder)?
+            .set_value(value);
+        net.execute(false)?;
+        loaded
+            .get_mut::<DiscreteTransformer>(restored_encoder)?
+            .set_value(value);
+        loaded.execute(false)?;
+    }
+    assert_eq!(
+        loaded
+            .get::<TemporalPooler>(restored)?
+            .output()
+            .borrow()
+            .state,
+        net.get::<TemporalPooler>(tp)?.output().borrow().state
+    );
+
+    Ok(())
+}